@@ -3,7 +3,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::{Error, Result};
+use anyhow::{bail, Context, Error, Result};
 use serde::{Deserialize, Serialize};
 
 use super::{dialog, Event, RpcUtils};
@@ -100,6 +100,58 @@ pub fn export_head_morph_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
     Ok(result)
 }
 
+pub fn import_head_morph_obj(utils: &RpcUtils) -> Result<Option<RpcFile>> {
+    match dialog::import_head_morph_obj(utils.window) {
+        Some(path) => open_file(path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn export_head_morph_obj_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::export_head_morph_obj(utils.window);
+    Ok(result)
+}
+
+pub fn head_morph_library(_: &RpcUtils) -> Result<Vec<RpcFile>> {
+    let library_dir = head_morph_library_dir()?;
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(library_dir)? {
+        let path = entry?.path();
+        let is_meta = path
+            .file_name()
+            .map(|name| name.to_string_lossy().ends_with(HEAD_MORPH_META_EXT))
+            .unwrap_or_default();
+        if is_meta {
+            files.push(open_file(path)?);
+        }
+    }
+    Ok(files)
+}
+
+pub fn add_to_head_morph_library(_: &RpcUtils, entry: HeadMorphLibraryEntry) -> Result<()> {
+    let HeadMorphLibraryEntry { file_stem, meta, head_morph } = entry;
+    if file_stem.is_empty() || file_stem.contains(|c: char| matches!(c, '/' | '\\' | '.')) {
+        bail!("Invalid name: {}", file_stem);
+    }
+
+    let library_dir = head_morph_library_dir()?;
+    fs::write(library_dir.join(format!("{}.ron", file_stem)), head_morph.decode()?)?;
+    fs::write(library_dir.join(format!("{}{}", file_stem, HEAD_MORPH_META_EXT)), meta.decode()?)?;
+    Ok(())
+}
+
+pub fn open_head_morph_from_library(_: &RpcUtils, meta_path: PathBuf) -> Result<RpcFile> {
+    open_file(library_head_morph_path(&meta_path)?)
+}
+
+pub fn remove_from_head_morph_library(_: &RpcUtils, meta_path: PathBuf) -> Result<()> {
+    let head_morph_path = library_head_morph_path(&meta_path)?;
+    fs::remove_file(head_morph_path)?;
+    fs::remove_file(meta_path)?;
+    Ok(())
+}
+
 pub fn load_database(_: &RpcUtils, path: PathBuf) -> Result<RpcFile> {
     #[cfg(not(debug_assertions))]
     let path = std::env::current_exe()?.parent().map(|parent| parent.join(&path)).unwrap_or(path);
@@ -108,6 +160,29 @@ pub fn load_database(_: &RpcUtils, path: PathBuf) -> Result<RpcFile> {
 }
 
 // Utils
+const HEAD_MORPH_META_EXT: &str = ".meta.ron";
+
+fn head_morph_library_dir() -> Result<PathBuf> {
+    let library_dir = dirs::data_dir()
+        .context("No data directory")?
+        .join("Trilogy Save Editor")
+        .join("Head Morphs");
+    fs::create_dir_all(&library_dir)?;
+    Ok(library_dir)
+}
+
+// Chaque head morph de la bibliothèque a un fichier `.meta.ron` à côté de lui
+fn library_head_morph_path(meta_path: &Path) -> Result<PathBuf> {
+    let library_dir = head_morph_library_dir()?;
+    let file_name = meta_path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    match file_name.strip_suffix(HEAD_MORPH_META_EXT) {
+        Some(file_stem) if meta_path.parent() == Some(library_dir.as_path()) => {
+            Ok(library_dir.join(format!("{}.ron", file_stem)))
+        }
+        _ => bail!("Not a head morph of the library: {}", meta_path.display()),
+    }
+}
+
 fn open_file(path: PathBuf) -> Result<RpcFile> {
     let file = fs::read(path.canonicalize()?)?;
     let unencoded_size = file.len();
@@ -138,6 +213,13 @@ pub struct DialogParams {
     pub filters: Vec<(String, Vec<String>)>,
 }
 
+#[derive(Deserialize, Default)]
+pub struct HeadMorphLibraryEntry {
+    pub file_stem: String,
+    pub meta: Base64File,
+    pub head_morph: Base64File,
+}
+
 #[derive(Deserialize, Serialize, Default)]
 pub struct RpcFile {
     pub path: PathBuf,
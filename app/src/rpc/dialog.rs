@@ -55,7 +55,23 @@ pub fn import_head_morph(window: &Window) -> Option<PathBuf> {
 }
 
 pub fn export_head_morph(window: &Window) -> Option<PathBuf> {
-    let dialog = rfd::FileDialog::new().add_filter("Head Morph", &["ron"]);
+    let dialog = rfd::FileDialog::new()
+        .add_filter("Head Morph", &["ron"])
+        .add_filter("Gibbed's ME2 Head Morph", &["me2headmorph"])
+        .add_filter("Gibbed's ME3 Head Morph", &["me3headmorph"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn import_head_morph_obj(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new()
+        .add_filter("Wavefront OBJ", &["obj"])
+        .add_filter("All Files", &["*"]);
+
+    with_parent(dialog, window).pick_file()
+}
+
+pub fn export_head_morph_obj(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Wavefront OBJ", &["obj"]);
     with_parent(dialog, window).save_file()
 }
 
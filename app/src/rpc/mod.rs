@@ -88,6 +88,9 @@ pub fn rpc_handler(mut req: RpcRequest, utils: RpcUtils) -> Option<RpcResponse>
             command::download_and_install_update,
             command::import_head_morph,
             command::export_head_morph_dialog,
+            command::import_head_morph_obj,
+            command::export_head_morph_obj_dialog,
+            command::head_morph_library,
         ]);
 
         call_commands_with_param!(req, utils => [
@@ -97,6 +100,9 @@ pub fn rpc_handler(mut req: RpcRequest, utils: RpcUtils) -> Option<RpcResponse>
             command::save_save_dialog,
             command::reload_save,
             command::load_database,
+            command::add_to_head_morph_library,
+            command::open_head_morph_from_library,
+            command::remove_from_head_morph_library,
         ]);
 
         bail!("Wrong RPC method, got: {}", req.method)
@@ -9,17 +9,22 @@ use crate::{
     gui::{
         components::{AutoUpdate, NavBar, Tab, TabBar, Table},
         format_code,
-        mass_effect_1::{Me1General, Me1Plot, Me1RawData, Me1RawPlot},
-        mass_effect_1_le::{Me1LeGeneral, Me1LeInventory},
+        mass_effect_1::{Me1General, Me1Inventory, Me1Plot, Me1RawData, Me1RawPlot, Me1Talents},
+        mass_effect_1_le::{
+            Me1LeExploration, Me1LeGeneral, Me1LeInventory, Me1LeShops, Me1LeWorld,
+        },
         mass_effect_2::{Me2General, Me2Plot, Me2RawPlot, Me2Type},
-        mass_effect_3::{Me3General, Me3Plot, Me3RawPlot},
+        mass_effect_3::{Me3General, Me3PlayerVariables, Me3Plot, Me3RawPlot, Me3World},
         raw_ui::RawUi,
         shared::HeadMorph,
-        shared::{FloatPlotType, IntPlotType},
+        shared::{
+            Appearance, AppearanceGame, DlcDependencies, DlcDependenciesType, FloatPlotType,
+            IntPlotType, WorldObjects, WorldObjectsType,
+        },
     },
     save_data::{
         mass_effect_1::Me1SaveGame, mass_effect_1_le::Me1LeSaveData, mass_effect_3::Me3SaveGame,
-        RcRef,
+        shared::head_morph_conversion::HeadMorphGame, RcRef,
     },
     services::{
         database::DatabaseProvider,
@@ -80,17 +85,17 @@ impl Component for App {
         let link = ctx.link();
         html! {
             <div class="h-[calc(100vh-28px)] flex flex-col">
-                <SaveHandlerProvider
-                    onnotification={link.callback(Msg::Notification)}
-                    onerror={link.callback(Msg::Error)}
-                >
-                    <NavBar>
-                        <AutoUpdate onerror={link.callback(Msg::Error)} />
-                    </NavBar>
-                    <DatabaseProvider onerror={link.callback(Msg::Error)}>
+                <DatabaseProvider onerror={link.callback(Msg::Error)}>
+                    <SaveHandlerProvider
+                        onnotification={link.callback(Msg::Notification)}
+                        onerror={link.callback(Msg::Error)}
+                    >
+                        <NavBar>
+                            <AutoUpdate onerror={link.callback(Msg::Error)} />
+                        </NavBar>
                         <SaveContent/>
-                    </DatabaseProvider>
-                </SaveHandlerProvider>
+                    </SaveHandlerProvider>
+                </DatabaseProvider>
                 { for notification }
                 { for error }
             </div>
@@ -171,11 +176,15 @@ fn save_content() -> Html {
             SaveGame::MassEffect2 { save_game, .. } => {
                 mass_effect_2(Me2Type::Vanilla(RcRef::clone(save_game)))
             }
-            SaveGame::MassEffect2Le { save_game, .. } => {
+            SaveGame::MassEffect2Le { save_game, .. }
+            | SaveGame::MassEffect2LePs4 { save_game, .. } => {
                 mass_effect_2(Me2Type::Legendary(RcRef::clone(save_game)))
             }
 
-            SaveGame::MassEffect3 { save_game, .. } => mass_effect_3(RcRef::clone(save_game)),
+            SaveGame::MassEffect3 { save_game, .. }
+            | SaveGame::MassEffect3LePs4 { save_game, .. } => {
+                mass_effect_3(RcRef::clone(save_game))
+            }
         }
     } else {
         changelog()
@@ -201,6 +210,12 @@ fn mass_effect_1(save_game: Ref<'_, Me1SaveGame>) -> Html {
                         integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
                     />
                 </Tab>
+                <Tab title="Talents">
+                    <Me1Talents player={RcRef::clone(&save_game.player)} />
+                </Tab>
+                <Tab title="Inventory">
+                    <Me1Inventory player={RcRef::clone(&save_game.player)} />
+                </Tab>
                 <Tab title="Raw Data">
                     <Me1RawData player={RcRef::clone(&save_game.player)} />
                 </Tab>
@@ -220,6 +235,29 @@ fn mass_effect_1_le(save_game: RcRef<Me1LeSaveData>) -> Html {
     let me1 = save_game.borrow();
     let plot = me1.plot();
     let head_morph = RcRef::clone(&me1.player().head_morph);
+    let is_female = me1.player().is_female();
+    let no_export = me1.no_export();
+    let shops = no_export.as_ref().map(|no_export| {
+        html_nested! {
+            <Tab title="Merchants">
+                <Me1LeShops no_export={RcRef::clone(no_export)} />
+            </Tab>
+        }
+    });
+    let world = no_export.as_ref().map(|no_export| {
+        html_nested! {
+            <Tab title="World">
+                <Me1LeWorld no_export={RcRef::clone(no_export)} />
+            </Tab>
+        }
+    });
+    let exploration = no_export.as_ref().map(|no_export| {
+        html_nested! {
+            <Tab title="Exploration">
+                <Me1LeExploration no_export={RcRef::clone(no_export)} />
+            </Tab>
+        }
+    });
 
     html! {
         <section class="flex-auto flex p-1">
@@ -239,8 +277,11 @@ fn mass_effect_1_le(save_game: RcRef<Me1LeSaveData>) -> Html {
                         squad={RcRef::clone(&me1.squad)}
                     />
                 </Tab>
+                { for shops }
+                { for world }
+                { for exploration }
                 <Tab title="Head Morph">
-                    <HeadMorph {head_morph} />
+                    <HeadMorph {head_morph} game={HeadMorphGame::Me1} {is_female} />
                 </Tab>
                 <Tab title="Raw Data">
                     { save_game.view_opened("Mass Effect 1", true) }
@@ -258,18 +299,22 @@ fn mass_effect_1_le(save_game: RcRef<Me1LeSaveData>) -> Html {
 }
 
 fn mass_effect_2(save_game: Me2Type) -> Html {
-    let (raw_data, plot, me1_plot, head_morph) = match save_game {
+    let (raw_data, plot, me1_plot, appearance, head_morph, is_female) = match save_game {
         Me2Type::Vanilla(ref me2) => (
             me2.view_opened("Mass Effect 2", true),
             RcRef::clone(&me2.borrow().plot),
             RcRef::clone(&me2.borrow().me1_plot),
+            RcRef::clone(&me2.borrow().player().appearance),
             RcRef::clone(&me2.borrow().player().appearance().head_morph),
+            me2.borrow().player().is_female(),
         ),
         Me2Type::Legendary(ref me2) => (
             me2.view_opened("Mass Effect 2", true),
             RcRef::clone(&me2.borrow().plot),
             RcRef::clone(&me2.borrow().me1_plot),
+            RcRef::clone(&me2.borrow().player().appearance),
             RcRef::clone(&me2.borrow().player().appearance().head_morph),
+            me2.borrow().player().is_female(),
         ),
     };
     let (plot, me1_plot) = (plot.borrow(), me1_plot.borrow());
@@ -288,8 +333,17 @@ fn mass_effect_2(save_game: Me2Type) -> Html {
                         me1_integers={IntPlotType::Vec(RcRef::clone(&me1_plot.integers))}
                     />
                 </Tab>
+                <Tab title="Appearance">
+                    <Appearance {appearance} game={AppearanceGame::Me2} {is_female} />
+                </Tab>
                 <Tab title="Head Morph">
-                    <HeadMorph {head_morph} />
+                    <HeadMorph {head_morph} game={HeadMorphGame::Me2} {is_female} />
+                </Tab>
+                <Tab title="Objects">
+                    <WorldObjects save_game={WorldObjectsType::Me2(Me2Type::clone(&save_game))} />
+                </Tab>
+                <Tab title="DLC">
+                    <DlcDependencies save_game={DlcDependenciesType::Me2(Me2Type::clone(&save_game))} />
                 </Tab>
                 <Tab title="Raw Data">
                     { raw_data }
@@ -309,7 +363,9 @@ fn mass_effect_2(save_game: Me2Type) -> Html {
 fn mass_effect_3(save_game: RcRef<Me3SaveGame>) -> Html {
     let me3 = save_game.borrow();
     let plot = me3.plot();
+    let appearance = RcRef::clone(&me3.player().appearance);
     let head_morph = RcRef::clone(&me3.player().appearance().head_morph);
+    let is_female = me3.player().is_female();
 
     html! {
         <section class="flex-auto flex p-1">
@@ -324,8 +380,23 @@ fn mass_effect_3(save_game: RcRef<Me3SaveGame>) -> Html {
                         variables={RcRef::clone(&me3.player_variables)}
                     />
                 </Tab>
+                <Tab title="Player Variables">
+                    <Me3PlayerVariables variables={RcRef::clone(&me3.player_variables)} />
+                </Tab>
+                <Tab title="World">
+                    <Me3World save_game={RcRef::clone(&save_game)} />
+                </Tab>
+                <Tab title="Appearance">
+                    <Appearance {appearance} game={AppearanceGame::Me3} {is_female} />
+                </Tab>
                 <Tab title="Head Morph">
-                    <HeadMorph {head_morph} />
+                    <HeadMorph {head_morph} game={HeadMorphGame::Me3} {is_female} />
+                </Tab>
+                <Tab title="Objects">
+                    <WorldObjects save_game={WorldObjectsType::Me3(RcRef::clone(&save_game))} />
+                </Tab>
+                <Tab title="DLC">
+                    <DlcDependencies save_game={DlcDependenciesType::Me3(RcRef::clone(&save_game))} />
                 </Tab>
                 <Tab title="Raw Data">
                     { save_game.view_opened("Mass Effect 3", true) }
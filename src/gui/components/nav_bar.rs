@@ -6,7 +6,7 @@ use crate::{
     gui::components::{Tab, TabBar},
     services::{
         rpc,
-        save_handler::{Action, SaveHandler},
+        save_handler::{Action, SaveGame, SaveHandler},
     },
 };
 
@@ -19,6 +19,7 @@ pub enum Msg {
     OpenSave,
     SaveSave,
     ReloadSave,
+    ConvertToLegendary,
     MenuOpen,
     MenuClose,
     MenuBlur,
@@ -77,6 +78,10 @@ impl Component for NavBar {
                 self.save_handler.action(Action::ReloadSave);
                 false
             }
+            Msg::ConvertToLegendary => {
+                self.save_handler.action(Action::ConvertToLegendary);
+                false
+            }
             // Menus
             Msg::MenuOpen => {
                 self.about_opened = true;
@@ -118,6 +123,19 @@ impl Component for NavBar {
                 </button>
             </> }
         });
+        let convert_button = self
+            .save_handler
+            .save_game
+            .as_deref()
+            .filter(|save_game| matches!(save_game, SaveGame::MassEffect1 { .. }))
+            .map(|_| {
+                html! { <>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ConvertToLegendary)}>
+                        {"Convert to Legendary"}
+                    </button>
+                </> }
+            });
 
         html! {
             <nav class="bg-menu-bar select-none flex">
@@ -126,6 +144,7 @@ impl Component for NavBar {
                         {"Open"}
                     </button>
                     { for loaded_buttons }
+                    { for convert_button }
                     { self.view_about_menu(ctx) }
                 </div>
                 { ctx.props().children.clone() }
@@ -1,6 +1,44 @@
+use std::rc::Rc;
+
 use gloo::utils;
 use web_sys::HtmlElement;
-use yew::prelude::*;
+use yew::{html::IntoPropValue, prelude::*};
+
+// Options connues à la compilation ou tirées d'une base de données
+#[derive(Clone, PartialEq)]
+pub enum SelectOptions {
+    Static(&'static [&'static str]),
+    Owned(Rc<Vec<String>>),
+}
+
+impl SelectOptions {
+    fn get(&self, idx: usize) -> Option<&str> {
+        match self {
+            SelectOptions::Static(options) => options.get(idx).copied(),
+            SelectOptions::Owned(options) => options.get(idx).map(String::as_str),
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        let len = match self {
+            SelectOptions::Static(options) => options.len(),
+            SelectOptions::Owned(options) => options.len(),
+        };
+        (0..len).filter_map(move |idx| self.get(idx))
+    }
+}
+
+impl IntoPropValue<SelectOptions> for &'static [&'static str] {
+    fn into_prop_value(self) -> SelectOptions {
+        SelectOptions::Static(self)
+    }
+}
+
+impl IntoPropValue<SelectOptions> for Vec<String> {
+    fn into_prop_value(self) -> SelectOptions {
+        SelectOptions::Owned(Rc::new(self))
+    }
+}
 
 pub enum Msg {
     Open,
@@ -11,7 +49,7 @@ pub enum Msg {
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
-    pub options: &'static [&'static str],
+    pub options: SelectOptions,
     pub current_idx: usize,
     pub onselect: Callback<usize>,
     #[prop_or(true)]
@@ -147,7 +185,7 @@ impl Component for Select {
                     ]}
                     {onclick}
                 >
-                    { ctx.props().options[self.current_idx] }
+                    { ctx.props().options.get(self.current_idx).unwrap_or_default() }
                 </a>
                 <div
                     class={classes![
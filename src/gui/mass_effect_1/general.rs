@@ -59,13 +59,8 @@ impl Component for Me1General {
                 // Then find game options
                 // Then find difficulty option
                 let value = player
-                    .objects
-                    .iter()
-                    .enumerate()
-                    .find_map(|(i, object)| {
-                        let object_name = player.get_name(object.object_name_id);
-                        (object_name == "CurrentGame").then(|| player.get_data(i as i32 + 1))
-                    })
+                    .find_object("CurrentGame")
+                    .map(|object_id| player.get_data(object_id))
                     .and_then(|current_game| {
                         let m_game_options =
                             Self::find_property(ctx, &current_game.properties, "m_GameOptions")?
@@ -108,10 +103,7 @@ impl Me1General {
     fn try_view(ctx: &Context<Self>) -> Option<Html> {
         let player = ctx.props().player();
 
-        let current_game = player.objects.iter().enumerate().find_map(|(i, object)| {
-            let object_name = player.get_name(object.object_name_id);
-            (object_name == "CurrentGame").then(|| player.get_data(i as i32 + 1))
-        })?;
+        let current_game = player.get_data(player.find_object("CurrentGame")?);
 
         let m_player = {
             let object_id = Self::find_object_id(ctx, &current_game.properties, "m_Player")?;
@@ -263,32 +255,12 @@ impl Me1General {
     fn find_property<'a>(
         ctx: &Context<Self>, properties: &'a List<RcRef<DataProperty>>, property_name: &str,
     ) -> Option<&'a RcRef<DataProperty>> {
-        let player = ctx.props().player();
-        properties.iter().find_map(|property| match *property.borrow() {
-            DataProperty::Array { name_id, .. }
-            | DataProperty::Bool { name_id, .. }
-            | DataProperty::Byte { name_id, .. }
-            | DataProperty::Float { name_id, .. }
-            | DataProperty::Int { name_id, .. }
-            | DataProperty::Name { name_id, .. }
-            | DataProperty::Object { name_id, .. }
-            | DataProperty::Str { name_id, .. }
-            | DataProperty::StringRef { name_id, .. }
-            | DataProperty::Struct { name_id, .. }
-            | DataProperty::None { name_id, .. } => {
-                (player.get_name(name_id) == property_name).then(|| property)
-            }
-        })
+        ctx.props().player().find_property(properties, property_name)
     }
 
     fn find_object_id(
         ctx: &Context<Self>, properties: &List<RcRef<DataProperty>>, property_name: &str,
     ) -> Option<i32> {
-        Self::find_property(ctx, properties, property_name).and_then(|property| {
-            match *property.borrow() {
-                DataProperty::Object { object_id, .. } => Some(object_id),
-                _ => None,
-            }
-        })
+        ctx.props().player().find_object_id(properties, property_name)
     }
 }
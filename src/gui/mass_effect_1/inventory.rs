@@ -0,0 +1,342 @@
+use std::{cell::Ref, rc::Rc};
+
+use yew::{context::ContextHandle, prelude::*};
+
+use super::henchman_name;
+use crate::{
+    gui::{
+        components::{Select, Table},
+        mass_effect_1_le::ItemSelect,
+    },
+    save_data::{
+        mass_effect_1::{
+            data::{ArrayType, Property as DataProperty, PropertyValue},
+            player::Player,
+        },
+        mass_effect_1_le::{
+            item_db::{DbItem, Me1ItemDb},
+            player::ItemLevel,
+        },
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    ChangeItem(i32, DbItem),
+    ChangeItemLevel(i32, usize),
+    RemoveItem(RcRef<DataProperty>, usize),
+    AddItem(RcRef<DataProperty>),
+    DismissError,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub player: RcRef<Player>,
+}
+
+impl Props {
+    fn player(&self) -> Ref<'_, Player> {
+        self.player.borrow()
+    }
+}
+
+pub struct Me1Inventory {
+    _db_handle: ContextHandle<Databases>,
+    item_db: Option<Rc<Me1ItemDb>>,
+    error: Option<String>,
+}
+
+impl Component for Me1Inventory {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me1Inventory { _db_handle, item_db: databases.get_me1_item_db(), error: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.item_db = dbs.get_me1_item_db();
+                true
+            }
+            Msg::ChangeItem(object_id, new_item) => {
+                let mut player = ctx.props().player.borrow_mut();
+                Self::set_int(&mut player, object_id, "m_nID", new_item.item_id);
+                Self::set_int(&mut player, object_id, "m_manufacturer", new_item.manufacturer_id);
+                false
+            }
+            Msg::ChangeItemLevel(object_id, item_level) => {
+                let mut player = ctx.props().player.borrow_mut();
+                let property = player.find_or_add_property(
+                    object_id,
+                    "m_eSophistication",
+                    PropertyValue::Byte(0),
+                );
+                let property = property.borrow();
+                match *property {
+                    DataProperty::Byte { ref value, .. } => {
+                        value.set(item_level as u8);
+                        false
+                    }
+                    _ => {
+                        self.error = Some(
+                            "Cannot change the item level, `m_eSophistication` is not a byte"
+                                .to_owned(),
+                        );
+                        true
+                    }
+                }
+            }
+            Msg::RemoveItem(item_list, idx) => {
+                item_list.borrow_mut().remove_array_item(idx);
+                true
+            }
+            Msg::AddItem(item_list) => {
+                // Un objet vide, l'arme / armure est choisie ensuite
+                let mut player = ctx.props().player.borrow_mut();
+                match player.add_object("BioItemXModdableSaveObject") {
+                    Ok(object_id) => {
+                        item_list.borrow_mut().add_array_item(ArrayType::Object(object_id));
+                    }
+                    Err(err) => self.error = Some(format!("Cannot add an item: {}", err)),
+                }
+                true
+            }
+            Msg::DismissError => {
+                self.error = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.item_db.is_some() {
+            let error = self.error.as_ref().map(|error| {
+                html! {
+                    <div class="flex items-center gap-1">
+                        <p class="flex-auto">{ error }</p>
+                        <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                            onclick={ctx.link().callback(|_| Msg::DismissError)}
+                        >
+                            {"dismiss"}
+                        </button>
+                    </div>
+                }
+            });
+            html! {
+                <>
+                    { for error }
+                    { for self.try_view(ctx) }
+                </>
+            }
+        } else {
+            html! {
+                <>
+                    <p>{ "Loading database..." }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        }
+    }
+}
+
+impl Me1Inventory {
+    fn try_view(&self, ctx: &Context<Self>) -> Option<Html> {
+        let player = ctx.props().player();
+
+        let current_game = player.get_data(player.find_object("CurrentGame")?);
+        let m_player = player.find_object_id(&current_game.properties, "m_Player")?;
+        let m_inventory = {
+            let m_squad =
+                player.find_object_id(&player.get_data(m_player).properties, "m_Squad")?;
+            let object_id =
+                player.find_object_id(&player.get_data(m_squad).properties, "m_Inventory")?;
+            player.get_data(object_id)
+        };
+
+        let squad = player.henchmen().into_iter().map(|(tag, object_id)| {
+            html! {
+                <div class="mt-1">
+                    { self.character(ctx, henchman_name(&tag), object_id) }
+                </div>
+            }
+        });
+
+        let inventory = player
+            .find_property(&m_inventory.properties, "m_aItem")
+            .map(|m_a_item| self.item_list(ctx, "Inventory", m_a_item));
+        let buy_pack = player
+            .find_property(&current_game.properties, "m_savedBuybackItems")
+            .map(|buy_pack| self.item_list(ctx, "Buy pack items", buy_pack));
+
+        Some(html! {
+            <div class="flex divide-solid divide-x divide-default-border">
+                <div class="flex-1 pr-1 min-w-0">
+                    { self.character(ctx, "Player", m_player) }
+                    { for squad }
+                </div>
+                <div class="flex-1 flex flex-col gap-1 pl-1 min-w-0">
+                    { for inventory }
+                    { for buy_pack }
+                </div>
+            </div>
+        })
+    }
+
+    fn character(&self, ctx: &Context<Self>, name: &str, object_id: i32) -> Html {
+        let player = ctx.props().player();
+        let pawn = player.get_data(object_id);
+
+        let slots = |property_name| {
+            player
+                .find_property(&pawn.properties, property_name)
+                .map(|property| Self::object_ids(&property.borrow()))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|object_id| {
+                    html! {
+                        <div class="flex items-center gap-1 min-w-0">
+                            { self.item_view(ctx, object_id) }
+                        </div>
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        let equipment = slots("m_aEquipped");
+        let quick_slots = slots("m_QuickSlotArray");
+
+        html! {
+            <div class="flex flex-col gap-1">
+                <div>
+                    <p>{ name }</p>
+                    <hr class="border-t border-default-border" />
+                </div>
+                <Table title="Equipement">
+                    { for equipment }
+                </Table>
+                <Table title="Quick slots">
+                    { for quick_slots }
+                </Table>
+            </div>
+        }
+    }
+
+    fn item_list(&self, ctx: &Context<Self>, title: &str, item_list: &RcRef<DataProperty>) -> Html {
+        let link = ctx.link();
+        let items = Self::object_ids(&item_list.borrow()).into_iter().enumerate().map(
+            |(idx, object_id)| {
+                let onremove = {
+                    let item_list = RcRef::clone(item_list);
+                    link.callback(move |_| Msg::RemoveItem(RcRef::clone(&item_list), idx))
+                };
+                html! {
+                    <div class="flex items-center gap-1 min-w-0">
+                        <div class="py-px">
+                            <a class={classes![
+                                    "rounded-none",
+                                    "select-none",
+                                    "hover:bg-theme-hover",
+                                    "active:bg-theme-active",
+                                    "bg-theme-bg",
+                                    "px-1",
+                                    "py-0",
+                                    "cursor-pointer",
+                                ]}
+                                onclick={onremove}
+                            >
+                                {"remove"}
+                            </a>
+                        </div>
+                        { self.item_view(ctx, object_id) }
+                    </div>
+                }
+            },
+        );
+        let onadd = {
+            let item_list = RcRef::clone(item_list);
+            link.callback(move |_| Msg::AddItem(RcRef::clone(&item_list)))
+        };
+
+        html! {
+            <Table title={title.to_owned()}>
+                { for items }
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                    onclick={onadd}
+                >
+                    {"add"}
+                </button>
+            </Table>
+        }
+    }
+
+    fn item_view(&self, ctx: &Context<Self>, object_id: i32) -> Html {
+        if object_id <= 0 {
+            return html! { "Empty" };
+        }
+
+        let player = ctx.props().player();
+        let properties = &player.get_data(object_id).properties;
+        let int_value = |property_name| {
+            player.find_property(properties, property_name).and_then(|p| match *p.borrow() {
+                DataProperty::Int { ref value, .. } => Some(value.get()),
+                _ => None,
+            })
+        };
+        let current_item = DbItem {
+            item_id: int_value("m_nID").unwrap_or_default(),
+            manufacturer_id: int_value("m_manufacturer").unwrap_or_default(),
+        };
+        let current_level = player
+            .find_property(properties, "m_eSophistication")
+            .and_then(|p| match *p.borrow() {
+                DataProperty::Byte { ref value, .. } => Some(value.get() as usize),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        html! {
+            <>
+                <ItemSelect
+                    item_db={Rc::clone(self.item_db.as_ref().unwrap())}
+                    {current_item}
+                    onselect={ctx.link().callback(move |new_item| Msg::ChangeItem(object_id, new_item))}
+                />
+                <Select
+                    options={ItemLevel::variants()}
+                    current_idx={current_level}
+                    onselect={ctx.link().callback(move |idx| Msg::ChangeItemLevel(object_id, idx))}
+                    sized=false
+                />
+            </>
+        }
+    }
+
+    fn object_ids(property: &DataProperty) -> Vec<i32> {
+        match property {
+            DataProperty::Array { array, .. } => array
+                .iter()
+                .filter_map(|item| match item {
+                    ArrayType::Object(object_id) => Some(*object_id),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn set_int(player: &mut Player, object_id: i32, property_name: &str, new_value: i32) {
+        let property = player.find_or_add_property(object_id, property_name, PropertyValue::Int(0));
+        if let DataProperty::Int { ref value, .. } = *property.borrow() {
+            value.set(new_value);
+        };
+    }
+}
@@ -1,6 +1,20 @@
 mod general;
+mod inventory;
 mod plot;
 mod raw_data;
 mod raw_plot;
+mod talents;
 
-pub use self::{general::*, plot::*, raw_data::*, raw_plot::*};
+pub use self::{general::*, inventory::*, plot::*, raw_data::*, raw_plot::*, talents::*};
+
+fn henchman_name(tag: &str) -> &'static str {
+    match tag {
+        "hench_asari" => "Liara",
+        "hench_humanfemale" => "Ashley",
+        "hench_humanmale" => "Kaidan",
+        "hench_krogan" => "Wrex",
+        "hench_quarian" => "Tali",
+        "hench_turian" => "Garrus",
+        _ => "Jenkins",
+    }
+}
@@ -1,3 +1,4 @@
+mod new_property;
 pub mod property;
 
 use std::cell::Ref;
@@ -5,10 +6,24 @@ use std::cell::Ref;
 use yew::prelude::*;
 
 use crate::{
-    gui::{components::Table, mass_effect_1::raw_data::property::Property},
-    save_data::{mass_effect_1::player::Player, RcRef},
+    gui::{
+        components::Table,
+        mass_effect_1::raw_data::{
+            new_property::PropertyType,
+            property::{create_property, view_property_list},
+        },
+    },
+    save_data::{
+        mass_effect_1::{data::Property as DataProperty, player::Player},
+        RcRef,
+    },
 };
 
+pub enum Msg {
+    AddProperty(String, PropertyType),
+    RemoveProperty(RcRef<DataProperty>),
+}
+
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub player: RcRef<Player>,
@@ -23,40 +38,56 @@ impl Props {
 pub struct Me1RawData;
 
 impl Component for Me1RawData {
-    type Message = ();
+    type Message = Msg;
     type Properties = Props;
 
     fn create(_ctx: &Context<Self>) -> Self {
         Me1RawData {}
     }
 
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let object_id = Self::current_game_id(&ctx.props().player());
+        match msg {
+            Msg::AddProperty(name, property_type) => {
+                let property = create_property(&ctx.props().player(), &name, property_type);
+                let mut player = ctx.props().player.borrow_mut();
+                player.get_data_mut(object_id).properties.add_property(property);
+                true
+            }
+            Msg::RemoveProperty(property) => {
+                let mut player = ctx.props().player.borrow_mut();
+                player.get_data_mut(object_id).properties.remove_property(&property);
+                true
+            }
+        }
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let player = ctx.props().player();
-        let object_id = player
-            .objects
-            .iter()
-            .enumerate()
-            .find_map(|(idx, object)| {
-                (player.get_name(object.object_name_id) == "CurrentGame").then(|| idx as i32 + 1)
-            })
-            .unwrap_or_default();
+        let object_id = Self::current_game_id(&player);
 
         let properties = &player.get_data(object_id).properties;
-        let len = properties.len();
-        let take = if len > 0 { len - 1 } else { 0 };
-        let properties = properties.iter().take(take).map(|property| {
-            html! {
-                <Property
-                    player={RcRef::clone(&ctx.props().player)}
-                    property={RcRef::clone(property)}
-                />
-            }
-        });
+        let onremove = ctx.link().callback(Msg::RemoveProperty);
+        let onadd =
+            ctx.link().callback(|(name, property_type)| Msg::AddProperty(name, property_type));
 
         html! {
             <Table>
-                { for properties }
+                { view_property_list(&ctx.props().player, properties, onremove, onadd) }
             </Table>
         }
     }
 }
+
+impl Me1RawData {
+    fn current_game_id(player: &Player) -> i32 {
+        player
+            .objects
+            .iter()
+            .enumerate()
+            .find_map(|(idx, object)| {
+                (player.get_name(object.object_name_id) == "CurrentGame").then(|| idx as i32 + 1)
+            })
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,87 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::gui::components::Select;
+
+#[derive(Clone, Copy, RawUi)]
+pub enum PropertyType {
+    Array,
+    Bool,
+    Byte,
+    Float,
+    Int,
+    Name,
+    Str,
+    StringRef,
+}
+
+pub enum Msg {
+    Name(InputEvent),
+    Type(usize),
+    Add,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub onadd: Callback<(String, PropertyType)>,
+}
+
+pub struct NewProperty {
+    name: String,
+    property_type: PropertyType,
+}
+
+impl Component for NewProperty {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        NewProperty { name: String::new(), property_type: PropertyType::Int }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Name(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.name = input.value();
+                }
+                false
+            }
+            Msg::Type(idx) => {
+                self.property_type = PropertyType::from(idx);
+                false
+            }
+            Msg::Add => {
+                let name = self.name.trim();
+                if name.is_empty() {
+                    return false;
+                }
+                ctx.props().onadd.emit((name.to_owned(), self.property_type));
+                self.name.clear();
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <form class="flex items-center gap-1"
+                onsubmit={ctx.link().callback(|e: FocusEvent| {
+                    e.prevent_default();
+                    Msg::Add
+                })}
+            >
+                <Select
+                    options={PropertyType::variants()}
+                    current_idx={self.property_type as usize}
+                    onselect={ctx.link().callback(Msg::Type)}
+                />
+                <input type="text" class="input w-1/3" placeholder="m_PropertyName"
+                    value={self.name.clone()}
+                    oninput={ctx.link().callback(Msg::Name)}
+                />
+                <input type="submit" class="button" value="Add property" />
+            </form>
+        }
+    }
+}
@@ -5,19 +5,31 @@ use yew::prelude::*;
 use crate::{
     gui::{
         components::{raw_ui::RawUiStruct, CallbackType, InputText},
+        mass_effect_1::raw_data::new_property::{NewProperty, PropertyType},
         raw_ui::RawUi,
     },
     save_data::{
         mass_effect_1::{
-            data::{ArrayType, Property as DataProperty, StructType},
+            data::{ArrayType, Property as DataProperty, PropertyValue, StructType},
             player::Player,
         },
         List, RcCell, RcRef,
     },
 };
 
+#[derive(Clone, Copy)]
+pub enum PropertyList {
+    Data(i32),
+    Struct,
+    ArrayItem(usize),
+}
+
 pub enum Msg {
     DuplicateName(RcCell<u32>, CallbackType),
+    AddArrayItem,
+    RemoveArrayItem(usize),
+    AddProperty(PropertyList, String, PropertyType),
+    RemoveProperty(PropertyList, RcRef<DataProperty>),
 }
 
 #[derive(Properties, PartialEq)]
@@ -65,7 +77,31 @@ impl Component for Property {
 
                 true
             }
-            _ => unreachable!(),
+            Msg::DuplicateName(..) => unreachable!(),
+            Msg::AddArrayItem => {
+                let item = ctx.props().player().new_array_item(&ctx.props().property());
+                if let Some(item) = item {
+                    ctx.props().property.borrow_mut().add_array_item(item);
+                }
+                true
+            }
+            Msg::RemoveArrayItem(idx) => {
+                ctx.props().property.borrow_mut().remove_array_item(idx);
+                true
+            }
+            Msg::AddProperty(list, name, property_type) => {
+                let property = create_property(&ctx.props().player(), &name, property_type);
+                self.edit_list(ctx, list, |properties| {
+                    properties.add_property(property);
+                });
+                true
+            }
+            Msg::RemoveProperty(list, property) => {
+                self.edit_list(ctx, list, |properties| {
+                    properties.remove_property(&property);
+                });
+                true
+            }
         }
     }
 
@@ -103,32 +139,59 @@ impl Component for Property {
             DataProperty::Array { name_id, array, .. } => {
                 let label = get_name(name_id);
 
-                let items = array.iter().enumerate().map(|(idx, item)| match item {
-                    ArrayType::Int(int) => int.view(&idx.to_string()),
-                    ArrayType::Object(object_id) => {
-                        if *object_id != 0 {
-                            // Object
-                            let object = player.get_object(*object_id);
-                            let object_name = player.get_name(object.object_name_id);
-
-                            let label = format!("{} : {}", object_name, idx);
-                            let properties = &player.get_data(*object_id).properties;
-                            self.view_properties(ctx, label, properties)
-                        } else {
-                            // Null
-                            html! { "Null" }
+                let items = array.iter().enumerate().map(|(idx, item)| {
+                    let item = match item {
+                        ArrayType::Int(int) => int.view(&idx.to_string()),
+                        ArrayType::Object(object_id) => {
+                            if *object_id != 0 {
+                                // Object
+                                let object = player.get_object(*object_id);
+                                let object_name = player.get_name(object.object_name_id);
+
+                                let label = format!("{} : {}", object_name, idx);
+                                let properties = &player.get_data(*object_id).properties;
+                                let list = PropertyList::Data(*object_id);
+                                self.view_properties(ctx, label, properties, list)
+                            } else {
+                                // Null
+                                html! { "Null" }
+                            }
                         }
-                    }
-                    ArrayType::Vector(vector) => vector.view(&idx.to_string()),
-                    ArrayType::String(string) => string.view(&idx.to_string()),
-                    ArrayType::Properties(properties) => {
-                        self.view_properties(ctx, idx.to_string(), properties)
+                        ArrayType::Vector(vector) => vector.view(&idx.to_string()),
+                        ArrayType::String(string) => string.view(&idx.to_string()),
+                        // Lecture seule, le format est inconnu
+                        ArrayType::Raw(raw) => {
+                            let bytes = raw.iter().map(|byte| format!("{:02X}", byte));
+                            html! {
+                                <div class="flex gap-1 cursor-default">
+                                    <span class="font-mono">{ bytes.collect::<Vec<_>>().join(" ") }</span>
+                                    { idx }
+                                </div>
+                            }
+                        }
+                        ArrayType::Properties(properties) => {
+                            let list = PropertyList::ArrayItem(idx);
+                            self.view_properties(ctx, idx.to_string(), properties, list)
+                        }
+                    };
+
+                    let onclick = ctx.link().callback(move |_| Msg::RemoveArrayItem(idx));
+                    html! {
+                        <div class="flex gap-1">
+                            { view_remove(onclick) }
+                            { item }
+                        </div>
                     }
                 });
 
                 html! {
                     <RawUiStruct {label}>
                         { for items }
+                        <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                            onclick={ctx.link().callback(|_| Msg::AddArrayItem)}
+                        >
+                            {"add"}
+                        </button>
                     </RawUiStruct>
                 }
             }
@@ -179,7 +242,8 @@ impl Component for Property {
 
                         let label = format!("{} : {}", object_name, label);
                         let properties = &player.get_data(*object_id).properties;
-                        self.view_properties(ctx, label, properties)
+                        let list = PropertyList::Data(*object_id);
+                        self.view_properties(ctx, label, properties, list)
                     }
                     Ordering::Less => {
                         // Class
@@ -211,7 +275,7 @@ impl Component for Property {
                     StructType::Vector(vector) => vector.view(&label),
                     StructType::Rotator(rotator) => rotator.view(&label),
                     StructType::Properties(properties) => {
-                        self.view_properties(ctx, label, properties)
+                        self.view_properties(ctx, label, properties, PropertyList::Struct)
                     }
                 }
             }
@@ -223,22 +287,108 @@ impl Component for Property {
 impl Property {
     fn view_properties(
         &self, ctx: &Context<Self>, label: String, properties: &List<RcRef<DataProperty>>,
+        list: PropertyList,
     ) -> Html {
-        let len = properties.len();
-        let take = if len > 0 { len - 1 } else { 0 };
-        let properties = properties.iter().take(take).map(|property| {
-            html! {
-                <Property
-                    player={RcRef::clone(&ctx.props().player)}
-                    property={RcRef::clone(property)}
-                />
-            }
-        });
+        let link = ctx.link();
+        let onremove = link.callback(move |property| Msg::RemoveProperty(list, property));
+        let onadd =
+            link.callback(move |(name, property_type)| Msg::AddProperty(list, name, property_type));
 
         html! {
             <RawUiStruct {label}>
-                { for properties }
+                { view_property_list(&ctx.props().player, properties, onremove, onadd) }
             </RawUiStruct>
         }
     }
+
+    fn edit_list<F>(&self, ctx: &Context<Self>, list: PropertyList, f: F)
+    where
+        F: FnOnce(&mut List<RcRef<DataProperty>>),
+    {
+        match list {
+            PropertyList::Data(object_id) => {
+                f(&mut ctx.props().player.borrow_mut().get_data_mut(object_id).properties)
+            }
+            PropertyList::Struct => {
+                if let DataProperty::Struct {
+                    struct_type: StructType::Properties(properties),
+                    ..
+                } = &mut *ctx.props().property.borrow_mut()
+                {
+                    f(properties)
+                }
+            }
+            PropertyList::ArrayItem(idx) => {
+                if let DataProperty::Array { array, .. } = &mut *ctx.props().property.borrow_mut() {
+                    if let Some(ArrayType::Properties(properties)) = array.get_mut(idx) {
+                        f(properties)
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn create_property(player: &Player, name: &str, property_type: PropertyType) -> DataProperty {
+    let value = match property_type {
+        PropertyType::Array => PropertyValue::Array,
+        PropertyType::Bool => PropertyValue::Bool(false),
+        PropertyType::Byte => PropertyValue::Byte(0),
+        PropertyType::Float => PropertyValue::Float(0.0),
+        PropertyType::Int => PropertyValue::Int(0),
+        PropertyType::Name => PropertyValue::Name(player.add_name("None")),
+        PropertyType::Str => PropertyValue::Str(String::new()),
+        PropertyType::StringRef => PropertyValue::StringRef(0),
+    };
+    player.new_property(name, value)
+}
+
+pub fn view_property_list(
+    player: &RcRef<Player>, properties: &List<RcRef<DataProperty>>,
+    onremove: Callback<RcRef<DataProperty>>, onadd: Callback<(String, PropertyType)>,
+) -> Html {
+    // Le dernier est toujours None
+    let len = properties.len();
+    let take = if len > 0 { len - 1 } else { 0 };
+    let properties = properties.iter().take(take).map(|property| {
+        let onclick = {
+            let property = RcRef::clone(property);
+            onremove.reform(move |_| RcRef::clone(&property))
+        };
+        html! {
+            <div class="flex gap-1">
+                { view_remove(onclick) }
+                <Property
+                    player={RcRef::clone(player)}
+                    property={RcRef::clone(property)}
+                />
+            </div>
+        }
+    });
+
+    html! { <>
+        { for properties }
+        <NewProperty {onadd} />
+    </> }
+}
+
+fn view_remove(onclick: Callback<MouseEvent>) -> Html {
+    html! {
+        <div class="py-px">
+            <a class={classes![
+                    "rounded-none",
+                    "select-none",
+                    "hover:bg-theme-hover",
+                    "active:bg-theme-active",
+                    "bg-theme-bg",
+                    "px-1",
+                    "py-0",
+                    "cursor-pointer",
+                ]}
+                {onclick}
+            >
+                {"remove"}
+            </a>
+        </div>
+    }
 }
@@ -0,0 +1,405 @@
+use std::{cell::Ref, rc::Rc};
+
+use yew::{context::ContextHandle, prelude::*};
+
+use super::henchman_name;
+use crate::{
+    gui::{
+        components::{CallbackType, InputNumber, NumberType, Table},
+        mass_effect_1_le::BONUS_TALENTS,
+    },
+    save_data::{
+        mass_effect_1::{
+            data::{ArrayType, Property as DataProperty, PropertyValue},
+            player::Player,
+        },
+        mass_effect_1_le::{player::Me1LeClass, player_class_db::Me1LePlayerClassDb},
+        List, RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    TalentPoints(i32, CallbackType),
+    TalentRank(RcRef<DataProperty>, usize, CallbackType),
+    ResetTalents(i32),
+    ToggleBonusTalent(i32, usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub player: RcRef<Player>,
+}
+
+impl Props {
+    fn player(&self) -> Ref<'_, Player> {
+        self.player.borrow()
+    }
+}
+
+pub struct Me1Talents {
+    _db_handle: ContextHandle<Databases>,
+    player_class_db: Option<Rc<Me1LePlayerClassDb>>,
+}
+
+impl Component for Me1Talents {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me1Talents { _db_handle, player_class_db: databases.get_me1_le_player_classes() }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.player_class_db = dbs.get_me1_le_player_classes();
+                true
+            }
+            Msg::TalentPoints(object_id, CallbackType::Int(talent_points)) => {
+                Self::set_talent_points(&mut ctx.props().player.borrow_mut(), object_id, |_| {
+                    talent_points
+                });
+                false
+            }
+            Msg::TalentRank(complex_talents, idx, CallbackType::Int(rank)) => {
+                let player = ctx.props().player();
+                if let DataProperty::Array { ref mut array, .. } = *complex_talents.borrow_mut() {
+                    if let Some(ArrayType::Properties(talent)) = array.get_mut(idx) {
+                        Self::set_int(&player, talent, "m_Ranks", rank);
+                    }
+                }
+                false
+            }
+            Msg::TalentPoints(..) | Msg::TalentRank(..) => unreachable!(),
+            Msg::ResetTalents(object_id) => {
+                let mut player = ctx.props().player.borrow_mut();
+                let complex_talents = match Self::complex_talents(&player, object_id) {
+                    Some(complex_talents) => complex_talents,
+                    None => return false,
+                };
+
+                let mut spent_points = 0;
+                if let DataProperty::Array { ref mut array, .. } = *complex_talents.borrow_mut() {
+                    for talent in array.iter_mut() {
+                        if let ArrayType::Properties(talent) = talent {
+                            let ranks = Self::get_int(&player, talent, "m_Ranks");
+                            if ranks != 0 {
+                                spent_points += ranks;
+                                Self::set_int(&player, talent, "m_Ranks", 0);
+                            }
+                        }
+                    }
+                }
+
+                Self::set_talent_points(&mut player, object_id, |tp| tp + spent_points);
+                true
+            }
+            Msg::ToggleBonusTalent(object_id, talent_idx) => {
+                let mut player = ctx.props().player.borrow_mut();
+                let (complex_talents, simple_talents) = match (
+                    Self::complex_talents(&player, object_id),
+                    player
+                        .find_property(&player.get_data(object_id).properties, "m_aSimpleTalents")
+                        .cloned(),
+                ) {
+                    (Some(complex_talents), Some(simple_talents)) => {
+                        (complex_talents, simple_talents)
+                    }
+                    _ => return false,
+                };
+
+                let (complex_id, simple_ids, _) = BONUS_TALENTS[talent_idx];
+
+                let found = Self::talents(&complex_talents.borrow())
+                    .iter()
+                    .position(|talent| Self::get_int(&player, talent, "m_TalentID") == complex_id);
+
+                if let Some(idx) = found {
+                    // Les points dépensés sont rendus
+                    let spent_points = Self::talents(&complex_talents.borrow())
+                        .get(idx)
+                        .map(|talent| Self::get_int(&player, talent, "m_Ranks"))
+                        .unwrap_or_default();
+                    complex_talents.borrow_mut().remove_array_item(idx);
+
+                    if let DataProperty::Array { ref mut array, .. } = *simple_talents.borrow_mut()
+                    {
+                        array.retain(|talent| match talent {
+                            ArrayType::Properties(talent) => {
+                                let talent_id = Self::get_int(&player, talent, "m_TalentID");
+                                !simple_ids.contains(&talent_id)
+                            }
+                            _ => true,
+                        });
+                    }
+
+                    Self::set_talent_points(&mut player, object_id, |tp| tp + spent_points);
+                } else {
+                    let complex = Self::new_talent(
+                        &player,
+                        &[
+                            ("m_TalentID", complex_id),
+                            ("m_Ranks", 0),
+                            ("m_MaxRank", 12),
+                            ("m_LevelOffset", -1),
+                            ("m_LevelsPerRank", 1),
+                            ("m_VisualOrder", 85),
+                        ],
+                        &["m_PrereqTalentIDArray", "m_PrereqTalentRankArray"],
+                    );
+                    complex_talents.borrow_mut().add_array_item(complex);
+
+                    for &simple_id in simple_ids {
+                        let simple = Self::new_talent(
+                            &player,
+                            &[("m_TalentID", simple_id), ("m_Ranks", 1)],
+                            &[],
+                        );
+                        simple_talents.borrow_mut().add_array_item(simple);
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.player_class_db.is_some() {
+            html! { for self.try_view(ctx) }
+        } else {
+            html! {
+                <>
+                    <p>{ "Loading database..." }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        }
+    }
+}
+
+impl Me1Talents {
+    fn try_view(&self, ctx: &Context<Self>) -> Option<Html> {
+        let player = ctx.props().player();
+
+        let current_game = player.get_data(player.find_object("CurrentGame")?);
+        let m_player = player.find_object_id(&current_game.properties, "m_Player")?;
+
+        let squad = player.henchmen().into_iter().map(|(tag, object_id)| {
+            html! {
+                <div class="mt-1">
+                    { Self::character(ctx, henchman_name(&tag), object_id) }
+                </div>
+            }
+        });
+
+        Some(html! {
+            <div class="flex divide-solid divide-x divide-default-border">
+                <div class="flex-1 pr-1 flex flex-col gap-1 min-w-0">
+                    { Self::character(ctx, "Player", m_player) }
+                    { self.bonus_talents(ctx, m_player) }
+                </div>
+                <div class="flex-1 pl-1 min-w-0">
+                    { for squad }
+                </div>
+            </div>
+        })
+    }
+
+    fn character(ctx: &Context<Self>, name: &str, object_id: i32) -> Html {
+        let player = ctx.props().player();
+        let link = ctx.link();
+        let pawn = &player.get_data(object_id).properties;
+
+        let talent_points = Self::get_int(&player, pawn, "m_TalentPoints");
+
+        let complex_talents = Self::complex_talents(&player, object_id);
+        let talents = complex_talents.iter().flat_map(|complex_talents| {
+            let talents = Self::talents(&complex_talents.borrow());
+            talents
+                .into_iter()
+                .enumerate()
+                .map(|(idx, talent)| {
+                    let talent_id = Self::get_int(&player, &talent, "m_TalentID");
+                    let label = BONUS_TALENTS
+                        .iter()
+                        .find_map(|&(id, _, label)| (id == talent_id).then(|| label.to_owned()))
+                        .unwrap_or_else(|| format!("Talent {}", talent_id));
+                    let max_rank = Self::get_int(&player, &talent, "m_MaxRank");
+                    let rank = Self::get_int(&player, &talent, "m_Ranks");
+
+                    let complex_talents = RcRef::clone(complex_talents);
+                    html! {
+                        <InputNumber
+                            label={format!("{} (max {})", label, max_rank)}
+                            value={NumberType::Int(rank.into())}
+                            onchange={link.callback(move |rank| {
+                                Msg::TalentRank(RcRef::clone(&complex_talents), idx, rank)
+                            })}
+                        />
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        html! {
+            <div class="flex flex-col gap-1">
+                <div>
+                    <p>{ name }</p>
+                    <hr class="border-t border-default-border" />
+                </div>
+                <Table title="Talents">
+                    <InputNumber
+                        label="Talent Points"
+                        value={NumberType::Int(talent_points.into())}
+                        onchange={link.callback(move |tp| Msg::TalentPoints(object_id, tp))}
+                    />
+                    { for talents }
+                    <button class="button" onclick={link.callback(move |_| Msg::ResetTalents(object_id))}>
+                        { format!("Reset {}'s talents", name) }
+                    </button>
+                </Table>
+            </div>
+        }
+    }
+
+    fn bonus_talents(&self, ctx: &Context<Self>, object_id: i32) -> Html {
+        let player = ctx.props().player();
+        let pawn = &player.get_data(object_id).properties;
+
+        // `BIO_PARTY_MEMBER_CLASS_BASE_SOLDIER` => `Me1LeClass::Soldier`
+        let class_name =
+            player.find_property(pawn, "m_ClassBase").and_then(|p| match *p.borrow() {
+                DataProperty::Name { ref value_name_id, .. } => {
+                    Some(player.get_name(value_name_id.get()))
+                }
+                _ => None,
+            });
+        let player_class = class_name.and_then(|class_name| {
+            Me1LeClass::variants()
+                .iter()
+                .position(|variant| class_name.ends_with(&variant.to_uppercase()))
+                .map(Me1LeClass::from)
+        });
+
+        let talent_list = player_class
+            .and_then(|player_class| {
+                self.player_class_db.as_ref().unwrap().iter().find_map(|class| {
+                    (class.player_class == player_class)
+                        .then(|| class.bonus_talents.borrow().clone())
+                })
+            })
+            .unwrap_or_default();
+
+        let complex_talents = Self::complex_talents(&player, object_id)
+            .map(|complex_talents| Self::talents(&complex_talents.borrow()))
+            .unwrap_or_default();
+
+        let selectables = BONUS_TALENTS
+            .iter()
+            .enumerate()
+            .filter(|(_, (complex_id, _, _))| talent_list.contains(complex_id))
+            .map(|(i, &(complex_id, _, talent_label))| {
+                let selected = complex_talents
+                    .iter()
+                    .any(|talent| Self::get_int(&player, talent, "m_TalentID") == complex_id);
+
+                html! {
+                    <button
+                        class={classes![
+                            "rounded-none",
+                            "hover:bg-theme-hover",
+                            "active:bg-theme-active",
+                            "px-1",
+                            "w-full",
+                            "text-left",
+                            selected.then(|| "bg-theme-bg"),
+                        ]}
+                        onclick={ctx.link().callback(move |_| Msg::ToggleBonusTalent(object_id, i))}
+                    >
+                        {talent_label}
+                    </button>
+                }
+            });
+
+        html! {
+            <Table title="Bonus Talents" helper=
+                "You can use as many bonus powers as you want and customize your build \
+                to your liking.\n\
+                The only restriction is that the game will only allow you to use around \
+                5-6 offensive abilities for use in game, no matter how many abilities \
+                you add. So, don't add talent points in abilities you're not going to use."
+            >
+                { for selectables }
+            </Table>
+        }
+    }
+
+    fn complex_talents(player: &Player, object_id: i32) -> Option<RcRef<DataProperty>> {
+        player.find_property(&player.get_data(object_id).properties, "m_aComplexTalents").cloned()
+    }
+
+    fn talents(property: &DataProperty) -> Vec<List<RcRef<DataProperty>>> {
+        match property {
+            DataProperty::Array { array, .. } => array
+                .iter()
+                .filter_map(|item| match item {
+                    ArrayType::Properties(talent) => Some(talent.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn new_talent(player: &Player, ints: &[(&str, i32)], arrays: &[&str]) -> ArrayType {
+        let mut talent = List::from(vec![RcRef::new(DataProperty::none(player.add_name("None")))]);
+        for &(name, value) in ints {
+            talent.add_property(player.new_property(name, PropertyValue::Int(value)));
+        }
+        for &name in arrays {
+            talent.add_property(player.new_property(name, PropertyValue::Array));
+        }
+        ArrayType::Properties(talent)
+    }
+
+    // Les valeurs à 0 ne sont pas dans la save
+    fn get_int(player: &Player, properties: &List<RcRef<DataProperty>>, name: &str) -> i32 {
+        player
+            .find_property(properties, name)
+            .and_then(|p| match *p.borrow() {
+                DataProperty::Int { ref value, .. } => Some(value.get()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    fn set_int(
+        player: &Player, properties: &mut List<RcRef<DataProperty>>, name: &str, new_value: i32,
+    ) {
+        let property = match player.find_property(properties, name).cloned() {
+            Some(property) => property,
+            None => properties.add_property(player.new_property(name, PropertyValue::Int(0))),
+        };
+        if let DataProperty::Int { ref value, .. } = *property.borrow() {
+            value.set(new_value);
+        };
+    }
+
+    fn set_talent_points<F>(player: &mut Player, object_id: i32, f: F)
+    where
+        F: FnOnce(i32) -> i32,
+    {
+        let property =
+            player.find_or_add_property(object_id, "m_TalentPoints", PropertyValue::Int(0));
+        if let DataProperty::Int { ref value, .. } = *property.borrow() {
+            value.update(f);
+        };
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     },
 };
 
-const BONUS_TALENTS: &[(i32, &[i32], &str)] = &[
+pub const BONUS_TALENTS: &[(i32, &[i32], &str)] = &[
     (50, &[248], "Lift"),
     (49, &[247], "Throw"),
     (56, &[249], "Warp"),
@@ -0,0 +1,204 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{CheckBox, Table},
+        raw_ui::RawUi,
+    },
+    save_data::{
+        mass_effect_1_le::{galaxy_db::Me1GalaxyDb, legacy::World, NoExportData},
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Scan(RcRef<Vec<RcCell<i32>>>, i32, bool),
+    ResetCinematics,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub no_export: RcRef<NoExportData>,
+}
+
+pub struct Me1LeExploration {
+    _db_handle: ContextHandle<Databases>,
+    galaxy_db: Option<Rc<Me1GalaxyDb>>,
+}
+
+impl Component for Me1LeExploration {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me1LeExploration { _db_handle, galaxy_db: databases.get_me1_galaxy_db() }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.galaxy_db = dbs.get_me1_galaxy_db();
+                true
+            }
+            Msg::Scan(ids, id, scanned) => {
+                let mut ids = ids.borrow_mut();
+                let is_scanned = ids.iter().any(|scanned| scanned.get() == id);
+                if scanned && !is_scanned {
+                    ids.push(id.into());
+                } else if !scanned {
+                    ids.retain(|scanned| scanned.get() != id);
+                }
+                true
+            }
+            Msg::ResetCinematics => {
+                if let Some(world) = ctx.props().no_export.borrow().world() {
+                    world.borrow().cinematics_seen.borrow_mut().clear();
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let no_export = ctx.props().no_export.borrow();
+        let world = no_export.world();
+        let galaxy = world.as_ref().map(|world| match self.galaxy_db {
+            Some(ref galaxy_db) => self.galaxy(ctx, galaxy_db, world),
+            None => html! {
+                <>
+                    <p>{ "Loading database..." }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            },
+        });
+        let cinematics = world.as_ref().map(|world| self.cinematics(ctx, world));
+        let destination = world.as_ref().map(|world| {
+            html! {
+                <Table>
+                    { world.borrow().destination_area_map.view("Destination area map") }
+                </Table>
+            }
+        });
+
+        let mako = no_export.mako();
+        html! {
+            <div class="flex divide-solid divide-x divide-default-border">
+                <div class="flex-1 flex flex-col gap-1 pr-1 min-w-0">
+                    <Table title="Mako" helper=
+                        "The maximum health and shield of the Mako are not known, \
+                        the values are edited as they are stored in the save."
+                    >
+                        { mako.health.view("Health") }
+                        { mako.shield.view("Shield") }
+                    </Table>
+                    { for destination }
+                    { for cinematics }
+                </div>
+                <div class="flex-1 flex flex-col gap-1 pl-1 min-w-0">
+                    { for galaxy }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl Me1LeExploration {
+    // Les IDs absents de la base sont affichés tels quels pour pouvoir être décochés
+    fn galaxy(&self, ctx: &Context<Self>, galaxy_db: &Me1GalaxyDb, world: &RcRef<World>) -> Html {
+        let world = world.borrow();
+        let scanned = |ids: &RcRef<Vec<RcCell<i32>>>, id: i32, label: String| {
+            let value = ids.borrow().iter().any(|scanned| scanned.get() == id);
+            let onchange = {
+                let ids = RcRef::clone(ids);
+                ctx.link().callback(move |value| Msg::Scan(RcRef::clone(&ids), id, value))
+            };
+            html! {
+                <CheckBox {label} value={RcCell::new(value)} {onchange} />
+            }
+        };
+
+        let clusters = galaxy_db.iter().map(|(&cluster_id, cluster)| {
+            let systems = cluster.systems.iter().map(|(&system_id, system)| {
+                let planets = system.planets.iter().map(|(&planet_id, planet)| {
+                    html! {
+                        <div class="pl-6">
+                            { scanned(&world.scanned_planets, planet_id, planet.clone()) }
+                        </div>
+                    }
+                });
+                html! {
+                    <>
+                        { scanned(&world.scanned_systems, system_id, system.name.clone()) }
+                        { for planets }
+                    </>
+                }
+            });
+
+            html! {
+                <Table title={cluster.name.clone()} opened=false>
+                    { scanned(&world.scanned_clusters, cluster_id, "Cluster scanned".to_owned()) }
+                    { for systems }
+                </Table>
+            }
+        });
+
+        let unknown =
+            |ids: &RcRef<Vec<RcCell<i32>>>, kind: &str, is_known: &dyn Fn(&i32) -> bool| {
+                ids.borrow()
+                    .iter()
+                    .map(RcCell::get)
+                    .filter(|id| !is_known(id))
+                    .map(|id| scanned(ids, id, format!("{} {}", kind, id)))
+                    .collect::<Vec<_>>()
+            };
+        let mut unknowns =
+            unknown(&world.scanned_clusters, "Cluster", &|id| galaxy_db.contains_key(id));
+        unknowns.extend(unknown(&world.scanned_systems, "System", &|id| {
+            galaxy_db.systems().any(|(system_id, _)| system_id == id)
+        }));
+        unknowns.extend(unknown(&world.scanned_planets, "Planet", &|id| {
+            galaxy_db.planets().any(|(planet_id, _)| planet_id == id)
+        }));
+        let unknowns = (!unknowns.is_empty()).then(|| {
+            html! {
+                <Table title="Unknown IDs" opened=false>
+                    { for unknowns }
+                </Table>
+            }
+        });
+
+        html! {
+            <>
+                { for clusters }
+                { for unknowns }
+            </>
+        }
+    }
+
+    fn cinematics(&self, ctx: &Context<Self>, world: &RcRef<World>) -> Html {
+        let world = world.borrow();
+        let cinematics = world.cinematics_seen();
+        let cinematics = cinematics.iter().map(|cinematic| html! { &*cinematic.borrow() });
+
+        html! {
+            <Table title="Cinematics seen">
+                { for cinematics }
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                    onclick={ctx.link().callback(|_| Msg::ResetCinematics)}
+                >
+                    { "reset" }
+                </button>
+            </Table>
+        }
+    }
+}
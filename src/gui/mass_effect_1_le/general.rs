@@ -8,9 +8,10 @@ use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
     gui::{
-        components::{CallbackType, Helper, InputNumber, InputText, NumberType, Select, Table},
+        components::{CallbackType, Helper, InputNumber, NumberType, Select, Table},
         mass_effect_1_le::bonus_talents::BonusTalents,
         raw_ui::RawUi,
+        shared::{FaceCode, Teleport, TeleportType},
     },
     save_data::{
         mass_effect_1_le::{
@@ -20,6 +21,7 @@ use crate::{
             Me1LeSaveData,
         },
         shared::{
+            face_code::FaceCodeGame,
             player::{Notoriety, Origin},
             plot::PlotTable,
         },
@@ -106,6 +108,7 @@ impl VanguardSpec {
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 pub enum Msg {
     DatabaseLoaded(Databases),
     Gender(usize),
@@ -425,6 +428,7 @@ impl Component for Me1LeGeneral {
                         { Self::morality(save_game.plot()) }
                         { Self::resources(save_game.player()) }
                         { Self::squad(ctx, save_game.squad()) }
+                        <Teleport save_game={TeleportType::Me1Le(RcRef::clone(&ctx.props().save_game))} />
                     </div>
                 </div>
             }
@@ -471,11 +475,9 @@ impl Me1LeGeneral {
                     />
                     {"Notoriety"}
                 </div>
-                <InputText label="Identity Code" value={RcRef::clone(&player.face_code)} helper=
-                    "If you change this you can display whatever you want in the menus \
-                    in place of your `Identity Code`.\n\
-                    This will NOT change your face, you have to edit your head morph \
-                    or import one to do so."
+                <FaceCode face_code={RcRef::clone(&player.face_code)}
+                    is_female={player.is_female()}
+                    game={FaceCodeGame::Me1}
                 />
             </Table>
         }
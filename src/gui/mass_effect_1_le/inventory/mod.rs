@@ -18,6 +18,7 @@ use crate::{
 mod item_select;
 pub use self::item_select::*;
 
+#[allow(clippy::large_enum_variant)]
 pub enum Msg {
     DatabaseLoaded(Databases),
     ChangeItem(RcRef<Item>, DbItem),
@@ -15,10 +15,15 @@ use crate::{
 };
 
 mod bonus_talents;
+mod exploration;
 mod general;
 mod inventory;
+mod shops;
+mod world;
 
-pub use self::{general::*, inventory::*};
+pub use self::{
+    bonus_talents::BONUS_TALENTS, exploration::*, general::*, inventory::*, shops::*, world::*,
+};
 
 impl RawUi for RcRef<Me1LeSaveData> {
     fn view(&self, _: &str) -> yew::Html {
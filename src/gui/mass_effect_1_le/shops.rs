@@ -0,0 +1,392 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Context as _, Result};
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{Select, Table},
+        mass_effect_1_le::ItemSelect,
+        raw_ui::RawUi,
+    },
+    save_data::{
+        mass_effect_1_le::{
+            item_db::{DbItem, Me1ItemDb},
+            legacy::{BaseObject, Object},
+            player::ItemLevel,
+            NoExportData,
+        },
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    ChangeItem(RcRef<BaseObject>, DbItem),
+    ChangeItemLevel(RcRef<BaseObject>, usize),
+    RemoveItem(RcRef<Vec<RcRef<BaseObject>>>, usize),
+    DuplicateItem(RcRef<BaseObject>, usize),
+    AddItem(RcRef<BaseObject>, RcRef<BaseObject>, DbItem),
+    Restock(RcRef<BaseObject>),
+    DismissError,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub no_export: RcRef<NoExportData>,
+}
+
+pub struct Me1LeShops {
+    _db_handle: ContextHandle<Databases>,
+    item_db: Option<Rc<Me1ItemDb>>,
+    error: Option<String>,
+}
+
+impl Component for Me1LeShops {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me1LeShops { _db_handle, item_db: databases.get_me1_item_db(), error: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.item_db = dbs.get_me1_item_db();
+                true
+            }
+            Msg::ChangeItem(object, new_item) => {
+                match object.borrow()._object {
+                    Object::Item(ref item) => {
+                        let mut item = item.borrow_mut();
+                        item.set_item_id(new_item.item_id);
+                        item.set_manufacturer_id(new_item.manufacturer_id);
+                    }
+                    Object::ItemMod(ref item_mod) => {
+                        let mut item_mod = item_mod.borrow_mut();
+                        item_mod.set_item_id(new_item.item_id);
+                        item_mod.set_manufacturer_id(new_item.manufacturer_id);
+                    }
+                    _ => (),
+                }
+                false
+            }
+            Msg::ChangeItemLevel(object, item_level) => {
+                match object.borrow()._object {
+                    Object::Item(ref item) => {
+                        *item.borrow_mut().item_level_mut() = ItemLevel::from(item_level)
+                    }
+                    Object::ItemMod(ref item_mod) => {
+                        *item_mod.borrow_mut().item_level_mut() = ItemLevel::from(item_level)
+                    }
+                    _ => (),
+                }
+                false
+            }
+            Msg::RemoveItem(item_list, idx) => {
+                item_list.borrow_mut().remove(idx);
+                true
+            }
+            Msg::DuplicateItem(stock, idx) => {
+                if let Object::Inventory(ref inventory) = stock.borrow()._object {
+                    self.error = inventory
+                        .borrow()
+                        .duplicate_item(idx)
+                        .err()
+                        .map(|err| format!("Failed to copy the item: {}", err));
+                }
+                true
+            }
+            Msg::AddItem(shop, stock, db_item) => {
+                self.error = self
+                    .add_item(ctx, &shop, &stock, db_item)
+                    .err()
+                    .map(|err| format!("Failed to add `{}`: {}", self.item_name(db_item), err));
+                true
+            }
+            Msg::Restock(shop) => {
+                if let Object::Shop(ref shop) = shop.borrow()._object {
+                    shop.borrow().restock();
+                }
+                true
+            }
+            Msg::DismissError => {
+                self.error = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.item_db.is_some() {
+            let no_export = ctx.props().no_export.borrow();
+            let legacy_maps = no_export.legacy_maps();
+
+            let shops = legacy_maps.values().flat_map(|map| {
+                map.borrow()
+                    .levels()
+                    .iter()
+                    .flat_map(|(level_name, level)| {
+                        // "Level BIOA_STA60_08A_DSG.TheWorld:PersistentLevel" => "BIOA_STA60_08A_DSG"
+                        let level_name = level_name.trim_start_matches("Level ");
+                        let level_name = level_name.split('.').next().unwrap_or(level_name);
+                        level
+                            .borrow()
+                            .objects()
+                            .iter()
+                            .filter(|object| matches!(object.borrow()._object, Object::Shop(_)))
+                            .map(|shop| self.shop(ctx, level_name, shop))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+            let error = self.error.as_ref().map(|error| {
+                html! {
+                    <div class="flex items-center gap-1">
+                        <p class="flex-auto">{ error }</p>
+                        <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                            onclick={ctx.link().callback(|_| Msg::DismissError)}
+                        >
+                            {"dismiss"}
+                        </button>
+                    </div>
+                }
+            });
+
+            html! {
+                <div class="flex flex-col gap-1">
+                    { for error }
+                    { for shops }
+                </div>
+            }
+        } else {
+            html! {
+                <>
+                    <p>{ "Loading database..." }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        }
+    }
+}
+
+impl Me1LeShops {
+    fn shop(&self, ctx: &Context<Self>, level_name: &str, object: &RcRef<BaseObject>) -> Html {
+        let base_object = object.borrow();
+        let shop = match base_object._object {
+            Object::Shop(ref shop) => shop.borrow(),
+            _ => unreachable!(),
+        };
+
+        let stocks = shop.inventory();
+        let stocks = stocks.iter().enumerate().filter_map(|(idx, stock)| {
+            let stock = stock.borrow();
+            let stock = stock.proxy.borrow();
+            stock
+                .as_ref()
+                .map(|stock| self.stock(ctx, object, &format!("Stock {}", idx + 1), stock))
+        });
+
+        let onrestock = {
+            let object = RcRef::clone(object);
+            ctx.link().callback(move |_| Msg::Restock(RcRef::clone(&object)))
+        };
+
+        let title = format!("{} - {}", level_name, base_object.owner_name.borrow());
+        html! {
+            <Table {title} opened=false>
+                <div class="flex items-center gap-1">
+                    <span class="w-1/3">
+                        { if shop.is_initialized() { "Initialized" } else { "Not initialized" } }
+                    </span>
+                    { shop.last_player_level.view("Last player level") }
+                </div>
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                    onclick={onrestock}
+                >
+                    {"restock"}
+                </button>
+                { for stocks }
+            </Table>
+        }
+    }
+
+    fn stock(
+        &self, ctx: &Context<Self>, shop: &RcRef<BaseObject>, title: &str,
+        object: &RcRef<BaseObject>,
+    ) -> Html {
+        let link = ctx.link();
+        let base_object = object.borrow();
+        let inventory = match base_object._object {
+            Object::Inventory(ref inventory) => inventory.borrow(),
+            _ => return Html::default(),
+        };
+
+        let items = inventory.items();
+        let items = items.iter().enumerate().map(|(idx, item)| {
+            let onremove = {
+                let item_list = RcRef::clone(&inventory.items);
+                link.callback(move |_| Msg::RemoveItem(RcRef::clone(&item_list), idx))
+            };
+            let onduplicate = {
+                let object = RcRef::clone(object);
+                link.callback(move |_| Msg::DuplicateItem(RcRef::clone(&object), idx))
+            };
+            let button = |label, onclick| {
+                html! {
+                    <a class={classes![
+                            "rounded-none",
+                            "select-none",
+                            "hover:bg-theme-hover",
+                            "active:bg-theme-active",
+                            "bg-theme-bg",
+                            "px-1",
+                            "py-0",
+                            "cursor-pointer",
+                        ]}
+                        {onclick}
+                    >
+                        { label }
+                    </a>
+                }
+            };
+            html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    <div class="flex gap-1 py-px">
+                        { button("remove", onremove) }
+                        { button("copy", onduplicate) }
+                    </div>
+                    { self.item_view(ctx, item) }
+                </div>
+            }
+        });
+        let onadd = {
+            let shop = RcRef::clone(shop);
+            let object = RcRef::clone(object);
+            link.callback(move |db_item| {
+                Msg::AddItem(RcRef::clone(&shop), RcRef::clone(&object), db_item)
+            })
+        };
+
+        html! {
+            <Table title={title.to_owned()}>
+                { for items }
+                <div class="flex items-center gap-1 min-w-0">
+                    <span class="whitespace-nowrap">{ "Add" }</span>
+                    <ItemSelect
+                        item_db={Rc::clone(self.item_db.as_ref().unwrap())}
+                        current_item={DbItem { item_id: 0, manufacturer_id: 0 }}
+                        onselect={onadd}
+                    />
+                </div>
+            </Table>
+        }
+    }
+
+    fn item_view(&self, ctx: &Context<Self>, object: &RcRef<BaseObject>) -> Html {
+        let (current_item, current_level) = match object.borrow()._object {
+            Object::Item(ref item) => {
+                let item = item.borrow();
+                let db_item =
+                    DbItem { item_id: item.item_id(), manufacturer_id: item.manufacturer_id() };
+                let item_level = *item.item_level() as usize;
+                (db_item, item_level)
+            }
+            Object::ItemMod(ref item_mod) => {
+                let item_mod = item_mod.borrow();
+                let db_item = DbItem {
+                    item_id: item_mod.item_id(),
+                    manufacturer_id: item_mod.manufacturer_id(),
+                };
+                let item_level = *item_mod.item_level() as usize;
+                (db_item, item_level)
+            }
+            _ => return html! { &object.borrow().owner_name.borrow() },
+        };
+
+        let onselect_item = {
+            let object = RcRef::clone(object);
+            ctx.link().callback(move |new_item| Msg::ChangeItem(RcRef::clone(&object), new_item))
+        };
+        let onselect_level = {
+            let object = RcRef::clone(object);
+            ctx.link().callback(move |idx| Msg::ChangeItemLevel(RcRef::clone(&object), idx))
+        };
+        html! {
+            <>
+                <ItemSelect
+                    item_db={Rc::clone(self.item_db.as_ref().unwrap())}
+                    {current_item}
+                    onselect={onselect_item}
+                />
+                <Select
+                    options={ItemLevel::variants()}
+                    current_idx={current_level}
+                    onselect={onselect_level}
+                    sized=false
+                />
+            </>
+        }
+    }
+
+    // Le type de l'objet (arme, armure, amélioration, etc.) n'est pas dans la base de données,
+    // le nouvel objet est donc une copie du dernier objet du stock, à défaut de celui
+    // d'un autre stock du marchand ou d'un autre marchand
+    fn add_item(
+        &self, ctx: &Context<Self>, shop: &RcRef<BaseObject>, stock: &RcRef<BaseObject>,
+        db_item: DbItem,
+    ) -> Result<()> {
+        let stock = match stock.borrow()._object {
+            Object::Inventory(ref inventory) => RcRef::clone(inventory),
+            _ => bail!("Not a merchant stock"),
+        };
+
+        let shop_template = || match shop.borrow()._object {
+            Object::Shop(ref shop) => shop.borrow().item_template(),
+            _ => None,
+        };
+        let any_template = || {
+            let no_export = ctx.props().no_export.borrow();
+            let legacy_maps = no_export.legacy_maps();
+            let template = legacy_maps.values().find_map(|map| {
+                map.borrow().levels().values().find_map(|level| {
+                    level.borrow().objects().iter().find_map(|object| {
+                        match object.borrow()._object {
+                            Object::Shop(ref shop) => shop.borrow().item_template(),
+                            _ => None,
+                        }
+                    })
+                })
+            });
+            template
+        };
+
+        let stock = stock.borrow();
+        let template = stock
+            .items()
+            .last()
+            .cloned()
+            .or_else(shop_template)
+            .or_else(any_template)
+            .context("No item in any merchant stock to use as a template")?;
+
+        stock.add_item(&template, db_item)
+    }
+
+    fn item_name(&self, db_item: DbItem) -> String {
+        self.item_db
+            .as_ref()
+            .and_then(|item_db| item_db.get(&db_item).cloned())
+            .unwrap_or_else(|| "Unknown item".to_owned())
+    }
+}
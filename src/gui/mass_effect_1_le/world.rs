@@ -0,0 +1,200 @@
+use yew::prelude::*;
+
+use crate::{
+    gui::components::Table,
+    save_data::{
+        mass_effect_1_le::{
+            legacy::{BaseObject, Level, Object},
+            NoExportData,
+        },
+        RcRef,
+    },
+};
+
+#[derive(Clone, Copy)]
+pub enum WorldAction {
+    Revive,
+    Unlock,
+    Lock,
+    ResetFailedBypass,
+    RegenerateTreasure,
+    Repair,
+}
+
+pub enum Msg {
+    Action(RcRef<BaseObject>, WorldAction),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub no_export: RcRef<NoExportData>,
+}
+
+pub struct Me1LeWorld {
+    // Difficulté d'origine des objets déverrouillés, un objet n'est reverrouillé qu'avec elle
+    unlocked: Vec<(RcRef<BaseObject>, u8)>,
+}
+
+impl Component for Me1LeWorld {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Me1LeWorld { unlocked: Vec::new() }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Action(object, action) => {
+                match (&object.borrow()._object, action) {
+                    (Object::PawnBehavior(pawn), WorldAction::Revive) => pawn.borrow().revive(),
+                    (Object::ArtPlaceableBehavior(placeable), action) => {
+                        let placeable = placeable.borrow();
+                        match action {
+                            WorldAction::Unlock => {
+                                let difficulty = placeable.unlock();
+                                self.unlocked.push((RcRef::clone(&object), difficulty));
+                            }
+                            WorldAction::Lock => {
+                                if let Some(idx) = self
+                                    .unlocked
+                                    .iter()
+                                    .position(|(unlocked, _)| *unlocked == object)
+                                {
+                                    let (_, difficulty) = self.unlocked.remove(idx);
+                                    placeable.lock(difficulty);
+                                }
+                            }
+                            WorldAction::ResetFailedBypass => placeable.reset_failed_bypass(),
+                            WorldAction::RegenerateTreasure => placeable.regenerate_treasure(),
+                            WorldAction::Repair => placeable.repair(),
+                            WorldAction::Revive => (),
+                        }
+                    }
+                    _ => (),
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let no_export = ctx.props().no_export.borrow();
+        let legacy_maps = no_export.legacy_maps();
+
+        let maps = legacy_maps.iter().filter_map(|(map_name, map)| {
+            let levels = map
+                .borrow()
+                .levels()
+                .iter()
+                .filter_map(|(level_name, level)| self.level(ctx, level_name, level))
+                .collect::<Vec<_>>();
+
+            (!levels.is_empty()).then(|| {
+                html! {
+                    <Table title={map_name.clone()} opened=false>
+                        { for levels }
+                    </Table>
+                }
+            })
+        });
+
+        html! {
+            <div class="flex flex-col gap-1">
+                { for maps }
+            </div>
+        }
+    }
+}
+
+impl Me1LeWorld {
+    fn level(&self, ctx: &Context<Self>, level_name: &str, level: &RcRef<Level>) -> Option<Html> {
+        let objects = level
+            .borrow()
+            .objects()
+            .iter()
+            .filter_map(|object| self.object(ctx, object))
+            .collect::<Vec<_>>();
+
+        (!objects.is_empty()).then(|| {
+            html! {
+                <Table title={level_name.to_owned()} opened=false>
+                    { for objects }
+                </Table>
+            }
+        })
+    }
+
+    fn object(&self, ctx: &Context<Self>, object: &RcRef<BaseObject>) -> Option<Html> {
+        let base_object = object.borrow();
+        let action = |label: &'static str, action: WorldAction| {
+            let object = RcRef::clone(object);
+            let onclick = ctx.link().callback(move |_| Msg::Action(RcRef::clone(&object), action));
+            html! {
+                <a class={classes![
+                        "rounded-none",
+                        "select-none",
+                        "hover:bg-theme-hover",
+                        "active:bg-theme-active",
+                        "bg-theme-bg",
+                        "px-1",
+                        "py-0",
+                        "cursor-pointer",
+                    ]}
+                    {onclick}
+                >
+                    { label }
+                </a>
+            }
+        };
+
+        let (states, actions) = match base_object._object {
+            Object::PawnBehavior(ref pawn) => {
+                let pawn = pawn.borrow();
+                let mut states = vec![format!("Level {}", pawn.level())];
+                let mut actions = Vec::new();
+                if pawn.is_dead() {
+                    states.push(String::from("Dead"));
+                    actions.push(action("revive", WorldAction::Revive));
+                }
+                (states, actions)
+            }
+            Object::ArtPlaceableBehavior(ref placeable) => {
+                let placeable = placeable.borrow();
+                let mut states = vec![placeable.current_fsm_state_name().clone()];
+                let mut actions = Vec::new();
+                if placeable.is_destroyed() {
+                    states.push(String::from("Destroyed"));
+                    actions.push(action("repair", WorldAction::Repair));
+                }
+                if placeable.is_locked() {
+                    states.push(format!("Locked ({})", placeable.skill_difficulty()));
+                    actions.push(action("unlock", WorldAction::Unlock));
+                } else if self.unlocked.iter().any(|(unlocked, _)| unlocked == object) {
+                    actions.push(action("lock", WorldAction::Lock));
+                }
+                if placeable.skill_game_failed() {
+                    states.push(String::from("Bypass failed"));
+                    actions.push(action("reset bypass", WorldAction::ResetFailedBypass));
+                }
+                if placeable.inventory().is_some() && placeable.generated_treasure() {
+                    states.push(String::from("Treasure generated"));
+                    actions.push(action("regenerate treasure", WorldAction::RegenerateTreasure));
+                }
+                (states, actions)
+            }
+            _ => return None,
+        };
+
+        let owner_name = base_object.owner_name.borrow().clone();
+        Some(html! {
+            <div class="flex items-center gap-1 min-w-0">
+                <span class="w-1/3 truncate" title={owner_name.clone()}>{ owner_name }</span>
+                <span class="w-1/3 truncate">{ states.join(", ") }</span>
+                <div class="flex gap-1 py-px">
+                    { for actions }
+                </div>
+            </div>
+        })
+    }
+}
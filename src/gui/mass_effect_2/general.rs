@@ -4,13 +4,17 @@ use yew::prelude::*;
 
 use crate::{
     gui::{
-        components::{Helper, InputText, Select, Table},
+        components::{Helper, Select, Table},
         raw_ui::RawUi,
-        shared::{BonusPowerType, BonusPowers},
+        shared::{
+            BonusPowerType, BonusPowers, FaceCode, LevelRepairTool, LevelRepairType, Teleport,
+            TeleportType,
+        },
     },
     save_data::{
         mass_effect_2::{player::Player, Difficulty},
         shared::{
+            face_code::FaceCodeGame,
             player::{Notoriety, Origin},
             plot::PlotTable,
             EndGameState,
@@ -237,6 +241,8 @@ impl Component for Me2General {
                 <div class="flex-1 pl-1 flex flex-col gap-1">
                     { Self::general(difficulty, end_game_state) }
                     { Self::bonus_powers(player.borrow()) }
+                    <Teleport save_game={TeleportType::Me2(Me2Type::clone(&ctx.props().save_game))} />
+                    <LevelRepairTool save_game={LevelRepairType::Me2(Me2Type::clone(&ctx.props().save_game))} />
                 </div>
             </div>
         }
@@ -278,11 +284,9 @@ impl Me2General {
                     />
                     {"Notoriety"}
                 </div>
-                <InputText label="Identity Code" value={RcRef::clone(&player.face_code)} helper=
-                    "If you change this you can display whatever you want in the menus \
-                    in place of your `Identity Code`.\n\
-                    This will NOT change your face, you have to edit your head morph \
-                    or import one to do so."
+                <FaceCode face_code={RcRef::clone(&player.face_code)}
+                    is_female={player.is_female()}
+                    game={FaceCodeGame::Me2}
                 />
             </Table>
         }
@@ -4,13 +4,19 @@ use yew::prelude::*;
 
 use crate::{
     gui::{
-        components::{Helper, InputText, Select, Table},
+        components::{Helper, Select, Table},
         raw_ui::RawUi,
-        shared::{BonusPowerType, BonusPowers},
+        shared::{
+            BonusPowerType, BonusPowers, FaceCode, LevelRepairTool, LevelRepairType, Teleport,
+            TeleportType,
+        },
     },
     save_data::{
         mass_effect_3::{player::Player, plot::PlotTable, Me3SaveGame},
-        shared::player::{Notoriety, Origin},
+        shared::{
+            face_code::FaceCodeGame,
+            player::{Notoriety, Origin},
+        },
         RcRef,
     },
 };
@@ -236,6 +242,8 @@ impl Component for Me3General {
                 <div class="flex-1 pl-1 flex flex-col gap-1">
                     { Self::general(&save_game) }
                     { Self::bonus_powers(save_game.player()) }
+                    <Teleport save_game={TeleportType::Me3(RcRef::clone(&ctx.props().save_game))} />
+                    <LevelRepairTool save_game={LevelRepairType::Me3(RcRef::clone(&ctx.props().save_game))} />
                 </div>
             </div>
         }
@@ -277,11 +285,9 @@ impl Me3General {
                     />
                     {"Notoriety"}
                 </div>
-                <InputText label="Identity Code" value={RcRef::clone(&player.face_code)} helper=
-                    "If you change this you can display whatever you want in the menus \
-                    in place of your `Identity Code`.\n\
-                    This will NOT change your face, you have to edit your head morph \
-                    or import one to do so."
+                <FaceCode face_code={RcRef::clone(&player.face_code)}
+                    is_female={player.is_female()}
+                    game={FaceCodeGame::Me3}
                 />
             </Table>
         }
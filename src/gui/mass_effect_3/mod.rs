@@ -1,9 +1,11 @@
 mod general;
+mod player_variables;
 mod plot;
 mod plot_variable;
 mod raw_plot;
+mod world;
 
-pub use self::{general::*, plot::*, plot_variable::*, raw_plot::*};
+pub use self::{general::*, player_variables::*, plot::*, plot_variable::*, raw_plot::*, world::*};
 
 use yew::prelude::*;
 
@@ -0,0 +1,231 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use web_sys::HtmlInputElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{CallbackType, InputNumber, NumberType, Table},
+        raw_ui::RawUi,
+    },
+    save_data::{
+        mass_effect_3::player_variable_db::{DbVariableGroup, Me3PlayerVariableDb},
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Filter(InputEvent),
+    Add(String, i32),
+    SetAll(String, i32),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub variables: RcRef<IndexMap<String, RcCell<i32>>>,
+}
+
+pub struct Me3PlayerVariables {
+    _db_handle: ContextHandle<Databases>,
+    player_variable_db: Option<Rc<Me3PlayerVariableDb>>,
+    filter: String,
+}
+
+impl Component for Me3PlayerVariables {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me3PlayerVariables {
+            _db_handle,
+            player_variable_db: databases.get_me3_player_variables(),
+            filter: String::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.player_variable_db = dbs.get_me3_player_variables();
+                true
+            }
+            Msg::Filter(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.filter = input.value().to_lowercase();
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::Add(key, value) => {
+                ctx.props().variables.borrow_mut().entry(key).or_insert_with(|| value.into());
+                true
+            }
+            Msg::SetAll(group_name, value) => {
+                if let Some(group) =
+                    self.player_variable_db.as_ref().and_then(|db| db.get(&group_name))
+                {
+                    let variables = ctx.props().variables.borrow();
+                    for (_, variable) in
+                        variables.iter().filter(|(key, _)| Self::find_key(group, key).is_some())
+                    {
+                        variable.set(group.clamp(value));
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(ref player_variable_db) = self.player_variable_db {
+            let groups = player_variable_db
+                .iter()
+                .filter_map(|(group_name, group)| self.group(ctx, group_name, group));
+            let unknown = self.unknown(ctx, player_variable_db);
+
+            html! {
+                <div class="flex-auto flex flex-col gap-1">
+                    <div class="flex gap-3 w-2/3">
+                        <label class="flex-auto flex items-center gap-1">
+                            <input type="text" class="flex-auto input" placeholder="<empty>" value={self.filter.clone()}
+                                oninput={ctx.link().callback(Msg::Filter)}
+                            />
+                            { "Filter" }
+                        </label>
+                    </div>
+                    <hr class="border-t border-default-border" />
+                    <div class="flex-auto flex flex-col gap-1 h-0 overflow-y-auto">
+                        { for groups }
+                        { unknown }
+                    </div>
+                </div>
+            }
+        } else {
+            html! {
+                <>
+                    <p>{ "Loading database..." }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        }
+    }
+}
+
+impl Me3PlayerVariables {
+    // Les noms des variables ne sont pas sensibles à la casse
+    fn find_key<'a>(group: &'a DbVariableGroup, key: &str) -> Option<&'a String> {
+        group.variables.keys().find(|db_key| db_key.eq_ignore_ascii_case(key))
+    }
+
+    fn is_match(&self, key: &str, label: &str) -> bool {
+        self.filter.is_empty()
+            || key.to_lowercase().contains(&self.filter)
+            || label.to_lowercase().contains(&self.filter)
+    }
+
+    fn group(
+        &self, ctx: &Context<Self>, group_name: &str, group: &DbVariableGroup,
+    ) -> Option<Html> {
+        let link = ctx.link();
+        let variables = ctx.props().variables.borrow();
+        let DbVariableGroup { min, max, .. } = *group;
+
+        let rows = group
+            .variables
+            .iter()
+            .filter(|(db_key, label)| self.is_match(db_key, label))
+            .map(|(db_key, label)| {
+                let value = variables.iter().find_map(|(key, value)| {
+                    db_key.eq_ignore_ascii_case(key).then(|| RcCell::clone(value))
+                });
+                match value {
+                    Some(value) => {
+                        // La valeur saisie est ramenée dans les bornes du groupe
+                        let onchange = {
+                            let value = RcCell::clone(&value);
+                            Callback::from(move |callback| {
+                                if let CallbackType::Int(int) = callback {
+                                    value.set(int.clamp(min, max));
+                                }
+                            })
+                        };
+                        html! {
+                            <InputNumber label={label.clone()} value={NumberType::Int(value)} {onchange} />
+                        }
+                    }
+                    None => {
+                        let onclick = {
+                            let db_key = db_key.clone();
+                            link.callback(move |_| Msg::Add(db_key.clone(), min))
+                        };
+                        html! {
+                            <div class="flex items-center gap-1">
+                                <div class="py-px">
+                                    <a class={classes![
+                                            "rounded-none",
+                                            "select-none",
+                                            "hover:bg-theme-hover",
+                                            "active:bg-theme-active",
+                                            "bg-theme-bg",
+                                            "px-1",
+                                            "py-0",
+                                            "cursor-pointer",
+                                        ]}
+                                        {onclick}
+                                    >
+                                        {"add"}
+                                    </a>
+                                </div>
+                                { label }
+                            </div>
+                        }
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if rows.is_empty() {
+            return None;
+        }
+
+        let onset_max = {
+            let group_name = group_name.to_owned();
+            link.callback(move |_| Msg::SetAll(group_name.clone(), max))
+        };
+        let title = format!("{} ({} - {})", group_name, min, max);
+        Some(html! {
+            <Table {title} opened={!self.filter.is_empty()}>
+                { for rows }
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                    onclick={onset_max}
+                >
+                    {"set all to max"}
+                </button>
+            </Table>
+        })
+    }
+
+    fn unknown(&self, ctx: &Context<Self>, player_variable_db: &Me3PlayerVariableDb) -> Html {
+        let variables = ctx.props().variables.borrow();
+        let rows = variables
+            .iter()
+            .filter(|(key, _)| !player_variable_db.contains_variable(key) && self.is_match(key, ""))
+            .map(|(key, value)| value.view(key));
+
+        html! {
+            <Table title="Other variables" opened={!self.filter.is_empty()}>
+                { for rows }
+            </Table>
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{components::Table, raw_ui::RawUi},
+    save_data::{mass_effect_3::Me3SaveGame, shared::guid_db::GuidDb, RcRef},
+    services::database::Databases,
+};
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    RespawnTreasure(usize),
+    ClearTreasure(usize),
+    ResetPlaceable(usize),
+    ResetPlaceables,
+    RemoveObjectiveMarker(usize),
+    RemoveAllObjectiveMarkers,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub save_game: RcRef<Me3SaveGame>,
+}
+
+pub struct Me3World {
+    _db_handle: ContextHandle<Databases>,
+    guid_db: Option<Rc<GuidDb>>,
+}
+
+impl Component for Me3World {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me3World { _db_handle, guid_db: databases.get_me3_guid() }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let save_game = ctx.props().save_game.borrow();
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.guid_db = dbs.get_me3_guid();
+                true
+            }
+            Msg::RespawnTreasure(idx) => {
+                save_game.treasures.borrow_mut().remove(idx);
+                true
+            }
+            Msg::ClearTreasure(idx) => {
+                if let Some(treasure) = save_game.treasures.borrow().get(idx) {
+                    treasure.borrow().clear();
+                }
+                true
+            }
+            Msg::ResetPlaceable(idx) => {
+                if let Some(placeable) = save_game.placeables.borrow().get(idx) {
+                    placeable.borrow().reset();
+                }
+                true
+            }
+            Msg::ResetPlaceables => {
+                save_game.reset_placeables();
+                true
+            }
+            Msg::RemoveObjectiveMarker(idx) => {
+                save_game.objective_markers.borrow_mut().remove(idx);
+                true
+            }
+            Msg::RemoveAllObjectiveMarkers => {
+                save_game.objective_markers.borrow_mut().clear();
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="flex-auto flex flex-col gap-1 h-0 overflow-y-auto">
+                { self.treasures(ctx) }
+                { self.placeables(ctx) }
+                { self.use_modules(ctx) }
+                { self.objective_markers(ctx) }
+            </div>
+        }
+    }
+}
+
+impl Me3World {
+    fn treasures(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let save_game = ctx.props().save_game.borrow();
+        let treasures = save_game.treasures.borrow();
+
+        let rows = treasures.iter().enumerate().map(|(idx, treasure)| {
+            let treasure = treasure.borrow();
+            let items = treasure.items.borrow();
+            let item_names =
+                items.iter().map(|item| item.borrow().clone()).collect::<Vec<_>>().join("\n");
+            html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    <div class="py-px flex gap-1">
+                        { Self::action(link.callback(move |_| Msg::RespawnTreasure(idx)), "respawn") }
+                        { Self::action(link.callback(move |_| Msg::ClearTreasure(idx)), "clear") }
+                    </div>
+                    <span class="w-1/4 truncate">{ treasure.level_name.borrow().clone() }</span>
+                    <span class="w-1/6">{ format!("{} credits", treasure.credits.get()) }</span>
+                    <span class="w-1/6">{ format!("{} XP", treasure.xp.get()) }</span>
+                    <span class="flex-1 truncate" title={item_names}>
+                        { format!("{} item(s)", items.len()) }
+                    </span>
+                </div>
+            }
+        });
+
+        html! {
+            <Table title="Level treasures" helper=
+                "`respawn` removes the record, the level will place its treasure again on the next visit.\n\
+                `clear` keeps the record but empties it, the treasure will not come back."
+            >
+                { for rows }
+            </Table>
+        }
+    }
+
+    fn placeables(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let save_game = ctx.props().save_game.borrow();
+        let placeables = save_game.placeables.borrow();
+
+        // La save ne stocke que le guid d'un placeable, son niveau vient de la base de données des
+        // guids, ceux qui n'y sont pas sont regroupés sous "Unknown level"
+        let mut levels: IndexMap<String, Vec<Html>> = IndexMap::new();
+        for (idx, placeable) in placeables.iter().enumerate() {
+            let placeable = placeable.borrow();
+            // Seuls les placeables détruits ou désactivés sont listés
+            if !placeable.is_modified() {
+                continue;
+            }
+
+            let guid = placeable.guid.borrow();
+            let object = self.guid_db.as_ref().and_then(|guid_db| guid_db.find(&guid));
+            let (level, label) = match object {
+                Some(object) => (object.level.clone(), object.description.clone()),
+                None => ("Unknown level".to_owned(), guid.hyphenated()),
+            };
+            levels.entry(level).or_default().push(html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    <div class="py-px">
+                        { Self::action(link.callback(move |_| Msg::ResetPlaceable(idx)), "reset") }
+                    </div>
+                    <span class="truncate" title={guid.hyphenated()}>{ label }</span>
+                </div>
+            });
+        }
+        let modified = levels.values().map(Vec::len).sum::<usize>();
+
+        let levels = levels.into_iter().map(|(level, rows)| {
+            html! {
+                <Table title={format!("{} ({})", level, rows.len())}>
+                    { for rows }
+                </Table>
+            }
+        });
+
+        html! {
+            <Table title="Placeables" helper=
+                "Destroyed or deactivated placeables grouped by level, `reset` restores their initial state.\n\
+                Only the placeables recorded in the GUID database have a known level, `reset all` restores every placeable of the save."
+            >
+                <div class="flex items-center gap-1">
+                    <span class="flex-auto">
+                        { format!("{} / {} destroyed or deactivated", modified, placeables.len()) }
+                    </span>
+                    <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                        onclick={link.callback(|_| Msg::ResetPlaceables)}
+                    >
+                        {"reset all"}
+                    </button>
+                </div>
+                { for levels }
+            </Table>
+        }
+    }
+
+    fn use_modules(&self, ctx: &Context<Self>) -> Html {
+        let save_game = ctx.props().save_game.borrow();
+        let use_modules = save_game.use_modules.borrow();
+
+        let rows = use_modules.iter().map(|guid| {
+            let guid = guid.borrow();
+            let label = self
+                .guid_db
+                .as_ref()
+                .and_then(|guid_db| guid_db.find(&guid))
+                .map(|object| object.label())
+                .unwrap_or_else(|| guid.hyphenated());
+            html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    <span class="truncate" title={guid.hyphenated()}>{ label }</span>
+                </div>
+            }
+        });
+
+        html! {
+            <Table title="Use modules" helper="Use modules recorded by the save, named when their GUID is in the database.">
+                { for rows }
+            </Table>
+        }
+    }
+
+    fn objective_markers(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let save_game = ctx.props().save_game.borrow();
+        let objective_markers = save_game.objective_markers.borrow();
+
+        let rows = objective_markers.iter().enumerate().map(|(idx, marker)| {
+            let marker = marker.borrow();
+            html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    <div class="py-px">
+                        { Self::action(link.callback(move |_| Msg::RemoveObjectiveMarker(idx)), "remove") }
+                    </div>
+                    <span class="w-1/3 truncate">{ marker.marker_owned_data.borrow().clone() }</span>
+                    { marker.marker_label.view("Label") }
+                    { marker.marker_icon_type.view("Icon") }
+                </div>
+            }
+        });
+
+        let remove_all = (!objective_markers.is_empty()).then(|| {
+            html! {
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                    onclick={link.callback(|_| Msg::RemoveAllObjectiveMarkers)}
+                >
+                    {"remove all"}
+                </button>
+            }
+        });
+
+        html! {
+            <Table title="Objective markers" helper=
+                "Markers that stay on the map after their quest is over can be removed here."
+            >
+                { for rows }
+                { for remove_all }
+            </Table>
+        }
+    }
+
+    fn action(onclick: Callback<MouseEvent>, label: &'static str) -> Html {
+        html! {
+            <a class={classes![
+                    "rounded-none",
+                    "select-none",
+                    "hover:bg-theme-hover",
+                    "active:bg-theme-active",
+                    "bg-theme-bg",
+                    "px-1",
+                    "py-0",
+                    "cursor-pointer",
+                ]}
+                {onclick}
+            >
+                { label }
+            </a>
+        }
+    }
+}
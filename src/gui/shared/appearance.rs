@@ -0,0 +1,213 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{Select, Table},
+    save_data::{
+        shared::{
+            appearance::{Appearance as DataAppearance, AppearancePiece, AppearanceTint},
+            appearance_db::{self, AppearanceDb},
+        },
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum AppearanceGame {
+    Me2,
+    Me3,
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    FullBody(usize),
+    Piece(AppearancePiece, i32),
+    Tint(AppearanceTint, i32),
+    Pattern(i32),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub appearance: RcRef<DataAppearance>,
+    pub game: AppearanceGame,
+    pub is_female: bool,
+}
+
+pub struct Appearance {
+    _db_handle: ContextHandle<Databases>,
+    appearance_db: Option<Rc<AppearanceDb>>,
+}
+
+impl Appearance {
+    fn get_appearance_db(databases: Databases, game: AppearanceGame) -> Option<Rc<AppearanceDb>> {
+        match game {
+            AppearanceGame::Me2 => databases.get_me2_appearance(),
+            AppearanceGame::Me3 => databases.get_me3_appearance(),
+        }
+    }
+}
+
+impl Component for Appearance {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Appearance {
+            _db_handle,
+            appearance_db: Self::get_appearance_db(databases, ctx.props().game),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let appearance = ctx.props().appearance.borrow();
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.appearance_db = Self::get_appearance_db(dbs, ctx.props().game);
+                true
+            }
+            Msg::FullBody(idx) => {
+                appearance.set_full_body(idx != 0);
+                true
+            }
+            Msg::Piece(piece, id) => {
+                appearance.set_piece_id(piece, id);
+                true
+            }
+            Msg::Tint(tint, id) => {
+                appearance.set_tint_id(tint, id);
+                true
+            }
+            Msg::Pattern(id) => {
+                appearance.set_pattern_id(id);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(ref appearance_db) = self.appearance_db {
+            let link = ctx.link();
+            let appearance = ctx.props().appearance.borrow();
+            let gender = appearance_db.gender(ctx.props().is_female);
+
+            let select = |label: &'static str, names, current_id, onselect: Callback<i32>| {
+                let (options, ids) = appearance_db::options(names, current_id);
+                let current_idx = ids.iter().position(|&id| id == current_id).unwrap_or_default();
+                html! {
+                    <div class="flex items-center gap-1 cursor-default">
+                        <Select {options} {current_idx}
+                            onselect={onselect.reform(move |idx| ids[idx])}
+                        />
+                        { label }
+                    </div>
+                }
+            };
+            let piece = |label, piece| {
+                select(
+                    label,
+                    gender.pieces(piece),
+                    appearance.piece_id(piece),
+                    link.callback(move |id| Msg::Piece(piece, id)),
+                )
+            };
+
+            let combat_appearances: &'static [&'static str] = &["Armor parts", "Full body armor"];
+            let parts = [
+                ("Torso", AppearancePiece::Torso),
+                ("Shoulders", AppearancePiece::Shoulder),
+                ("Arms", AppearancePiece::Arm),
+                ("Legs", AppearancePiece::Leg),
+            ];
+            let armor = if appearance.is_full_body() {
+                piece("Armor", AppearancePiece::FullBody)
+            } else {
+                parts.into_iter().map(|(label, part)| piece(label, part)).collect::<Html>()
+            };
+
+            let tints = [
+                ("Primary color", AppearanceTint::Tint1),
+                ("Secondary color", AppearanceTint::Tint2),
+                ("Tertiary color", AppearanceTint::Tint3),
+                ("Pattern color", AppearanceTint::PatternColor),
+            ];
+            let tints = tints.into_iter().map(|(label, tint)| {
+                let current_id = appearance.tint_id(tint);
+                let swatches = appearance_db.tints.iter().map(|(&id, db_tint)| {
+                    let border =
+                        if id == current_id { "border-white" } else { "border-default-border" };
+                    // Sans couleur connue, la case affiche l'ID
+                    let (style, text) = match db_tint.color {
+                        Some(ref color) => (format!("background-color: {}", color), None),
+                        None => (String::new(), Some(id.to_string())),
+                    };
+                    html! {
+                        <a class={classes![
+                                "border", border, "min-w-[20px]", "h-5", "px-0.5", "text-xs",
+                                "text-center", "cursor-pointer"
+                            ]}
+                            {style}
+                            title={db_tint.name.clone()}
+                            onclick={link.callback(move |_| Msg::Tint(tint, id))}
+                        >
+                            { for text }
+                        </a>
+                    }
+                });
+                let current_name = match appearance_db.tints.get(&current_id) {
+                    Some(db_tint) => db_tint.name.clone(),
+                    None => format!("Unknown ({})", current_id),
+                };
+                html! {
+                    <div class="flex items-center gap-1 cursor-default">
+                        <span class="w-[200px]">{ label }</span>
+                        { for swatches }
+                        <span>{ current_name }</span>
+                    </div>
+                }
+            });
+
+            html! {
+                <div class="flex-auto flex flex-col gap-1">
+                    <Table title="Casual">
+                        { piece("Outfit", AppearancePiece::Casual) }
+                    </Table>
+                    <Table title="Combat" helper=
+                        "Choosing a full body armor or an armor part also changes the combat appearance.\n\
+                        IDs missing from the database can still be edited in the `Raw Data` tab."
+                    >
+                        <div class="flex items-center gap-1 cursor-default">
+                            <Select
+                                options={combat_appearances}
+                                current_idx={appearance.is_full_body() as usize}
+                                onselect={link.callback(Msg::FullBody)}
+                            />
+                            { "Combat appearance" }
+                        </div>
+                        { armor }
+                        { piece("Helmet", AppearancePiece::Helmet) }
+                    </Table>
+                    <Table title="Colors">
+                        { for tints }
+                        { select("Pattern", &appearance_db.patterns, appearance.pattern_id(),
+                            link.callback(Msg::Pattern)) }
+                    </Table>
+                </div>
+            }
+        } else {
+            html! {
+                <>
+                    <p>{ "Loading database..." }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        }
+    }
+}
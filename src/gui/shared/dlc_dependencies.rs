@@ -0,0 +1,339 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{InputNumber, InputText, NumberType, Table},
+        mass_effect_2::Me2Type,
+    },
+    save_data::{
+        mass_effect_2, mass_effect_3,
+        mass_effect_3::Me3SaveGame,
+        shared::{
+            dlc_db::{self, DbDlc, DlcDb},
+            plot::BitVec,
+        },
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+#[derive(Clone)]
+pub enum DlcDependenciesType {
+    Me2(Me2Type),
+    Me3(RcRef<Me3SaveGame>),
+}
+
+impl PartialEq for DlcDependenciesType {
+    fn eq(&self, other: &DlcDependenciesType) -> bool {
+        match (self, other) {
+            (DlcDependenciesType::Me2(me2), DlcDependenciesType::Me2(other)) => me2 == other,
+            (DlcDependenciesType::Me3(me3), DlcDependenciesType::Me3(other)) => me3 == other,
+            _ => false,
+        }
+    }
+}
+
+enum DependentDlcs {
+    Me2(RcRef<Vec<RcRef<mass_effect_2::DependentDlc>>>),
+    Me3(RcRef<Vec<RcRef<mass_effect_3::DependentDlc>>>),
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Remove(usize),
+    Add(i32),
+    AddCustom,
+    DismissError,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub save_game: DlcDependenciesType,
+}
+
+pub struct DlcDependencies {
+    _db_handle: ContextHandle<Databases>,
+    dlc_db: Option<Rc<DlcDb>>,
+    // DLC absent de la base de données (mods)
+    custom_id: RcCell<i32>,
+    custom_name: RcRef<String>,
+    custom_canonical_name: RcRef<String>,
+    error: Option<String>,
+}
+
+impl Component for DlcDependencies {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        let dlc_db = Self::dlc_db(ctx, databases);
+        DlcDependencies {
+            _db_handle,
+            dlc_db,
+            custom_id: Default::default(),
+            custom_name: Default::default(),
+            custom_canonical_name: Default::default(),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.dlc_db = Self::dlc_db(ctx, dbs);
+                true
+            }
+            Msg::Remove(idx) => {
+                match Self::dependent_dlcs(ctx) {
+                    DependentDlcs::Me2(dlcs) => {
+                        dlcs.borrow_mut().remove(idx);
+                    }
+                    DependentDlcs::Me3(dlcs) => {
+                        dlcs.borrow_mut().remove(idx);
+                    }
+                }
+                true
+            }
+            Msg::Add(id) => {
+                if let Some(dlc) = self.dlc_db.as_ref().and_then(|dlc_db| dlc_db.get(&id)) {
+                    Self::add(ctx, id, dlc);
+                }
+                true
+            }
+            Msg::AddCustom => {
+                let id = self.custom_id.get();
+                let required_ids: Vec<_> =
+                    Self::required(ctx).into_iter().map(|(id, _)| id).collect();
+                match dlc_db::check_custom_dlc_id(id, &required_ids) {
+                    Ok(()) => {
+                        let dlc = DbDlc {
+                            name: self.custom_name.borrow().clone(),
+                            canonical_name: self.custom_canonical_name.borrow().clone(),
+                            plots: Default::default(),
+                        };
+                        Self::add(ctx, id, &dlc);
+                        self.error = None;
+                    }
+                    Err(err) => self.error = Some(format!("Cannot add the DLC: {}", err)),
+                }
+                true
+            }
+            Msg::DismissError => {
+                self.error = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(ref dlc_db) = self.dlc_db {
+            let link = ctx.link();
+            let booleans = Self::booleans(ctx);
+            let booleans = booleans.borrow();
+
+            let required = Self::required(ctx);
+
+            let required_rows = required.iter().enumerate().map(|(idx, (id, name))| {
+                let db_dlc = dlc_db.get(id);
+                let name = db_dlc.map(|dlc| &dlc.name).unwrap_or(name);
+                let plots_set = db_dlc
+                    .map(|dlc| dlc.plots_set(&booleans).cloned().collect::<Vec<_>>())
+                    .unwrap_or_default();
+                let warning = (!plots_set.is_empty()).then(|| {
+                    html! {
+                        <p class="pl-6">
+                            { format!("Plot flags set by this DLC: {}", plots_set.join(", ")) }
+                        </p>
+                    }
+                });
+
+                html! {
+                    <>
+                        <div class="flex items-center gap-1">
+                            <div class="py-px">
+                                { Self::action(link.callback(move |_| Msg::Remove(idx)), "remove") }
+                            </div>
+                            { format!("{} - {}", id, name) }
+                            { if db_dlc.is_none() { " (unknown)" } else { "" } }
+                        </div>
+                        { for warning }
+                    </>
+                }
+            });
+
+            let available_rows = dlc_db
+                .iter()
+                .filter(|(id, _)| !required.iter().any(|(required, _)| required == *id))
+                .map(|(&id, dlc)| {
+                    html! {
+                        <div class="flex items-center gap-1">
+                            <div class="py-px">
+                                { Self::action(link.callback(move |_| Msg::Add(id)), "add") }
+                            </div>
+                            { format!("{} - {}", id, dlc.name) }
+                        </div>
+                    }
+                });
+
+            html! {
+                <div class="flex divide-solid divide-x divide-default-border">
+                    <div class="flex-1 flex flex-col gap-1 pr-1 min-w-0">
+                        <Table title="Required DLCs" helper=
+                            "The game refuses to load a save if one of these DLCs is not installed.\n\
+                            Removing a DLC does not reset the plot flags it has set, the story may be inconsistent\n\
+                            if you reinstall it later."
+                        >
+                            { for required_rows }
+                        </Table>
+                    </div>
+                    <div class="flex-1 flex flex-col gap-1 pl-1 min-w-0">
+                        <Table title="Official DLCs">
+                            { for available_rows }
+                        </Table>
+                        { self.custom(ctx) }
+                    </div>
+                </div>
+            }
+        } else {
+            html! {
+                <>
+                    <p>{ "Loading database..." }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        }
+    }
+}
+
+impl DlcDependencies {
+    fn add(ctx: &Context<Self>, id: i32, dlc: &DbDlc) {
+        match Self::dependent_dlcs(ctx) {
+            DependentDlcs::Me2(dlcs) => {
+                dlcs.borrow_mut().push(mass_effect_2::DependentDlc::new(id, dlc).into())
+            }
+            DependentDlcs::Me3(dlcs) => {
+                dlcs.borrow_mut().push(mass_effect_3::DependentDlc::new(id, dlc).into())
+            }
+        }
+    }
+
+    fn required(ctx: &Context<Self>) -> Vec<(i32, String)> {
+        match Self::dependent_dlcs(ctx) {
+            DependentDlcs::Me2(dlcs) => dlcs
+                .borrow()
+                .iter()
+                .map(|dlc| {
+                    let dlc = dlc.borrow();
+                    let name = dlc.name().clone();
+                    (dlc.id(), name)
+                })
+                .collect(),
+            DependentDlcs::Me3(dlcs) => dlcs
+                .borrow()
+                .iter()
+                .map(|dlc| {
+                    let dlc = dlc.borrow();
+                    let name = dlc.name().clone();
+                    (dlc.id(), name)
+                })
+                .collect(),
+        }
+    }
+
+    fn custom(&self, ctx: &Context<Self>) -> Html {
+        let canonical_name = matches!(ctx.props().save_game, DlcDependenciesType::Me3(_)).then(|| {
+            html! {
+                <InputText label="Canonical name" value={RcRef::clone(&self.custom_canonical_name)} />
+            }
+        });
+        let error = self.error.as_ref().map(|error| {
+            html! {
+                <div class="flex items-center gap-1">
+                    <p class="flex-auto">{ error }</p>
+                    <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                        onclick={ctx.link().callback(|_| Msg::DismissError)}
+                    >
+                        {"dismiss"}
+                    </button>
+                </div>
+            }
+        });
+
+        html! {
+            <Table title="Other DLCs" helper=
+                "Mod DLCs are not in the database,\n\
+                add them with the ID and the name given by the mod author."
+            >
+                { for error }
+                <InputNumber label="ID" value={NumberType::Int(RcCell::clone(&self.custom_id))} />
+                <InputText label="Name" value={RcRef::clone(&self.custom_name)} />
+                { for canonical_name }
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                    onclick={ctx.link().callback(|_| Msg::AddCustom)}
+                >
+                    {"add"}
+                </button>
+            </Table>
+        }
+    }
+
+    fn dlc_db(ctx: &Context<Self>, dbs: Databases) -> Option<Rc<DlcDb>> {
+        match ctx.props().save_game {
+            DlcDependenciesType::Me2(_) => dbs.get_me2_dlc(),
+            DlcDependenciesType::Me3(_) => dbs.get_me3_dlc(),
+        }
+    }
+
+    fn dependent_dlcs(ctx: &Context<Self>) -> DependentDlcs {
+        match ctx.props().save_game {
+            DlcDependenciesType::Me2(Me2Type::Vanilla(ref me2)) => {
+                DependentDlcs::Me2(RcRef::clone(&me2.borrow().dependant_dlcs))
+            }
+            DlcDependenciesType::Me2(Me2Type::Legendary(ref me2)) => {
+                DependentDlcs::Me2(RcRef::clone(&me2.borrow().dependant_dlcs))
+            }
+            DlcDependenciesType::Me3(ref me3) => {
+                DependentDlcs::Me3(RcRef::clone(&me3.borrow().dependant_dlcs))
+            }
+        }
+    }
+
+    fn booleans(ctx: &Context<Self>) -> RcRef<BitVec> {
+        match ctx.props().save_game {
+            DlcDependenciesType::Me2(Me2Type::Vanilla(ref me2)) => {
+                RcRef::clone(&me2.borrow().plot().booleans)
+            }
+            DlcDependenciesType::Me2(Me2Type::Legendary(ref me2)) => {
+                RcRef::clone(&me2.borrow().plot().booleans)
+            }
+            DlcDependenciesType::Me3(ref me3) => RcRef::clone(&me3.borrow().plot().booleans),
+        }
+    }
+
+    fn action(onclick: Callback<MouseEvent>, label: &'static str) -> Html {
+        html! {
+            <a class={classes![
+                    "rounded-none",
+                    "select-none",
+                    "hover:bg-theme-hover",
+                    "active:bg-theme-active",
+                    "bg-theme-bg",
+                    "px-1",
+                    "py-0",
+                    "cursor-pointer",
+                ]}
+                {onclick}
+            >
+                { label }
+            </a>
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::{InputText, Table},
+    save_data::{
+        shared::face_code::{FaceCodeDb, FaceCodeGame},
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+const GAMES: [FaceCodeGame; 3] = [FaceCodeGame::Me1, FaceCodeGame::Me2, FaceCodeGame::Me3];
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Changed,
+    Import(FaceCodeGame),
+    DismissError,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub face_code: RcRef<String>,
+    pub is_female: bool,
+    pub game: FaceCodeGame,
+}
+
+pub struct FaceCode {
+    _db_handle: ContextHandle<Databases>,
+    face_code_db: Option<Rc<FaceCodeDb>>,
+    // Code d'un autre jeu à traduire pour celui-ci
+    import_code: RcRef<String>,
+    // Les champs gardent le même RcRef après un import, la clé force leur rendu
+    imports: usize,
+    error: Option<String>,
+}
+
+impl Component for FaceCode {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        FaceCode {
+            _db_handle,
+            face_code_db: databases.get_face_code(),
+            import_code: Default::default(),
+            imports: 0,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.face_code_db = dbs.get_face_code();
+                true
+            }
+            Msg::Changed => true,
+            Msg::Import(from) => {
+                if let Some(ref face_code_db) = self.face_code_db {
+                    let Props { face_code, is_female, game } = ctx.props();
+                    let import_code = self.import_code.borrow().trim().to_owned();
+                    match face_code_db.translate(&import_code, from, *game, *is_female) {
+                        Ok(translated) => {
+                            *face_code.borrow_mut() = translated;
+                            self.import_code.borrow_mut().clear();
+                            self.imports += 1;
+                            self.error = None;
+                        }
+                        Err(err) => {
+                            self.error =
+                                Some(format!("Not a valid {} face code: {}", from.name(), err))
+                        }
+                    }
+                }
+                true
+            }
+            Msg::DismissError => {
+                self.error = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props { face_code, is_female, game } = ctx.props();
+
+        let details = self.face_code_db.as_ref().and_then(|face_code_db| {
+            let face_code = face_code.borrow();
+            if face_code.is_empty() {
+                return None;
+            }
+
+            let html = match face_code_db.decode(&face_code, *game, *is_female) {
+                Ok(sliders) => {
+                    let sliders = sliders.iter().map(|slider| {
+                        html! {
+                            <div class="flex gap-1">
+                                <span class="w-1/3">{ &slider.name }</span>
+                                { format!("{} / {}", slider.value, slider.values) }
+                            </div>
+                        }
+                    });
+                    let translations = GAMES.iter().filter(|other| *other != game).map(|&other| {
+                        let translated = face_code_db
+                            .translate(&face_code, *game, other, *is_female)
+                            .unwrap_or_default();
+                        html! {
+                            <div class="flex gap-1">
+                                <span class="w-1/3">{ format!("Export to {}", other.name()) }</span>
+                                <span class="select-all">{ translated }</span>
+                            </div>
+                        }
+                    });
+
+                    html! {
+                        <Table title="Face code" opened=false helper=
+                            "Slider values of the character creator and the same face code \
+                            translated for the other games, to paste in their character creator."
+                        >
+                            { for translations }
+                            <hr class="border-t border-default-border" />
+                            { for sliders }
+                        </Table>
+                    }
+                }
+                Err(err) => html! {
+                    <p>{ format!("Not a valid face code: {}", err) }</p>
+                },
+            };
+            Some(html)
+        });
+
+        let import = self.face_code_db.as_ref().map(|_| {
+            let buttons = GAMES.iter().filter(|other| *other != game).map(|&other| {
+                html! {
+                    <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                        onclick={ctx.link().callback(move |_| Msg::Import(other))}
+                    >
+                        { format!("import from {}", other.name()) }
+                    </button>
+                }
+            });
+            let error = self.error.as_ref().map(|error| {
+                html! {
+                    <div class="flex items-center gap-1">
+                        <p class="flex-auto">{ error }</p>
+                        <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                            onclick={ctx.link().callback(|_| Msg::DismissError)}
+                        >
+                            {"dismiss"}
+                        </button>
+                    </div>
+                }
+            });
+
+            html! {
+                <>
+                    <div class="flex items-center gap-1">
+                        <InputText key={self.imports} label="Other game code" value={RcRef::clone(&self.import_code)}
+                            helper="Face code of the same Shepard in another game, \
+                            `import` translates it for this game and replaces the `Identity Code`."
+                        />
+                        { for buttons }
+                    </div>
+                    { for error }
+                </>
+            }
+        });
+
+        html! {
+            <>
+                <InputText key={self.imports} label="Identity Code" value={RcRef::clone(face_code)}
+                    oninput={ctx.link().callback(|_| Msg::Changed)}
+                    helper=
+                    "If you change this you can display whatever you want in the menus \
+                    in place of your `Identity Code`.\n\
+                    This will NOT change your face, you have to edit your head morph \
+                    or import one to do so."
+                />
+                { for import }
+                { for details }
+            </>
+        }
+    }
+}
@@ -1,23 +1,65 @@
-use std::cell::{Ref, RefMut};
+use std::{
+    cell::{Ref, RefMut},
+    cmp::Ordering,
+    fmt::Write,
+    path::PathBuf,
+    rc::Rc,
+};
 
+use web_sys::HtmlInputElement;
 use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
-    gui::{components::Table, raw_ui::RawUiChildren},
-    save_data::{shared::appearance::HeadMorph as DataHeadMorph, RcRef},
-    services::save_handler::{Action, SaveHandler},
+    gui::{
+        components::{Helper, InputText, Table},
+        raw_ui::RawUiChildren,
+    },
+    save_data::{
+        shared::{
+            appearance::HeadMorph as DataHeadMorph,
+            head_morph_conversion::{HeadMorphConversionDb, HeadMorphGame},
+            head_morph_edit::HeadMorphSide,
+            head_morph_library::{self, HeadMorphMeta, HeadMorphThumbnail},
+            head_morph_obj::HeadMorphTopologyDb,
+            head_morph_validation::HeadMorphValidationDb,
+        },
+        RcRef,
+    },
+    services::{
+        database::Databases,
+        save_handler::{Action, SaveHandler},
+    },
 };
 
 pub enum Msg {
+    DatabaseLoaded(Databases),
     Import,
+    ImportFromSave,
     HeadMorphImported(DataHeadMorph),
     Export,
+    ImportObj,
+    ObjImported,
+    ExportObj,
+    Symmetrize(HeadMorphSide),
+    SelectBlend,
+    BlendSelected(DataHeadMorph),
+    BlendRatio(InputEvent),
+    Blend,
+    Edited,
     RemoveHeadMorph,
+    LoadLibrary,
+    LibraryLoaded(Vec<(PathBuf, HeadMorphMeta)>),
+    LibraryFilter(InputEvent),
+    AddToLibrary,
+    ApplyFromLibrary(PathBuf),
+    RemoveFromLibrary(PathBuf),
 }
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub head_morph: RcRef<Option<RcRef<DataHeadMorph>>>,
+    pub game: HeadMorphGame,
+    pub is_female: bool,
 }
 
 impl Props {
@@ -33,6 +75,19 @@ impl Props {
 pub struct HeadMorph {
     _db_handle: ContextHandle<SaveHandler>,
     save_handler: SaveHandler,
+    _databases_handle: ContextHandle<Databases>,
+    conversion_db: Option<Rc<HeadMorphConversionDb>>,
+    topology_db: Option<Rc<HeadMorphTopologyDb>>,
+    validation_db: Option<Rc<HeadMorphValidationDb>>,
+    blend_with: Option<DataHeadMorph>,
+    blend_ratio: f32,
+    blend_error: Option<String>,
+    library: Option<Vec<(PathBuf, HeadMorphMeta)>>,
+    library_name: RcRef<String>,
+    library_tags: RcRef<String>,
+    library_filter: String,
+    // Arêtes de l'aperçu pour un nombre de sommets donné
+    wireframe: Option<(usize, Vec<(usize, usize)>)>,
 }
 
 impl Component for HeadMorph {
@@ -43,18 +98,70 @@ impl Component for HeadMorph {
         let (save_handler, _db_handle) =
             ctx.link().context::<SaveHandler>(Callback::noop()).expect("no save handler provider");
 
-        HeadMorph { _db_handle, save_handler }
+        let (databases, _databases_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        ctx.link().send_message(Msg::LoadLibrary);
+
+        let mut head_morph = HeadMorph {
+            _db_handle,
+            save_handler,
+            _databases_handle,
+            conversion_db: databases.clone().get_head_morph_conversion(),
+            topology_db: databases.clone().get_head_morph_topology(),
+            validation_db: databases.get_head_morph_validation(),
+            blend_with: None,
+            blend_ratio: 0.5,
+            blend_error: None,
+            library: None,
+            library_name: Default::default(),
+            library_tags: Default::default(),
+            library_filter: String::new(),
+            wireframe: None,
+        };
+        head_morph.update_wireframe(ctx);
+        head_morph
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.update_wireframe(ctx);
+        true
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.conversion_db = dbs.clone().get_head_morph_conversion();
+                self.topology_db = dbs.clone().get_head_morph_topology();
+                self.validation_db = dbs.get_head_morph_validation();
+                // Les arêtes du modèle remplacent celles des plus proches voisins
+                self.wireframe = None;
+                self.update_wireframe(ctx);
+                true
+            }
             Msg::Import => {
                 let callback = ctx.link().callback(Msg::HeadMorphImported);
                 self.save_handler.action(Action::ImportHeadMorph(callback));
                 false
             }
+            Msg::ImportFromSave => {
+                if let Some(ref conversion_db) = self.conversion_db {
+                    let callback = ctx.link().callback(Msg::HeadMorphImported);
+                    let reference = ctx.props().head_morph().as_ref().map(RcRef::clone);
+                    self.save_handler.action(Action::ImportHeadMorphFromSave(
+                        Rc::clone(conversion_db),
+                        ctx.props().game,
+                        reference,
+                        callback,
+                    ));
+                }
+                false
+            }
             Msg::HeadMorphImported(head_morph) => {
                 *ctx.props().head_morph_mut() = Some(head_morph.into());
+                self.update_wireframe(ctx);
                 true
             }
             Msg::Export => {
@@ -63,10 +170,112 @@ impl Component for HeadMorph {
                 }
                 false
             }
+            Msg::ImportObj => {
+                if let Some(ref head_morph) = *ctx.props().head_morph() {
+                    let callback = ctx.link().callback(|_| Msg::ObjImported);
+                    self.save_handler
+                        .action(Action::ImportHeadMorphObj(RcRef::clone(head_morph), callback));
+                }
+                false
+            }
+            Msg::ObjImported => true,
+            Msg::ExportObj => {
+                if let (Some(head_morph), Some(topology_db)) =
+                    (ctx.props().head_morph().as_ref(), self.topology_db.as_ref())
+                {
+                    self.save_handler.action(Action::ExportHeadMorphObj(
+                        RcRef::clone(head_morph),
+                        Rc::clone(topology_db),
+                    ));
+                }
+                false
+            }
+            Msg::Symmetrize(source) => {
+                if let Some(ref head_morph) = *ctx.props().head_morph() {
+                    head_morph.borrow().symmetrize(source);
+                }
+                true
+            }
+            Msg::SelectBlend => {
+                let callback = ctx.link().callback(Msg::BlendSelected);
+                self.save_handler.action(Action::ImportHeadMorph(callback));
+                false
+            }
+            Msg::BlendSelected(head_morph) => {
+                self.blend_with = Some(head_morph);
+                self.blend_error = None;
+                true
+            }
+            Msg::BlendRatio(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    let value = input.value_as_number();
+                    if !value.is_nan() {
+                        self.blend_ratio = value as f32 / 100.0;
+                    }
+                }
+                true
+            }
+            Msg::Blend => {
+                if let (Some(head_morph), Some(blend_with)) =
+                    (ctx.props().head_morph().as_ref(), self.blend_with.as_ref())
+                {
+                    let result = head_morph.borrow().blend(blend_with, self.blend_ratio);
+                    self.blend_error = result.err().map(|err| err.to_string());
+                }
+                true
+            }
+            Msg::Edited => true,
             Msg::RemoveHeadMorph => {
                 ctx.props().head_morph_mut().take();
                 true
             }
+            Msg::LoadLibrary => {
+                let callback = ctx.link().callback(Msg::LibraryLoaded);
+                self.save_handler.action(Action::LoadHeadMorphLibrary(callback));
+                false
+            }
+            Msg::LibraryLoaded(library) => {
+                self.library = Some(library);
+                true
+            }
+            Msg::LibraryFilter(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.library_filter = input.value();
+                }
+                true
+            }
+            Msg::AddToLibrary => {
+                let name = self.library_name.borrow().trim().to_owned();
+                if let (Some(head_morph), false) =
+                    (ctx.props().head_morph().as_ref(), name.is_empty())
+                {
+                    let Props { game, is_female, .. } = ctx.props();
+                    let meta = HeadMorphMeta {
+                        name,
+                        game: *game,
+                        is_female: *is_female,
+                        tags: head_morph_library::parse_tags(&self.library_tags.borrow()),
+                        thumbnail: thumbnail(&head_morph.borrow()),
+                    };
+                    let callback = ctx.link().callback(|_| Msg::LoadLibrary);
+                    self.save_handler.action(Action::AddHeadMorphToLibrary(
+                        meta,
+                        RcRef::clone(head_morph),
+                        callback,
+                    ));
+                }
+                false
+            }
+            Msg::ApplyFromLibrary(meta_path) => {
+                let callback = ctx.link().callback(Msg::HeadMorphImported);
+                self.save_handler.action(Action::ImportHeadMorphFromLibrary(meta_path, callback));
+                false
+            }
+            Msg::RemoveFromLibrary(meta_path) => {
+                let callback = ctx.link().callback(|_| Msg::LoadLibrary);
+                self.save_handler.action(Action::RemoveHeadMorphFromLibrary(meta_path, callback));
+                false
+            }
         }
     }
 
@@ -79,17 +288,60 @@ impl Component for HeadMorph {
                         {"Export"}
                     </button>
                     <span>{"-"}</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ExportObj)}>
+                        {"Export OBJ"}
+                    </button>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ImportObj)}>
+                        {"Import OBJ"}
+                    </button>
+                    <Helper text=
+                        "One object per LOD (`lod0` to `lod3`), with the faces of the base head \
+                        when its vertex count is in the topology database, vertices only otherwise.\n\
+                        Keep the vertex count and order, objects renamed by your 3D software \
+                        (`lod0.001`...) are still recognized."
+                    />
+                    <span>{"-"}</span>
                     <button class="button" onclick={ctx.link().callback(|_| Msg::RemoveHeadMorph)}>
                         {"Remove head morph"}
                     </button>
                 </>
             }
         });
+        let report = head_morph.as_ref().zip(self.validation_db.as_ref()).and_then(
+            |(head_morph, validation_db)| {
+                let Props { game, is_female, .. } = ctx.props();
+                let report = validation_db.validate(&head_morph.borrow(), *game, *is_female);
+                (!report.is_empty()).then(|| {
+                    let errors = report.errors.iter().map(|error| {
+                        html! { <p>{ format!("Error: {}", error) }</p> }
+                    });
+                    let warnings = report.warnings.iter().map(|warning| {
+                        html! { <p>{ format!("Warning: {}", warning) }</p> }
+                    });
+                    html! {
+                        <Table title="Compatibility" helper=
+                            "Errors will probably crash the game on load, you will be asked \
+                            to confirm before saving.\n\
+                            Warnings are names this editor does not know, they are probably ignored by the game."
+                        >
+                            { for errors }
+                            { for warnings }
+                        </Table>
+                    }
+                })
+            },
+        );
+        let preview = head_morph.as_ref().and_then(|head_morph| self.preview(&head_morph.borrow()));
+        let tools = head_morph.is_some().then(|| self.tools(ctx));
+        let library = self.library(ctx, head_morph.is_some());
+        // Les modifications du tableau remontent jusqu'ici pour mettre à jour l'aperçu
         let raw = head_morph.as_ref().map(|head_morph| {
             html! {
-                <Table title="Raw">
-                    { for head_morph.children() }
-                </Table>
+                <div onchange={ctx.link().callback(|_| Msg::Edited)}>
+                    <Table title="Raw">
+                        { for head_morph.children() }
+                    </Table>
+                </div>
             }
         });
         html! {
@@ -98,11 +350,314 @@ impl Component for HeadMorph {
                     <button class="button" onclick={ctx.link().callback(|_| Msg::Import)}>
                         {"Import"}
                     </button>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::ImportFromSave)}>
+                        {"Import from save"}
+                    </button>
+                    <Helper text=
+                        "Imports the head morph of another save, from any game.\n\
+                        Hair and their textures are converted when their equivalent in this game \
+                        is known, the others are kept as they are: if this game does not have them, \
+                        replace them in the raw data."
+                    />
                     { for export_remove }
                 </div>
                 <hr class="border-t border-default-border" />
+                { for report }
+                { for preview }
+                { for tools }
+                { library }
                 { for raw }
             </div>
         }
     }
 }
+
+type Projection = fn((f32, f32, f32)) -> (f32, f32);
+
+fn positions(head_morph: &DataHeadMorph) -> Vec<(f32, f32, f32)> {
+    head_morph.lod0_vertices.borrow().iter().map(|vertex| vertex.borrow().position()).collect()
+}
+
+// Sans topologie pour ce nombre de sommets, chaque sommet est relié à ses plus proches voisins.
+// Les sommets d'un même modèle sont toujours dans le même ordre, les arêtes calculées
+// sur un head morph servent donc pour tous ceux qui ont le même nombre de sommets
+fn wireframe(positions: &[(f32, f32, f32)]) -> Vec<(usize, usize)> {
+    const NEIGHBOURS: usize = 3;
+    // Sommets dupliqués aux coutures du modèle
+    const SAME_POSITION: f32 = 1e-6;
+
+    let mut edges = Vec::new();
+    for (idx, &(x, y, z)) in positions.iter().enumerate() {
+        let mut nearest: Vec<(f32, usize)> = positions
+            .iter()
+            .enumerate()
+            .map(|(other, &(ox, oy, oz))| {
+                ((ox - x).powi(2) + (oy - y).powi(2) + (oz - z).powi(2), other)
+            })
+            .filter(|&(distance, _)| distance > SAME_POSITION)
+            .collect();
+
+        let len = nearest.len().min(NEIGHBOURS);
+        if len == 0 {
+            continue;
+        }
+        nearest.select_nth_unstable_by(len - 1, |a, b| {
+            a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal)
+        });
+        edges.extend(nearest[..len].iter().map(|&(_, other)| (idx.min(other), idx.max(other))));
+    }
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+// Arêtes si elles sont connues, nuage de points sinon
+fn project_vertices(
+    positions: &[(f32, f32, f32)], edges: Option<&[(usize, usize)]>, project: Projection,
+) -> HeadMorphThumbnail {
+    let points: Vec<(f32, f32)> = positions.iter().copied().map(project).collect();
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    for &(x, y) in &points {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+    let margin = (max_x - min_x).max(max_y - min_y) * 0.05;
+    let view_box = format!(
+        "{} {} {} {}",
+        min_x - margin,
+        min_y - margin,
+        max_x - min_x + margin * 2.0,
+        max_y - min_y + margin * 2.0
+    );
+
+    let mut path = String::new();
+    match edges {
+        Some(edges) => {
+            for &(a, b) in edges {
+                if let (Some(a), Some(b)) = (points.get(a), points.get(b)) {
+                    let _ = write!(path, "M{:.2} {:.2}L{:.2} {:.2}", a.0, a.1, b.0, b.1);
+                }
+            }
+        }
+        None => {
+            for (x, y) in &points {
+                let _ = write!(path, "M{:.2} {:.2}h0", x, y);
+            }
+        }
+    }
+    HeadMorphThumbnail { view_box, path }
+}
+
+// Vue de face en nuage de points, un sommet sur quatre suffit pour une miniature
+fn thumbnail(head_morph: &DataHeadMorph) -> HeadMorphThumbnail {
+    let positions: Vec<_> = positions(head_morph).into_iter().step_by(4).collect();
+    if positions.is_empty() {
+        return HeadMorphThumbnail::default();
+    }
+    project_vertices(&positions, None, |(_, y, z)| (-y, -z))
+}
+
+impl HeadMorph {
+    fn update_wireframe(&mut self, ctx: &Context<Self>) {
+        let positions = match *ctx.props().head_morph() {
+            Some(ref head_morph) => positions(&head_morph.borrow()),
+            None => return,
+        };
+
+        let is_up_to_date = matches!(self.wireframe, Some((len, _)) if len == positions.len());
+        if !is_up_to_date {
+            let topology =
+                self.topology_db.as_ref().and_then(|topology_db| topology_db.get(&positions.len()));
+            let edges = match topology {
+                Some(topology) => topology.edges(),
+                None => wireframe(&positions),
+            };
+            self.wireframe = Some((positions.len(), edges));
+        }
+    }
+
+    fn preview(&self, head_morph: &DataHeadMorph) -> Option<Html> {
+        let positions = positions(head_morph);
+        if positions.is_empty() {
+            return None;
+        }
+
+        let edges = self
+            .wireframe
+            .as_ref()
+            .filter(|(len, _)| *len == positions.len())
+            .map(|(_, edges)| edges.as_slice());
+
+        // Le visage regarde vers +X, Z vers le haut (Y du SVG vers le bas)
+        let views: [(&str, Projection); 2] =
+            [("Front", |(_, y, z)| (-y, -z)), ("Side", |(x, _, z)| (x, -z))];
+
+        let views = views.iter().map(|(title, project)| {
+            let HeadMorphThumbnail { view_box, path } =
+                project_vertices(&positions, edges, *project);
+            let stroke_width = if edges.is_some() { "0.5" } else { "2" };
+
+            html! {
+                <div class="flex-1 flex flex-col items-center">
+                    <svg class="w-full h-72" viewBox={view_box} preserveAspectRatio="xMidYMid meet">
+                        <path d={path} fill="none" stroke="currentColor" stroke-width={stroke_width}
+                            stroke-linecap="round" vector-effect="non-scaling-stroke"
+                        />
+                    </svg>
+                    <span>{ title }</span>
+                </div>
+            }
+        });
+
+        Some(html! {
+            <Table title="Preview" helper=
+                "Orthographic views of the LOD0 vertices, updated after each vertex edit.\n\
+                The wireframe uses the faces of the topology database, or joins each vertex \
+                to its nearest neighbours for an unknown vertex count.\n\
+                Offset bones are not drawn, how they move the vertices depends on the skinning \
+                of the game models which is not known either."
+            >
+                <div class="flex gap-2">
+                    { for views }
+                </div>
+            </Table>
+        })
+    }
+
+    fn tools(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let blend = self.blend_with.as_ref().map(|_| {
+            let percent = (self.blend_ratio * 100.0).round();
+            html! {
+                <>
+                    <input type="range" min="0" max="100" value={percent.to_string()}
+                        oninput={link.callback(Msg::BlendRatio)}
+                    />
+                    <span>{ format!("{}%", percent) }</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Blend)}>
+                        {"Blend"}
+                    </button>
+                </>
+            }
+        });
+        let blend_error = self.blend_error.as_ref().map(|error| {
+            html! { <p>{ format!("Failed to blend: {}", error) }</p> }
+        });
+
+        html! {
+            <Table title="Tools" helper=
+                "Mirror: copies one side of the face onto the other, vertices and bones. \
+                The game uses X forward, Y from the left to the right of the character and Z up, \
+                the face is mirrored across its middle plane (Y = 0).\n\
+                Blend: moves this head morph toward another one, from 0% (unchanged) to 100% \
+                (same sliders, vertices, bones and colors as the other head morph). \
+                Both head morphs must come from the same game."
+            >
+                <div class="flex items-center gap-2">
+                    <button class="button"
+                        onclick={link.callback(|_| Msg::Symmetrize(HeadMorphSide::Left))}
+                    >
+                        {"Mirror left side"}
+                    </button>
+                    <button class="button"
+                        onclick={link.callback(|_| Msg::Symmetrize(HeadMorphSide::Right))}
+                    >
+                        {"Mirror right side"}
+                    </button>
+                </div>
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={link.callback(|_| Msg::SelectBlend)}>
+                        {"Select head morph to blend"}
+                    </button>
+                    { for blend }
+                </div>
+                { for blend_error }
+            </Table>
+        }
+    }
+
+    fn library(&self, ctx: &Context<Self>, has_head_morph: bool) -> Html {
+        let link = ctx.link();
+        let add = has_head_morph.then(|| {
+            html! {
+                <div class="flex items-center gap-2">
+                    <InputText label="Name" value={RcRef::clone(&self.library_name)} />
+                    <InputText label="Tags" value={RcRef::clone(&self.library_tags)}
+                        helper="Separated by commas"
+                    />
+                    <button class="button" onclick={link.callback(|_| Msg::AddToLibrary)}>
+                        {"Add to library"}
+                    </button>
+                </div>
+            }
+        });
+
+        let entries = match self.library {
+            Some(ref library) => {
+                let entries = library
+                    .iter()
+                    .filter(|(_, meta)| meta.matches(&self.library_filter))
+                    .map(|(meta_path, meta)| {
+                        let apply = {
+                            let meta_path = meta_path.clone();
+                            link.callback(move |_| Msg::ApplyFromLibrary(meta_path.clone()))
+                        };
+                        let remove = {
+                            let meta_path = meta_path.clone();
+                            link.callback(move |_| Msg::RemoveFromLibrary(meta_path.clone()))
+                        };
+                        let gender = if meta.is_female { "Female" } else { "Male" };
+                        let HeadMorphThumbnail { view_box, path } = meta.thumbnail.clone();
+                        html! {
+                            <div class="flex items-center gap-2">
+                                <svg class="w-12 h-12 flex-none" viewBox={view_box}
+                                    preserveAspectRatio="xMidYMid meet"
+                                >
+                                    <path d={path} fill="none" stroke="currentColor" stroke-width="1"
+                                        stroke-linecap="round" vector-effect="non-scaling-stroke"
+                                    />
+                                </svg>
+                                <div class="flex-auto flex flex-col">
+                                    <span>{ &meta.name }</span>
+                                    <span class="text-sm">
+                                        { format!("{} - {}", meta.game.name(), gender) }
+                                        { for (!meta.tags.is_empty()).then(|| format!(" - {}", meta.tags.join(", "))) }
+                                    </span>
+                                </div>
+                                <button class="button" onclick={apply}>{"Apply"}</button>
+                                <button class="button" onclick={remove}>{"Remove"}</button>
+                            </div>
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                if entries.is_empty() {
+                    html! { <p>{"<empty>"}</p> }
+                } else {
+                    entries.into_iter().collect::<Html>()
+                }
+            }
+            None => html! { <p>{"Loading..."}</p> },
+        };
+
+        html! {
+            <Table title="Library" helper=
+                "Head morphs saved on this computer with their game, gender and tags.\n\
+                Apply replaces the current head morph, adding a head morph with an existing name replaces it."
+            >
+                { for add }
+                <label class="flex-auto flex items-center gap-1">
+                    <input type="text" class="flex-auto input" placeholder="<empty>"
+                        value={self.library_filter.clone()}
+                        oninput={link.callback(Msg::LibraryFilter)}
+                    />
+                    {"Filter"}
+                </label>
+                { entries }
+            </Table>
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{components::Table, mass_effect_2::Me2Type},
+    save_data::{
+        mass_effect_3::Me3SaveGame,
+        shared::level_repair::{LevelRepairAction, LevelRepairDb},
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+#[derive(Clone)]
+pub enum LevelRepairType {
+    Me2(Me2Type),
+    Me3(RcRef<Me3SaveGame>),
+}
+
+impl PartialEq for LevelRepairType {
+    fn eq(&self, other: &LevelRepairType) -> bool {
+        match (self, other) {
+            (LevelRepairType::Me2(me2), LevelRepairType::Me2(other)) => me2 == other,
+            (LevelRepairType::Me3(me3), LevelRepairType::Me3(other)) => me3 == other,
+            _ => false,
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Repair(LevelRepairAction),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub save_game: LevelRepairType,
+}
+
+pub struct LevelRepairTool {
+    _db_handle: ContextHandle<Databases>,
+    level_repair_db: Option<Rc<LevelRepairDb>>,
+}
+
+impl Component for LevelRepairTool {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        let level_repair_db = Self::level_repair_db(ctx, databases);
+        LevelRepairTool { _db_handle, level_repair_db }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.level_repair_db = Self::level_repair_db(ctx, dbs);
+                true
+            }
+            Msg::Repair(action) => {
+                if let Some(ref level_repair_db) = self.level_repair_db {
+                    match ctx.props().save_game {
+                        LevelRepairType::Me2(Me2Type::Vanilla(ref me2)) => {
+                            let me2 = me2.borrow();
+                            if let Some(repair) = me2.level_repair(level_repair_db) {
+                                me2.repair_level(repair, action);
+                            }
+                        }
+                        LevelRepairType::Me2(Me2Type::Legendary(ref me2)) => {
+                            let me2 = me2.borrow();
+                            if let Some(repair) = me2.level_repair(level_repair_db) {
+                                me2.repair_level(repair, action);
+                            }
+                        }
+                        LevelRepairType::Me3(ref me3) => {
+                            let me3 = me3.borrow();
+                            if let Some(repair) = me3.level_repair(level_repair_db) {
+                                me3.repair_level(repair, action);
+                            }
+                        }
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let content = self.level_repair_db.as_ref().map(|level_repair_db| {
+            let repair = match ctx.props().save_game {
+                LevelRepairType::Me2(Me2Type::Vanilla(ref me2)) => {
+                    me2.borrow().level_repair(level_repair_db)
+                }
+                LevelRepairType::Me2(Me2Type::Legendary(ref me2)) => {
+                    me2.borrow().level_repair(level_repair_db)
+                }
+                LevelRepairType::Me3(ref me3) => me3.borrow().level_repair(level_repair_db),
+            };
+
+            if let Some(repair) = repair {
+                // Seules les actions renseignées dans le profil du niveau sont proposées
+                let button = |label: &'static str, action: LevelRepairAction, is_known: bool| {
+                    is_known.then(|| html! {
+                        <button class="button" onclick={ctx.link().callback(move |_| Msg::Repair(action))}>
+                            { label }
+                        </button>
+                    })
+                };
+                html! {
+                    <>
+                        { for button(
+                            "Reset streaming states",
+                            LevelRepairAction::ResetStreamingStates,
+                            !repair.streaming_states.is_empty(),
+                        ) }
+                        { for button(
+                            "Clear stale kismet records",
+                            LevelRepairAction::ClearStaleKismetRecords,
+                            !repair.stale_kismet_records.is_empty(),
+                        ) }
+                        { for button("Reset doors", LevelRepairAction::ResetDoors, !repair.doors.is_empty()) }
+                    </>
+                }
+            } else {
+                html! { "No repair profile for this level" }
+            }
+        });
+
+        html! {
+            <Table title="Repair level" opened=false helper=
+                "Resets the streaming states, the kismet records and the doors of the save's level\n\
+                to a known-good profile. Use it if the save loads in a broken state.\n\
+                Only the actions known for the level are listed."
+            >
+                { for content }
+            </Table>
+        }
+    }
+}
+
+impl LevelRepairTool {
+    fn level_repair_db(ctx: &Context<Self>, dbs: Databases) -> Option<Rc<LevelRepairDb>> {
+        match ctx.props().save_game {
+            LevelRepairType::Me2(_) => dbs.get_me2_level_repair(),
+            LevelRepairType::Me3(_) => dbs.get_me3_level_repair(),
+        }
+    }
+}
@@ -1,10 +1,19 @@
+mod appearance;
 mod bonus_powers;
+mod dlc_dependencies;
+mod face_code;
 mod head_morph;
+mod level_repair;
 mod link;
 mod plot_category;
 mod raw_plot;
+mod teleport;
+mod world_objects;
 
-pub use self::{bonus_powers::*, head_morph::*, link::*, plot_category::*, raw_plot::*};
+pub use self::{
+    appearance::*, bonus_powers::*, dlc_dependencies::*, face_code::*, head_morph::*,
+    level_repair::*, link::*, plot_category::*, raw_plot::*, teleport::*, world_objects::*,
+};
 
 use indexmap::IndexMap;
 use yew::prelude::*;
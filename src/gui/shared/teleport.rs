@@ -0,0 +1,137 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{components::Table, mass_effect_2::Me2Type},
+    save_data::{
+        mass_effect_1_le::Me1LeSaveData, mass_effect_3::Me3SaveGame, shared::teleport::TeleportDb,
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+#[derive(Clone)]
+pub enum TeleportType {
+    Me1Le(RcRef<Me1LeSaveData>),
+    Me2(Me2Type),
+    Me3(RcRef<Me3SaveGame>),
+}
+
+impl PartialEq for TeleportType {
+    fn eq(&self, other: &TeleportType) -> bool {
+        match (self, other) {
+            (TeleportType::Me1Le(me1), TeleportType::Me1Le(other)) => me1 == other,
+            (TeleportType::Me2(me2), TeleportType::Me2(other)) => me2 == other,
+            (TeleportType::Me3(me3), TeleportType::Me3(other)) => me3 == other,
+            _ => false,
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Teleport(usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub save_game: TeleportType,
+}
+
+pub struct Teleport {
+    _db_handle: ContextHandle<Databases>,
+    teleport_db: Option<Rc<TeleportDb>>,
+}
+
+impl Component for Teleport {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        let teleport_db = Self::teleport_db(ctx, databases);
+        Teleport { _db_handle, teleport_db }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.teleport_db = Self::teleport_db(ctx, dbs);
+                true
+            }
+            Msg::Teleport(idx) => {
+                if let Some(point) = self.teleport_db.as_ref().and_then(|db| db.get(idx)) {
+                    match ctx.props().save_game {
+                        TeleportType::Me1Le(ref me1) => me1.borrow().teleport(point),
+                        TeleportType::Me2(Me2Type::Vanilla(ref me2)) => {
+                            me2.borrow().teleport(point)
+                        }
+                        TeleportType::Me2(Me2Type::Legendary(ref me2)) => {
+                            me2.borrow().teleport(point)
+                        }
+                        TeleportType::Me3(ref me3) => me3.borrow().teleport(point),
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let points = self.teleport_db.as_ref().map(|teleport_db| {
+            teleport_db
+                .iter()
+                .enumerate()
+                .map(|(idx, point)| {
+                    html! {
+                        <div class="flex items-center gap-1">
+                            <div class="py-px">
+                                <a class={classes![
+                                        "rounded-none",
+                                        "select-none",
+                                        "hover:bg-theme-hover",
+                                        "active:bg-theme-active",
+                                        "bg-theme-bg",
+                                        "px-1",
+                                        "py-0",
+                                        "cursor-pointer",
+                                    ]}
+                                    onclick={ctx.link().callback(move |_| Msg::Teleport(idx))}
+                                >
+                                    {"move"}
+                                </a>
+                            </div>
+                            { &point.name }
+                        </div>
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        html! {
+            <Table title="Move Shepard to..." opened=false helper=
+                "Changes the level, the position and the loaded levels of the save.\n\
+                Use it to escape a stuck save.\n\
+                Only the spawn points recorded from real saves are listed."
+            >
+                { for points.into_iter().flatten() }
+            </Table>
+        }
+    }
+}
+
+impl Teleport {
+    fn teleport_db(ctx: &Context<Self>, dbs: Databases) -> Option<Rc<TeleportDb>> {
+        match ctx.props().save_game {
+            TeleportType::Me1Le(_) => dbs.get_me1_le_teleport(),
+            TeleportType::Me2(_) => dbs.get_me2_teleport(),
+            TeleportType::Me3(_) => dbs.get_me3_teleport(),
+        }
+    }
+}
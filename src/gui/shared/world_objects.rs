@@ -0,0 +1,246 @@
+use std::rc::Rc;
+
+use web_sys::HtmlInputElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{components::Table, mass_effect_2::Me2Type, raw_ui::RawUi},
+    save_data::{
+        mass_effect_3::{Me3SaveGame, Placeable},
+        shared::{guid_db::GuidDb, Door, Kismet},
+        Guid, RcRef,
+    },
+    services::database::Databases,
+};
+
+#[derive(Clone)]
+pub enum WorldObjectsType {
+    Me2(Me2Type),
+    Me3(RcRef<Me3SaveGame>),
+}
+
+impl PartialEq for WorldObjectsType {
+    fn eq(&self, other: &WorldObjectsType) -> bool {
+        match (self, other) {
+            (WorldObjectsType::Me2(me2), WorldObjectsType::Me2(other)) => me2 == other,
+            (WorldObjectsType::Me3(me3), WorldObjectsType::Me3(other)) => me3 == other,
+            _ => false,
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Filter(InputEvent),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub save_game: WorldObjectsType,
+}
+
+pub struct WorldObjects {
+    _db_handle: ContextHandle<Databases>,
+    guid_db: Option<Rc<GuidDb>>,
+    filter: String,
+}
+
+impl Component for WorldObjects {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        let guid_db = Self::guid_db(ctx, databases);
+        WorldObjects { _db_handle, guid_db, filter: String::new() }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.guid_db = Self::guid_db(ctx, dbs);
+                true
+            }
+            Msg::Filter(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.filter = input.value().to_lowercase();
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if let Some(ref guid_db) = self.guid_db {
+            let (kismet_records, doors, placeables, pawns) = match ctx.props().save_game {
+                WorldObjectsType::Me2(Me2Type::Vanilla(ref me2)) => {
+                    let me2 = me2.borrow();
+                    (
+                        RcRef::clone(&me2.kismet_records),
+                        RcRef::clone(&me2.doors),
+                        None,
+                        RcRef::clone(&me2.pawns),
+                    )
+                }
+                WorldObjectsType::Me2(Me2Type::Legendary(ref me2)) => {
+                    let me2 = me2.borrow();
+                    (
+                        RcRef::clone(&me2.kismet_records),
+                        RcRef::clone(&me2.doors),
+                        None,
+                        RcRef::clone(&me2.pawns),
+                    )
+                }
+                WorldObjectsType::Me3(ref me3) => {
+                    let me3 = me3.borrow();
+                    (
+                        RcRef::clone(&me3.kismet_records),
+                        RcRef::clone(&me3.doors),
+                        Some(RcRef::clone(&me3.placeables)),
+                        RcRef::clone(&me3.pawns),
+                    )
+                }
+            };
+
+            let kismet_records = self.kismet_records(guid_db, &kismet_records);
+            let doors = self.doors(guid_db, &doors);
+            let placeables = placeables.map(|placeables| self.placeables(guid_db, &placeables));
+            let pawns = self.pawns(guid_db, &pawns);
+
+            html! {
+                <div class="flex-auto flex flex-col gap-1">
+                    <div class="flex gap-3 w-2/3">
+                        <label class="flex-auto flex items-center gap-1">
+                            <input type="text" class="flex-auto input" placeholder="<empty>" value={self.filter.clone()}
+                                oninput={ctx.link().callback(Msg::Filter)}
+                            />
+                            { "Filter" }
+                        </label>
+                    </div>
+                    <hr class="border-t border-default-border" />
+                    <div class="flex-auto flex flex-col gap-1 h-0 overflow-y-auto">
+                        { kismet_records }
+                        { doors }
+                        { for placeables }
+                        { pawns }
+                    </div>
+                </div>
+            }
+        } else {
+            html! {
+                <>
+                    <p>{ "Loading database..." }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        }
+    }
+}
+
+impl WorldObjects {
+    fn guid_db(ctx: &Context<Self>, dbs: Databases) -> Option<Rc<GuidDb>> {
+        match ctx.props().save_game {
+            WorldObjectsType::Me2(_) => dbs.get_me2_guid(),
+            WorldObjectsType::Me3(_) => dbs.get_me3_guid(),
+        }
+    }
+
+    // Le nom de la base de données si connu, le guid sinon
+    fn label(&self, guid_db: &GuidDb, guid: &Guid) -> Option<(String, String)> {
+        let hyphenated = guid.hyphenated();
+        let label = guid_db.find(guid).map(|object| object.label());
+
+        let is_match = self.filter.is_empty()
+            || hyphenated.contains(&self.filter)
+            || label
+                .as_ref()
+                .map(|label| label.to_lowercase().contains(&self.filter))
+                .unwrap_or(false);
+
+        is_match.then(|| (label.unwrap_or_else(|| hyphenated.clone()), hyphenated))
+    }
+
+    fn kismet_records(&self, guid_db: &GuidDb, kismet_records: &RcRef<Vec<RcRef<Kismet>>>) -> Html {
+        let kismet_records = kismet_records.borrow();
+        let rows = kismet_records.iter().filter_map(|kismet| {
+            let kismet = kismet.borrow();
+            let (label, guid) = self.label(guid_db, &kismet.guid())?;
+            Some(html! {
+                <div class="flex items-center gap-1 min-w-0" title={guid}>
+                    { kismet.value.view(&label) }
+                </div>
+            })
+        });
+
+        html! {
+            <Table title="Kismet records">
+                { for rows }
+            </Table>
+        }
+    }
+
+    fn doors(&self, guid_db: &GuidDb, doors: &RcRef<Vec<RcRef<Door>>>) -> Html {
+        let doors = doors.borrow();
+        let rows = doors.iter().filter_map(|door| {
+            let door = door.borrow();
+            let (label, guid) = self.label(guid_db, &door.guid())?;
+            Some(html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    <span class="w-1/3 truncate" title={guid}>{ label }</span>
+                    { door.current_state.view("Current state") }
+                    { door.old_state.view("Old state") }
+                </div>
+            })
+        });
+
+        html! {
+            <Table title="Doors">
+                { for rows }
+            </Table>
+        }
+    }
+
+    fn placeables(&self, guid_db: &GuidDb, placeables: &RcRef<Vec<RcRef<Placeable>>>) -> Html {
+        let placeables = placeables.borrow();
+        let rows = placeables.iter().filter_map(|placeable| {
+            let placeable = placeable.borrow();
+            let (label, guid) = self.label(guid_db, &placeable.guid())?;
+            Some(html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    <span class="w-1/3 truncate" title={guid}>{ label }</span>
+                    { placeable.is_destroyed.view("Destroyed") }
+                    { placeable.is_deactivated.view("Deactivated") }
+                </div>
+            })
+        });
+
+        html! {
+            <Table title="Placeables">
+                { for rows }
+            </Table>
+        }
+    }
+
+    fn pawns(&self, guid_db: &GuidDb, pawns: &RcRef<Vec<RcRef<Guid>>>) -> Html {
+        let pawns = pawns.borrow();
+        let rows = pawns.iter().filter_map(|pawn| {
+            let (label, guid) = self.label(guid_db, &pawn.borrow())?;
+            Some(html! {
+                <span class="truncate" title={guid}>{ label }</span>
+            })
+        });
+
+        html! {
+            <Table title="Pawns">
+                { for rows }
+            </Table>
+        }
+    }
+}
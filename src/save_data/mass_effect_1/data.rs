@@ -1,7 +1,17 @@
-use anyhow::Result;
-use serde::{de, Serialize};
+use std::{fmt, marker::PhantomData};
 
-use super::{player::Name, List};
+use anyhow::{bail, Result};
+use serde::{
+    de,
+    ser::{self, SerializeStructVariant},
+    Serialize, Serializer,
+};
+
+use super::{
+    player::Name,
+    schema::{ArrayKind, Me1Schema},
+    List,
+};
 use crate::{
     save_data::{
         shared::{appearance::LinearColor, Rotator, Vector},
@@ -18,12 +28,22 @@ pub struct Data {
 }
 
 impl Data {
-    pub fn visit_seq<'de, A>(names: &[Name], seq: &mut A) -> Result<Self, A::Error>
+    // Données vides, le jeu utilise les valeurs par défaut de la classe
+    pub fn new(none_name_id: u32) -> Self {
+        Data {
+            _osef: Dummy([0xff; 4]),
+            properties: vec![RcRef::new(Property::none(none_name_id))].into(),
+        }
+    }
+
+    pub fn visit_seq<'de, A>(
+        names: &[Name], schema: &Me1Schema, class_name: &str, seq: &mut A,
+    ) -> Result<Self, A::Error>
     where
         A: de::SeqAccess<'de>,
     {
         let _osef = seq.next_element()?.unwrap();
-        let properties = List::<RcRef<Property>>::visit_seq(names, seq)?;
+        let properties = List::<RcRef<Property>>::visit_seq(names, schema, Some(class_name), seq)?;
         Ok(Self { _osef, properties })
     }
 
@@ -36,12 +56,17 @@ impl Data {
     }
 }
 
-fn get_name(names: &[Name], id: u32) -> String {
-    names[id as usize].string.borrow().clone()
+fn get_name<E: de::Error>(names: &[Name], id: u32) -> Result<String, E> {
+    names
+        .get(id as usize)
+        .map(|name| name.string.borrow().clone())
+        .ok_or_else(|| E::custom(format!("invalid name id: {}", id)))
 }
 
 impl List<RcRef<Property>> {
-    pub fn visit_seq<'de, A>(names: &[Name], seq: &mut A) -> Result<Self, A::Error>
+    pub fn visit_seq<'de, A>(
+        names: &[Name], schema: &Me1Schema, owner: Option<&str>, seq: &mut A,
+    ) -> Result<Self, A::Error>
     where
         A: de::SeqAccess<'de>,
     {
@@ -49,7 +74,7 @@ impl List<RcRef<Property>> {
 
         let mut finished = false;
         while !finished {
-            let property = Property::visit_seq(names, seq)?;
+            let property = Property::visit_seq(names, schema, owner, seq)?;
 
             // Ça se termine toujours par un None donc on break ici
             if let Property::None { .. } = property {
@@ -60,25 +85,49 @@ impl List<RcRef<Property>> {
 
         Ok(properties.into())
     }
+
+    // Ajoute une propriété juste avant le `None` final
+    pub fn add_property(&mut self, property: Property) -> RcRef<Property> {
+        let property = RcRef::new(property);
+        let idx = match self.last() {
+            Some(last) if matches!(*last.borrow(), Property::None { .. }) => self.len() - 1,
+            _ => self.len(),
+        };
+        self.insert(idx, RcRef::clone(&property));
+        property
+    }
+
+    // Supprime une propriété, le `None` final ne peut pas être supprimé
+    pub fn remove_property(&mut self, property: &RcRef<Property>) -> bool {
+        if matches!(*property.borrow(), Property::None { .. }) {
+            return false;
+        }
+        match self.iter().position(|p| p == property) {
+            Some(idx) => {
+                self.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Clone)]
 pub enum Property {
     Array {
         name_id: u32,
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         array: Vec<ArrayType>,
+        kind: Option<ArrayKind>,
     },
     Bool {
         name_id: u32,
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         value: RcCell<bool>,
     },
@@ -87,7 +136,6 @@ pub enum Property {
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         value: RcCell<u8>,
     },
@@ -96,7 +144,6 @@ pub enum Property {
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         value: RcCell<f32>,
     },
@@ -105,7 +152,6 @@ pub enum Property {
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         value: RcCell<i32>,
     },
@@ -114,7 +160,6 @@ pub enum Property {
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         value_name_id: RcCell<u32>,
         _osef4: Dummy<4>,
@@ -124,7 +169,6 @@ pub enum Property {
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         object_id: i32,
     },
@@ -133,7 +177,6 @@ pub enum Property {
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         string: RcRef<String>,
     },
@@ -142,7 +185,6 @@ pub enum Property {
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         value: RcCell<i32>,
     },
@@ -151,7 +193,6 @@ pub enum Property {
         _osef1: Dummy<4>,
         type_id: u32,
         _osef2: Dummy<4>,
-        size: u32,
         _osef3: Dummy<4>,
         struct_name_id: u32,
         _osef4: Dummy<4>,
@@ -164,7 +205,120 @@ pub enum Property {
 }
 
 impl Property {
-    pub fn visit_seq<'de, A>(names: &[Name], seq: &mut A) -> Result<Self, A::Error>
+    pub fn new(name_id: u32, type_id: u32, value: PropertyValue) -> Self {
+        let (_osef1, _osef2, _osef3) = Default::default();
+        match value {
+            PropertyValue::Bool(value) => {
+                Property::Bool { name_id, _osef1, type_id, _osef2, _osef3, value: value.into() }
+            }
+            PropertyValue::Byte(value) => {
+                Property::Byte { name_id, _osef1, type_id, _osef2, _osef3, value: value.into() }
+            }
+            PropertyValue::Float(value) => {
+                Property::Float { name_id, _osef1, type_id, _osef2, _osef3, value: value.into() }
+            }
+            PropertyValue::Int(value) => {
+                Property::Int { name_id, _osef1, type_id, _osef2, _osef3, value: value.into() }
+            }
+            PropertyValue::Name(value_name_id) => Property::Name {
+                name_id,
+                _osef1,
+                type_id,
+                _osef2,
+                _osef3,
+                value_name_id: value_name_id.into(),
+                _osef4: Default::default(),
+            },
+            PropertyValue::Str(string) => {
+                Property::Str { name_id, _osef1, type_id, _osef2, _osef3, string: string.into() }
+            }
+            PropertyValue::StringRef(value) => Property::StringRef {
+                name_id,
+                _osef1,
+                type_id,
+                _osef2,
+                _osef3,
+                value: value.into(),
+            },
+            PropertyValue::Array => Property::Array {
+                name_id,
+                _osef1,
+                type_id,
+                _osef2,
+                _osef3,
+                array: Vec::new(),
+                kind: None,
+            },
+        }
+    }
+
+    pub fn none(none_name_id: u32) -> Self {
+        Property::None { name_id: none_name_id, _osef: Default::default() }
+    }
+
+    pub fn name_id(&self) -> u32 {
+        match self {
+            Property::Array { name_id, .. }
+            | Property::Bool { name_id, .. }
+            | Property::Byte { name_id, .. }
+            | Property::Float { name_id, .. }
+            | Property::Int { name_id, .. }
+            | Property::Name { name_id, .. }
+            | Property::Object { name_id, .. }
+            | Property::Str { name_id, .. }
+            | Property::StringRef { name_id, .. }
+            | Property::Struct { name_id, .. }
+            | Property::None { name_id, .. } => *name_id,
+        }
+    }
+
+    pub fn new_array_item(&self, none_name_id: u32) -> Option<ArrayType> {
+        match self {
+            Property::Array { kind, .. } => match kind {
+                Some(ArrayKind::Int) => Some(ArrayType::Int(Default::default())),
+                Some(ArrayKind::Object) => Some(ArrayType::Object(0)),
+                Some(ArrayKind::Vector) => Some(ArrayType::Vector(Default::default())),
+                Some(ArrayKind::String) => Some(ArrayType::String(Default::default())),
+                // Le format des octets bruts est inconnu, on ne sait pas en créer
+                Some(ArrayKind::Raw) => None,
+                // Une struct vide, le jeu utilise les valeurs par défaut
+                Some(ArrayKind::Struct(_)) | None => Some(ArrayType::Properties(
+                    vec![RcRef::new(Property::none(none_name_id))].into(),
+                )),
+            },
+            _ => None,
+        }
+    }
+
+    pub fn add_array_item(&mut self, item: ArrayType) {
+        if let Property::Array { array, .. } = self {
+            array.push(item);
+        }
+    }
+
+    pub fn remove_array_item(&mut self, idx: usize) {
+        if let Property::Array { array, .. } = self {
+            if idx < array.len() {
+                array.remove(idx);
+            }
+        }
+    }
+
+    // Taille des données sans l'en-tête de la propriété, calculée à la sérialisation
+    pub fn data_size(&self) -> Result<u32> {
+        let total_size = self.size()? as u32;
+        Ok(match self {
+            // La valeur d'un bool n'est pas comptée
+            Property::Bool { .. } | Property::None { .. } => 0,
+            // Ni le nom de la struct
+            Property::Struct { .. } => total_size - 32,
+            _ => total_size - 24,
+        })
+    }
+
+    pub fn visit_seq<'de, A>(
+        names: &[Name], schema: &Me1Schema, owner: Option<&str>, seq: &mut A,
+    ) -> Result<Self, A::Error>
     where
         A: de::SeqAccess<'de>,
     {
@@ -172,7 +326,7 @@ impl Property {
         let name_id = seq.next_element()?.unwrap();
         let _osef1 = seq.next_element()?.unwrap();
 
-        let name = get_name(names, name_id);
+        let name = get_name(names, name_id)?;
         if name == "None" {
             return Ok(Property::None { name_id, _osef: _osef1 });
         }
@@ -181,63 +335,67 @@ impl Property {
         let type_id = seq.next_element()?.unwrap();
         let _osef2 = seq.next_element()?.unwrap();
         // Size
-        let size = seq.next_element()?.unwrap();
+        let size: u32 = seq.next_element()?.unwrap();
         let _osef3 = seq.next_element()?.unwrap();
 
-        let type_name = get_name(names, type_id);
+        let type_name = get_name(names, type_id)?;
         let property = match type_name.as_str() {
             "ArrayProperty" => {
                 let len: u32 = seq.next_element()?.unwrap();
-                let mut array = Vec::new();
-                // Hardcodé sinon je dois chercher dans toutes les classes du jeu...
-                match name.as_str() {
-                    "m_PrereqTalentIDArray" | "m_PrereqTalentRankArray" => {
-                        for _ in 0..len {
-                            let array_int = ArrayType::Int(seq.next_element()?.unwrap());
-                            array.push(array_int);
-                        }
-                    }
-                    "m_aItem"
-                    | "m_aXMod"
-                    | "m_aEquipped"
-                    | "m_QuickSlotArray"
-                    | "m_savedBuybackItems" => {
-                        for _ in 0..len {
-                            let array_object = ArrayType::Object(seq.next_element()?.unwrap());
-                            array.push(array_object);
-                        }
-                    }
-                    "m_vPosition" => {
-                        for _ in 0..len {
-                            let array_vector = ArrayType::Vector(seq.next_element()?.unwrap());
-                            array.push(array_vector);
-                        }
-                    }
-                    "m_DependentPackages" => {
+                // Les types des éléments ne sont pas dans la save, on les cherche dans le schéma
+                let mut kind = schema.array_kind(owner, &name).cloned();
+                let array = match kind {
+                    Some(ref known) if !matches!(known, ArrayKind::Raw) => {
+                        let mut array = Vec::new();
                         for _ in 0..len {
-                            let array_string = ArrayType::String(seq.next_element()?.unwrap());
-                            array.push(array_string);
+                            let item = match known {
+                                ArrayKind::Int => ArrayType::Int(seq.next_element()?.unwrap()),
+                                ArrayKind::Object => {
+                                    ArrayType::Object(seq.next_element()?.unwrap())
+                                }
+                                ArrayKind::Vector => {
+                                    ArrayType::Vector(seq.next_element()?.unwrap())
+                                }
+                                ArrayKind::String => {
+                                    ArrayType::String(seq.next_element()?.unwrap())
+                                }
+                                ArrayKind::Struct(struct_name) => {
+                                    ArrayType::Properties(List::<RcRef<Property>>::visit_seq(
+                                        names,
+                                        schema,
+                                        Some(struct_name),
+                                        seq,
+                                    )?)
+                                }
+                                ArrayKind::Raw => unreachable!(),
+                            };
+                            array.push(item);
                         }
+                        array
                     }
+                    // Absent du schéma, le type est déduit des données
                     _ => {
-                        for _ in 0..len {
-                            let array_properties = ArrayType::Properties(
-                                List::<RcRef<Property>>::visit_seq(names, seq)?,
-                            );
-                            array.push(array_properties);
+                        let mut bytes = Vec::new();
+                        for _ in 0..size.saturating_sub(4) {
+                            bytes.push(seq.next_element()?.unwrap());
                         }
+                        let (array, unknown_kind) =
+                            ArrayType::from_unknown(names, schema, len as usize, &bytes)
+                                .map_err(|err| de::Error::custom(format!("{}: {}", name, err)))?;
+                        kind = unknown_kind;
+                        array
                     }
-                }
-                Property::Array { name_id, _osef1, type_id, _osef2, size, _osef3, array }
+                };
+                Property::Array { name_id, _osef1, type_id, _osef2, _osef3, array, kind }
             }
             "BoolProperty" => {
                 let value = seq.next_element()?.unwrap();
-                Property::Bool { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+                Property::Bool { name_id, _osef1, type_id, _osef2, _osef3, value }
             }
             "ByteProperty" => {
                 if size == 1 {
                     let value = seq.next_element()?.unwrap();
-                    Property::Byte { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+                    Property::Byte { name_id, _osef1, type_id, _osef2, _osef3, value }
                 } else {
                     let value_name_id = seq.next_element()?.unwrap();
                     let _osef4 = seq.next_element()?.unwrap();
@@ -246,7 +404,6 @@ impl Property {
                         _osef1,
                         type_id,
                         _osef2,
-                        size,
                         _osef3,
                         value_name_id,
                         _osef4,
@@ -255,62 +412,57 @@ impl Property {
             }
             "FloatProperty" => {
                 let value = seq.next_element()?.unwrap();
-                Property::Float { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+                Property::Float { name_id, _osef1, type_id, _osef2, _osef3, value }
             }
             "IntProperty" => {
                 let value = seq.next_element()?.unwrap();
-                Property::Int { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+                Property::Int { name_id, _osef1, type_id, _osef2, _osef3, value }
             }
             "NameProperty" => {
                 let value_name_id = seq.next_element()?.unwrap();
                 let _osef4 = seq.next_element()?.unwrap();
-                Property::Name {
-                    name_id,
-                    _osef1,
-                    type_id,
-                    _osef2,
-                    size,
-                    _osef3,
-                    value_name_id,
-                    _osef4,
-                }
+                Property::Name { name_id, _osef1, type_id, _osef2, _osef3, value_name_id, _osef4 }
             }
             "ObjectProperty" => {
                 let object_id = seq.next_element()?.unwrap();
-                Property::Object { name_id, _osef1, type_id, _osef2, size, _osef3, object_id }
+                Property::Object { name_id, _osef1, type_id, _osef2, _osef3, object_id }
             }
             "StrProperty" => {
                 let string = seq.next_element()?.unwrap();
-                Property::Str { name_id, _osef1, type_id, _osef2, size, _osef3, string }
+                Property::Str { name_id, _osef1, type_id, _osef2, _osef3, string }
             }
             "StringRefProperty" => {
                 let value = seq.next_element()?.unwrap();
-                Property::StringRef { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+                Property::StringRef { name_id, _osef1, type_id, _osef2, _osef3, value }
             }
             "StructProperty" => {
                 let struct_name_id = seq.next_element()?.unwrap();
                 let _osef4 = seq.next_element()?.unwrap();
 
-                let struct_name = get_name(names, struct_name_id);
+                let struct_name = get_name(names, struct_name_id)?;
                 let struct_type = match struct_name.as_str() {
                     "LinearColor" => StructType::LinearColor(seq.next_element()?.unwrap()),
                     "Vector" => StructType::Vector(seq.next_element()?.unwrap()),
                     "Rotator" => StructType::Rotator(seq.next_element()?.unwrap()),
-                    _ => StructType::Properties(List::<RcRef<Property>>::visit_seq(names, seq)?),
+                    _ => StructType::Properties(List::<RcRef<Property>>::visit_seq(
+                        names,
+                        schema,
+                        Some(&struct_name),
+                        seq,
+                    )?),
                 };
                 Property::Struct {
                     name_id,
                     _osef1,
                     type_id,
                     _osef2,
-                    size,
                     _osef3,
                     struct_name_id,
                     _osef4,
                     struct_type,
                 }
             }
-            _ => unimplemented!(),
+            _ => return Err(de::Error::custom(format!("unknown property type: {}", type_name))),
         };
         Ok(property)
     }
@@ -342,6 +494,125 @@ impl Property {
     }
 }
 
+// Les tailles sont calculées ici plutôt que stockées, sérialiser ne modifie pas la sauvegarde
+impl Serialize for Property {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        fn header<S: SerializeStructVariant>(
+            state: &mut S, name_id: &u32, _osef1: &Dummy<4>, type_id: &u32, _osef2: &Dummy<4>,
+            size: u32, _osef3: &Dummy<4>,
+        ) -> Result<(), S::Error> {
+            state.serialize_field("name_id", name_id)?;
+            state.serialize_field("_osef1", _osef1)?;
+            state.serialize_field("type_id", type_id)?;
+            state.serialize_field("_osef2", _osef2)?;
+            state.serialize_field("size", &size)?;
+            state.serialize_field("_osef3", _osef3)
+        }
+
+        let size = self.data_size().map_err(ser::Error::custom)?;
+        let (idx, variant, len) = match self {
+            Property::Array { .. } => (0, "Array", 7),
+            Property::Bool { .. } => (1, "Bool", 7),
+            Property::Byte { .. } => (2, "Byte", 7),
+            Property::Float { .. } => (3, "Float", 7),
+            Property::Int { .. } => (4, "Int", 7),
+            Property::Name { .. } => (5, "Name", 8),
+            Property::Object { .. } => (6, "Object", 7),
+            Property::Str { .. } => (7, "Str", 7),
+            Property::StringRef { .. } => (8, "StringRef", 7),
+            Property::Struct { .. } => (9, "Struct", 9),
+            Property::None { .. } => (10, "None", 2),
+        };
+        let mut state = serializer.serialize_struct_variant("Property", idx, variant, len)?;
+        match self {
+            Property::Array { name_id, _osef1, type_id, _osef2, _osef3, array, .. } => {
+                header(&mut state, name_id, _osef1, type_id, _osef2, size, _osef3)?;
+                state.serialize_field("array", array)?;
+            }
+            Property::Bool { name_id, _osef1, type_id, _osef2, _osef3, value } => {
+                header(&mut state, name_id, _osef1, type_id, _osef2, size, _osef3)?;
+                state.serialize_field("value", value)?;
+            }
+            Property::Byte { name_id, _osef1, type_id, _osef2, _osef3, value } => {
+                header(&mut state, name_id, _osef1, type_id, _osef2, size, _osef3)?;
+                state.serialize_field("value", value)?;
+            }
+            Property::Float { name_id, _osef1, type_id, _osef2, _osef3, value } => {
+                header(&mut state, name_id, _osef1, type_id, _osef2, size, _osef3)?;
+                state.serialize_field("value", value)?;
+            }
+            Property::Int { name_id, _osef1, type_id, _osef2, _osef3, value }
+            | Property::StringRef { name_id, _osef1, type_id, _osef2, _osef3, value } => {
+                header(&mut state, name_id, _osef1, type_id, _osef2, size, _osef3)?;
+                state.serialize_field("value", value)?;
+            }
+            Property::Name { name_id, _osef1, type_id, _osef2, _osef3, value_name_id, _osef4 } => {
+                header(&mut state, name_id, _osef1, type_id, _osef2, size, _osef3)?;
+                state.serialize_field("value_name_id", value_name_id)?;
+                state.serialize_field("_osef4", _osef4)?;
+            }
+            Property::Object { name_id, _osef1, type_id, _osef2, _osef3, object_id } => {
+                header(&mut state, name_id, _osef1, type_id, _osef2, size, _osef3)?;
+                state.serialize_field("object_id", object_id)?;
+            }
+            Property::Str { name_id, _osef1, type_id, _osef2, _osef3, string } => {
+                header(&mut state, name_id, _osef1, type_id, _osef2, size, _osef3)?;
+                state.serialize_field("string", string)?;
+            }
+            Property::Struct {
+                name_id,
+                _osef1,
+                type_id,
+                _osef2,
+                _osef3,
+                struct_name_id,
+                _osef4,
+                struct_type,
+            } => {
+                header(&mut state, name_id, _osef1, type_id, _osef2, size, _osef3)?;
+                state.serialize_field("struct_name_id", struct_name_id)?;
+                state.serialize_field("_osef4", _osef4)?;
+                state.serialize_field("struct_type", struct_type)?;
+            }
+            Property::None { name_id, _osef } => {
+                state.serialize_field("name_id", name_id)?;
+                state.serialize_field("_osef", _osef)?;
+            }
+        }
+        state.end()
+    }
+}
+
+#[derive(Clone)]
+pub enum PropertyValue {
+    Array,
+    Bool(bool),
+    Byte(u8),
+    Float(f32),
+    Int(i32),
+    Name(u32),
+    Str(String),
+    StringRef(i32),
+}
+
+impl PropertyValue {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            PropertyValue::Array => "ArrayProperty",
+            PropertyValue::Bool(_) => "BoolProperty",
+            PropertyValue::Byte(_) => "ByteProperty",
+            PropertyValue::Float(_) => "FloatProperty",
+            PropertyValue::Int(_) => "IntProperty",
+            PropertyValue::Name(_) => "NameProperty",
+            PropertyValue::Str(_) => "StrProperty",
+            PropertyValue::StringRef(_) => "StringRefProperty",
+        }
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub enum ArrayType {
     Int(RcCell<i32>),
@@ -349,14 +620,53 @@ pub enum ArrayType {
     Vector(RcRef<Vector>),
     String(RcRef<String>),
     Properties(List<RcRef<Property>>),
+    // Éléments d'un tableau inconnu qu'on n'a pas su typer
+    Raw(List<u8>),
 }
 
 impl ArrayType {
+    // Pour un tableau absent du schéma, seuls les structs et les chaînes se reconnaissent
+    // à coup sûr, le reste est gardé en octets bruts (4 octets peuvent être un int ou un objet)
+    fn from_unknown(
+        names: &[Name], schema: &Me1Schema, len: usize, bytes: &[u8],
+    ) -> Result<(Vec<Self>, Option<ArrayKind>)> {
+        if len == 0 {
+            if !bytes.is_empty() {
+                bail!("{} bytes for an empty array", bytes.len());
+            }
+            return Ok((Vec::new(), None));
+        }
+
+        let structs = StructArraySeed { names, schema, len };
+        if let Ok(array) = unreal::Deserializer::from_bytes_seed(structs, bytes) {
+            return Ok((array.into_iter().map(ArrayType::Properties).collect(), None));
+        }
+
+        // Les chaînes sont lues comme un `Vec` avec sa longueur en tête
+        let mut strings = (len as u32).to_le_bytes().to_vec();
+        strings.extend(bytes);
+        let seed = PhantomData::<Vec<RcRef<String>>>;
+        if let Ok(array) = unreal::Deserializer::from_bytes_seed(seed, &strings) {
+            return Ok((
+                array.into_iter().map(ArrayType::String).collect(),
+                Some(ArrayKind::String),
+            ));
+        }
+
+        if bytes.len() % len != 0 {
+            bail!("cannot split {} bytes into {} items", bytes.len(), len);
+        }
+        let array =
+            bytes.chunks_exact(bytes.len() / len).map(|raw| ArrayType::Raw(raw.to_vec().into()));
+        Ok((array.collect(), Some(ArrayKind::Raw)))
+    }
+
     fn size(&self) -> Result<usize> {
         Ok(match self {
             ArrayType::Int(_) => 4,
             ArrayType::Object(_) => 4,
             ArrayType::Vector(_) => 12,
+            ArrayType::Raw(raw) => raw.len(),
             ArrayType::String(string) => {
                 let bytes = unreal::Serializer::to_vec(string)?;
                 bytes.len()
@@ -372,6 +682,48 @@ impl ArrayType {
     }
 }
 
+// `len` structs à la suite, le type de leurs tableaux vient du schéma
+struct StructArraySeed<'a> {
+    names: &'a [Name],
+    schema: &'a Me1Schema,
+    len: usize,
+}
+
+impl<'de, 'a> de::DeserializeSeed<'de> for StructArraySeed<'a> {
+    type Value = Vec<List<RcRef<Property>>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(usize::MAX, self)
+    }
+}
+
+impl<'de, 'a> de::Visitor<'de> for StructArraySeed<'a> {
+    type Value = Vec<List<RcRef<Property>>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a seq of structs")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut array = Vec::new();
+        for _ in 0..self.len {
+            array.push(List::<RcRef<Property>>::visit_seq(
+                self.names,
+                self.schema,
+                None,
+                &mut seq,
+            )?);
+        }
+        Ok(array)
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub enum StructType {
     LinearColor(RcRef<LinearColor>),
@@ -1,15 +1,17 @@
 pub mod data;
 pub mod player;
 pub mod plot_db;
+pub mod schema;
 pub mod state;
 
-use self::{player::*, state::*};
+use self::{player::*, schema::Me1Schema, state::*};
 
 use std::fmt;
 use std::io::{Cursor, Read, Write};
+use std::rc::Rc;
 
 use anyhow::Result;
-use serde::de;
+use serde::de::{self, DeserializeOwned};
 use serde::ser::SerializeTupleStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
@@ -30,28 +32,48 @@ pub struct Me1SaveGame {
 }
 
 impl Me1SaveGame {
-    fn unzip(input: &[u8]) -> Result<(Player, State, Option<WorldSavePackage>)> {
-        let mut zip = ZipArchive::new(Cursor::new(input))?;
+    pub fn is_xbox360(&self) -> bool {
+        self.magic_number.borrow().is_xbox360
+    }
 
-        let player: Player = {
-            let mut bytes = Vec::new();
-            zip.by_name("player.sav")?.read_to_end(&mut bytes)?;
-            unreal::Deserializer::from_bytes(&bytes)?
-        };
+    pub fn set_xbox360(&mut self, is_xbox360: bool) {
+        self.magic_number.borrow_mut().is_xbox360 = is_xbox360;
+    }
 
-        let state: State = {
+    fn unzip(
+        input: &[u8], schema: &Rc<Me1Schema>, is_xbox360: bool,
+    ) -> Result<(Player, State, Option<WorldSavePackage>)> {
+        fn read(zip: &mut ZipArchive<Cursor<&[u8]>>, file_name: &str) -> Result<Vec<u8>> {
             let mut bytes = Vec::new();
-            zip.by_name("state.sav")?.read_to_end(&mut bytes)?;
-            unreal::Deserializer::from_bytes(&bytes)?
+            zip.by_name(file_name)?.read_to_end(&mut bytes)?;
+            Ok(bytes)
+        }
+
+        // Les packages de la save Xbox 360 sont en big endian
+        fn unpack<T: DeserializeOwned>(bytes: &[u8], is_xbox360: bool) -> Result<T> {
+            Ok(if is_xbox360 {
+                unreal::Deserializer::from_be_bytes(bytes)?
+            } else {
+                unreal::Deserializer::from_bytes(bytes)?
+            })
+        }
+
+        let mut zip = ZipArchive::new(Cursor::new(input))?;
+
+        let player: Player = {
+            let bytes = read(&mut zip, "player.sav")?;
+            let seed = PlayerSeed(schema);
+            if is_xbox360 {
+                unreal::Deserializer::from_be_bytes_seed(seed, &bytes)?
+            } else {
+                unreal::Deserializer::from_bytes_seed(seed, &bytes)?
+            }
         };
+        let state: State = unpack(&read(&mut zip, "state.sav")?, is_xbox360)?;
 
         let world_save_package: Option<WorldSavePackage> =
             if zip.file_names().any(|f| f == "WorldSavePackage.sav") {
-                Some({
-                    let mut bytes = Vec::new();
-                    zip.by_name("WorldSavePackage.sav")?.read_to_end(&mut bytes)?;
-                    unreal::Deserializer::from_bytes(&bytes)?
-                })
+                Some(unpack(&read(&mut zip, "WorldSavePackage.sav")?, is_xbox360)?)
             } else {
                 None
             };
@@ -60,6 +82,15 @@ impl Me1SaveGame {
     }
 
     fn zip(&self) -> Result<List<u8>> {
+        fn pack<T: Serialize>(value: &T, is_xbox360: bool) -> Result<Vec<u8>> {
+            Ok(if is_xbox360 {
+                unreal::Serializer::to_be_vec(value)?
+            } else {
+                unreal::Serializer::to_vec(value)?
+            })
+        }
+
+        let is_xbox360 = self.is_xbox360();
         let mut zip = Vec::new();
         {
             let mut zipper = ZipWriter::new(Cursor::new(&mut zip));
@@ -67,19 +98,19 @@ impl Me1SaveGame {
 
             // Player
             {
-                let player_data = unreal::Serializer::to_vec(&self.player)?;
+                let player_data = pack(&self.player, is_xbox360)?;
                 zipper.start_file("player.sav", options)?;
                 zipper.write_all(&player_data)?;
             }
             // State
             {
-                let state_data = unreal::Serializer::to_vec(&self.state)?;
+                let state_data = pack(&self.state, is_xbox360)?;
                 zipper.start_file("state.sav", options)?;
                 zipper.write_all(&state_data)?;
             }
             // WorldSavePackage
             if let Some(ref world_save_package) = self._world_save_package {
-                let world_save_package_data = unreal::Serializer::to_vec(world_save_package)?;
+                let world_save_package_data = pack(world_save_package, is_xbox360)?;
                 zipper.start_file("WorldSavePackage.sav", options)?;
                 zipper.write_all(&world_save_package_data)?;
             }
@@ -88,13 +119,18 @@ impl Me1SaveGame {
     }
 }
 
-impl<'de> Deserialize<'de> for Me1SaveGame {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+// Le schéma est chargé avec les autres bases de données
+pub struct Me1SaveGameSeed<'a>(pub &'a Rc<Me1Schema>);
+
+impl<'de, 'a> de::DeserializeSeed<'de> for Me1SaveGameSeed<'a> {
+    type Value = Me1SaveGame;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct Me1SaveGameVisitor;
-        impl<'de> de::Visitor<'de> for Me1SaveGameVisitor {
+        struct Me1SaveGameVisitor<'a>(&'a Rc<Me1Schema>);
+        impl<'de, 'a> de::Visitor<'de> for Me1SaveGameVisitor<'a> {
             type Value = Me1SaveGame;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -105,7 +141,7 @@ impl<'de> Deserialize<'de> for Me1SaveGame {
             where
                 A: de::SeqAccess<'de>,
             {
-                let magic_number = seq.next_element()?.unwrap();
+                let magic_number: Me1MagicNumber = seq.next_element()?.unwrap();
                 let begin = seq.next_element()?.unwrap();
                 let zip_offset = seq.next_element()?.unwrap();
 
@@ -117,10 +153,11 @@ impl<'de> Deserialize<'de> for Me1SaveGame {
 
                 let zip_data: List<u8> = seq.next_element()?.unwrap();
                 let (player, state, world_save_package) =
-                    Me1SaveGame::unzip(&zip_data).map_err(de::Error::custom)?;
+                    Me1SaveGame::unzip(&zip_data, self.0, magic_number.is_xbox360)
+                        .map_err(de::Error::custom)?;
 
                 Ok(Me1SaveGame {
-                    magic_number,
+                    magic_number: magic_number.into(),
                     _begin: begin,
                     _zip_offset: zip_offset,
                     _no_mans_land: no_mans_land.into(),
@@ -130,7 +167,7 @@ impl<'de> Deserialize<'de> for Me1SaveGame {
                 })
             }
         }
-        deserializer.deserialize_tuple_struct("Me1SaveGame", usize::MAX, Me1SaveGameVisitor)
+        deserializer.deserialize_tuple_struct("Me1SaveGame", usize::MAX, Me1SaveGameVisitor(self.0))
     }
 }
 
@@ -161,20 +198,25 @@ impl serde::Serialize for Me1SaveGame {
 }
 
 #[derive(Serialize, Clone)]
-pub struct Me1MagicNumber(u32);
+pub struct Me1MagicNumber {
+    magic_number: u32,
+    #[serde(skip)]
+    pub is_xbox360: bool,
+}
 
 impl<'de> Deserialize<'de> for Me1MagicNumber {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let version: [u8; 4] = Deserialize::deserialize(deserializer)?;
+        let bytes: [u8; 4] = Deserialize::deserialize(deserializer)?;
 
-        if &version != b"RGMH" {
-            return Err(de::Error::custom("Wrong magic number"));
+        // Le nombre magique est écrit dans l'ordre des octets de la plateforme
+        match &bytes {
+            b"RGMH" => Ok(Self { magic_number: u32::from_le_bytes(bytes), is_xbox360: false }),
+            b"HMGR" => Ok(Self { magic_number: u32::from_be_bytes(bytes), is_xbox360: true }),
+            _ => Err(de::Error::custom("Wrong magic number")),
         }
-
-        Ok(Self(u32::from_le_bytes(version)))
     }
 }
 
@@ -195,6 +237,8 @@ mod test {
 
     #[test]
     fn unzip_deserialize_serialize_zip() -> Result<()> {
+        let schema = Rc::new(ron::from_str(&fs::read_to_string("databases/me1_schema.ron")?)?);
+
         let files = [
             "test/ME1Save.MassEffectSave",   // Normal save
             "test/ME1Export.MassEffectSave", // Export save
@@ -206,7 +250,8 @@ mod test {
             let now = Instant::now();
 
             // Deserialize
-            let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+            let me1_save_game =
+                unreal::Deserializer::from_bytes_seed(Me1SaveGameSeed(&schema), &input)?;
 
             println!("Deserialize 1 : {:?}", Instant::now() - now);
             let now = Instant::now();
@@ -218,7 +263,8 @@ mod test {
             let now = Instant::now();
 
             // Deserialize (again)
-            let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&output)?;
+            let me1_save_game =
+                unreal::Deserializer::from_bytes_seed(Me1SaveGameSeed(&schema), &output)?;
 
             println!("Deserialize 2 : {:?}", Instant::now() - now);
             let now = Instant::now();
@@ -241,4 +287,33 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    fn deserialize_serialize_xbox360() -> Result<()> {
+        let schema = Rc::new(ron::from_str(&fs::read_to_string("databases/me1_schema.ron")?)?);
+
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let mut me1_save_game: Me1SaveGame =
+            unreal::Deserializer::from_bytes_seed(Me1SaveGameSeed(&schema), &input)?;
+        assert!(!me1_save_game.is_xbox360());
+        let pc_output = unreal::Serializer::to_vec(&me1_save_game)?;
+
+        // PC => Xbox 360
+        me1_save_game.set_xbox360(true);
+        let output = unreal::Serializer::to_be_vec(&me1_save_game)?;
+        assert_eq!(&output[..4], b"HMGR");
+
+        // Xbox 360 => Xbox 360
+        let mut me1_save_game: Me1SaveGame =
+            unreal::Deserializer::from_be_bytes_seed(Me1SaveGameSeed(&schema), &output)?;
+        assert!(me1_save_game.is_xbox360());
+        let output_2 = unreal::Serializer::to_be_vec(&me1_save_game)?;
+        assert!(output == output_2);
+
+        // Xbox 360 => PC
+        me1_save_game.set_xbox360(false);
+        let output_3 = unreal::Serializer::to_vec(&me1_save_game)?;
+        assert!(output_3 == pc_output);
+        Ok(())
+    }
 }
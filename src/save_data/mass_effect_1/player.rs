@@ -1,11 +1,15 @@
-use std::fmt;
+use std::{fmt, rc::Rc};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::de;
 use serde::ser::SerializeTupleStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use super::{data::Data, List};
+use super::{
+    data::{ArrayType, Data, Property, PropertyValue},
+    schema::Me1Schema,
+    List,
+};
 use crate::save_data::Dummy;
 use crate::save_data::RcRef;
 use crate::unreal;
@@ -21,6 +25,7 @@ pub struct Player {
     pub objects: List<Object>,
     _no_mans_land2: List<u8>,
     datas: List<Data>,
+    schema: Rc<Me1Schema>,
 }
 
 impl Player {
@@ -32,6 +37,16 @@ impl Player {
         &self.classes[id.abs() as usize - 1]
     }
 
+    // Chemin complet d'un objet importé (ex: `BIOG_HMF_HIR_PRO.Classy.HMF_HIR_Cls_MDL`)
+    pub fn get_import_path(&self, id: i32) -> String {
+        let class = self.get_class(id);
+        let name = self.get_name(class.class_name_id);
+        match class.link_id as i32 {
+            link if link < 0 => format!("{}.{}", self.get_import_path(link), name),
+            _ => name,
+        }
+    }
+
     pub fn get_object(&self, id: i32) -> &Object {
         &self.objects[id as usize - 1]
     }
@@ -39,15 +54,167 @@ impl Player {
     pub fn get_data(&self, i: i32) -> &Data {
         &self.datas[i as usize - 1]
     }
+
+    pub fn get_data_mut(&mut self, i: i32) -> &mut Data {
+        &mut self.datas[i as usize - 1]
+    }
+
+    pub fn find_name(&self, string: &str) -> Option<u32> {
+        self.names.borrow().iter().enumerate().find_map(|(id, name)| {
+            (!name.is_duplicate && name.string.borrow().as_str() == string).then(|| id as u32)
+        })
+    }
+
+    // Retourne l'id du nom, l'ajoute à la table des noms s'il n'existe pas
+    pub fn add_name(&self, string: &str) -> u32 {
+        if let Some(id) = self.find_name(string) {
+            return id;
+        }
+
+        let mut names = self.names.borrow_mut();
+        let mut name = names[0].clone();
+        name.string = RcRef::new(string.to_owned());
+        name.is_duplicate = false;
+        names.push(name);
+        (names.len() - 1) as u32
+    }
+
+    pub fn new_property(&self, name: &str, value: PropertyValue) -> Property {
+        let name_id = self.add_name(name);
+        let type_id = self.add_name(value.type_name());
+        let mut property = Property::new(name_id, type_id, value);
+        if let Property::Array { ref mut kind, .. } = property {
+            *kind = self.schema.array_kind(None, name).cloned();
+        }
+        property
+    }
+
+    pub fn new_array_item(&self, property: &Property) -> Option<ArrayType> {
+        property.new_array_item(self.add_name("None"))
+    }
+
+    pub fn find_object(&self, object_name: &str) -> Option<i32> {
+        self.objects
+            .iter()
+            .position(|object| self.get_name(object.object_name_id) == object_name)
+            .map(|i| i as i32 + 1)
+    }
+
+    pub fn find_property<'a>(
+        &self, properties: &'a List<RcRef<Property>>, property_name: &str,
+    ) -> Option<&'a RcRef<Property>> {
+        properties
+            .iter()
+            .find(|property| self.get_name(property.borrow().name_id()) == property_name)
+    }
+
+    pub fn find_object_id(
+        &self, properties: &List<RcRef<Property>>, property_name: &str,
+    ) -> Option<i32> {
+        self.find_property(properties, property_name).and_then(|property| {
+            match *property.borrow() {
+                Property::Object { object_id, .. } => Some(object_id),
+                _ => None,
+            }
+        })
+    }
+
+    // Les propriétés à leur valeur par défaut ne sont pas dans la save, on les ajoute au besoin
+    pub fn find_or_add_property(
+        &mut self, object_id: i32, property_name: &str, default: PropertyValue,
+    ) -> RcRef<Property> {
+        let found =
+            self.find_property(&self.get_data(object_id).properties, property_name).cloned();
+        match found {
+            Some(property) => property,
+            None => {
+                let property = self.new_property(property_name, default);
+                self.get_data_mut(object_id).properties.add_property(property)
+            }
+        }
+    }
+
+    // Tag et id du `BioPawnBehaviorSaveObject` de chaque membre de l'escouade
+    pub fn henchmen(&self) -> Vec<(String, i32)> {
+        let current_game = match self.find_object("CurrentGame") {
+            Some(object_id) => self.get_data(object_id),
+            None => return Vec::new(),
+        };
+        let m_henchmen = match self.find_property(&current_game.properties, "m_Henchmen") {
+            Some(m_henchmen) => m_henchmen.borrow(),
+            None => return Vec::new(),
+        };
+
+        match *m_henchmen {
+            Property::Array { ref array, .. } => array
+                .iter()
+                .filter_map(|henchman| match henchman {
+                    ArrayType::Properties(properties) => {
+                        let tag = match *self.find_property(properties, "Tag")?.borrow() {
+                            Property::Name { ref value_name_id, .. } => {
+                                self.get_name(value_name_id.get())
+                            }
+                            _ => return None,
+                        };
+                        let object_id = self.find_object_id(properties, "Save")?;
+                        (object_id > 0).then(|| (tag, object_id))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    // Ajoute un objet sans propriétés d'une classe importée, retourne son id
+    pub fn add_object(&mut self, class_name: &str) -> Result<i32> {
+        let class_id = self
+            .classes
+            .iter()
+            .position(|class| self.get_name(class.class_name_id) == class_name)
+            .map(|idx| -(idx as i32) - 1)
+            .with_context(|| format!("No `{}` class in this save", class_name))?;
+
+        let object_name_id = self.add_name(class_name);
+        let value_id = self
+            .objects
+            .iter()
+            .filter(|object| object.object_name_id == object_name_id)
+            .map(|object| object.value_id)
+            .max()
+            .unwrap_or_default()
+            + 1;
+
+        self.objects.push(Object {
+            class_id,
+            class_parent_id: 0,
+            link_id: 0,
+            object_name_id,
+            value_id,
+            archtype_id: 0,
+            flag: 0x0007_0000_0000_0000,
+            data_size: 0,
+            data_offset: 0,
+            _osef: Default::default(),
+        });
+        let none_name_id = self.add_name("None");
+        self.datas.push(Data::new(none_name_id));
+        Ok(self.objects.len() as i32)
+    }
 }
 
-impl<'de> Deserialize<'de> for Player {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+// Le type des éléments des tableaux vient du schéma
+pub struct PlayerSeed<'a>(pub &'a Rc<Me1Schema>);
+
+impl<'de, 'a> de::DeserializeSeed<'de> for PlayerSeed<'a> {
+    type Value = Player;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct PlayerVisitor;
-        impl<'de> de::Visitor<'de> for PlayerVisitor {
+        struct PlayerVisitor<'a>(&'a Rc<Me1Schema>);
+        impl<'de, 'a> de::Visitor<'de> for PlayerVisitor<'a> {
             type Value = Player;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -70,19 +237,19 @@ impl<'de> Deserialize<'de> for Player {
                 let header: Header = seq.next_element()?.unwrap();
 
                 // Names
-                let mut names = Vec::new();
+                let mut names: Vec<Name> = Vec::new();
                 for _ in 0..header.name_len {
                     names.push(seq.next_element()?.unwrap());
                 }
 
                 // Imports
-                let mut classes = Vec::new();
+                let mut classes: Vec<Class> = Vec::new();
                 for _ in 0..header.classes_len {
                     classes.push(seq.next_element()?.unwrap());
                 }
 
                 // Objects
-                let mut objects = Vec::new();
+                let mut objects: Vec<Object> = Vec::new();
                 for _ in 0..header.objects_len {
                     objects.push(seq.next_element()?.unwrap());
                 }
@@ -94,9 +261,25 @@ impl<'de> Deserialize<'de> for Player {
                 }
 
                 // Data
+                let schema = self.0;
                 let mut datas = Vec::new();
-                for _ in objects.iter() {
-                    let data = Data::visit_seq(&names, &mut seq)?;
+                for object in objects.iter() {
+                    // Le schéma a besoin de la classe de l'objet
+                    // < 0 : import, > 0 : export, 0 : pas de classe
+                    let class_name_id = match object.class_id {
+                        id if id < 0 => classes
+                            .get(id.unsigned_abs() as usize - 1)
+                            .map(|class| class.class_name_id),
+                        id if id > 0 => {
+                            objects.get(id as usize - 1).map(|object| object.object_name_id)
+                        }
+                        _ => None,
+                    };
+                    let class_name = class_name_id
+                        .and_then(|id| names.get(id as usize))
+                        .map(|name| name.string.borrow().clone())
+                        .unwrap_or_default();
+                    let data = Data::visit_seq(&names, schema, &class_name, &mut seq)?;
                     datas.push(data);
                 }
 
@@ -110,10 +293,11 @@ impl<'de> Deserialize<'de> for Player {
                     objects: objects.into(),
                     _no_mans_land2: no_mans_land2.into(),
                     datas: datas.into(),
+                    schema: Rc::clone(schema),
                 })
             }
         }
-        deserializer.deserialize_tuple_struct("Player", usize::MAX, PlayerVisitor)
+        deserializer.deserialize_tuple_struct("Player", usize::MAX, PlayerVisitor(self.0))
     }
 }
 
@@ -133,6 +317,7 @@ impl serde::Serialize for Player {
             objects,
             _no_mans_land2,
             datas,
+            schema: _,
         } = self;
         let names = names.borrow();
 
@@ -140,6 +325,11 @@ impl serde::Serialize for Player {
         let mut header = header.clone();
 
         header.name_len = names.len() as u32;
+        header.objects_len = objects.len() as u32;
+        if let Some(generation) = header.generations.last_mut() {
+            generation.objects_len = header.objects_len;
+            generation.name_len = header.name_len;
+        }
         header.classes_offset = header.name_offset;
         for name in names.iter() {
             header.classes_offset += name.size().map_err(Error::custom)? as u32;
@@ -189,11 +379,20 @@ struct Header {
     classes_len: u32,
     classes_offset: u32,
     no_mans_land_offset: u32,
-    _osef1: Dummy<68>,
+    _guid: Dummy<16>,
+    generations: Vec<Generation>,
+    _osef1: Dummy<36>,
     _compression: u32,
     _osef2: Dummy<12>,
 }
 
+#[derive(Deserialize, Serialize, Clone)]
+struct Generation {
+    objects_len: u32,
+    name_len: u32,
+    _net_objects_len: u32,
+}
+
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Name {
     pub string: RcRef<String>,
@@ -245,23 +444,30 @@ mod test {
     use super::*;
     use crate::unreal;
 
-    #[test]
-    fn deserialize_serialize() -> Result<()> {
+    fn schema() -> Result<Rc<Me1Schema>> {
+        Ok(Rc::new(ron::from_str(&fs::read_to_string("databases/me1_schema.ron")?)?))
+    }
+
+    fn player_data() -> Result<Vec<u8>> {
         let input = fs::read("test/ME1Save.MassEffectSave")?;
 
-        let player_data = {
-            let mut offset_bytes = [0; 4];
-            offset_bytes.copy_from_slice(&input[8..12]);
-            let zip_offset = <u32>::from_le_bytes(offset_bytes);
-            let mut zip = ZipArchive::new(Cursor::new(&input[zip_offset as usize..]))?;
+        let mut offset_bytes = [0; 4];
+        offset_bytes.copy_from_slice(&input[8..12]);
+        let zip_offset = <u32>::from_le_bytes(offset_bytes);
+        let mut zip = ZipArchive::new(Cursor::new(&input[zip_offset as usize..]))?;
 
-            let mut bytes = Vec::new();
-            zip.by_name("player.sav")?.read_to_end(&mut bytes)?;
-            bytes
-        };
+        let mut bytes = Vec::new();
+        zip.by_name("player.sav")?.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[test]
+    fn deserialize_serialize() -> Result<()> {
+        let schema = schema()?;
+        let player_data = player_data()?;
 
         // Deserialize
-        let player: Player = unreal::Deserializer::from_bytes(&player_data)?;
+        let player = unreal::Deserializer::from_bytes_seed(PlayerSeed(&schema), &player_data)?;
 
         // Serialize
         let output = unreal::Serializer::to_vec(&player)?;
@@ -279,4 +485,184 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn add_remove_properties() -> Result<()> {
+        let schema = schema()?;
+        let player_data = player_data()?;
+        let mut player = unreal::Deserializer::from_bytes_seed(PlayerSeed(&schema), &player_data)?;
+
+        let object_id = player
+            .objects
+            .iter()
+            .position(|object| player.get_name(object.object_name_id) == "CurrentGame")
+            .unwrap() as i32
+            + 1;
+        let names_len = player.names.borrow().len();
+
+        // Add a property with a new name
+        let property = player.new_property("m_sTestProperty", PropertyValue::Str("Test".into()));
+        player.get_data_mut(object_id).properties.add_property(property);
+        assert_eq!(player.names.borrow().len(), names_len + 1);
+        assert_eq!(player.add_name("m_sTestProperty") as usize, names_len);
+
+        // Remove the first property
+        let removed = RcRef::clone(&player.get_data(object_id).properties[0]);
+        let removed_name_id = removed.borrow().name_id();
+        assert!(player.get_data_mut(object_id).properties.remove_property(&removed));
+
+        // Add an array item
+        let array = player
+            .datas
+            .iter()
+            .flat_map(|data| data.properties.iter())
+            .find(|property| matches!(*property.borrow(), Property::Array { .. }))
+            .cloned()
+            .unwrap();
+        let array_len = match *array.borrow() {
+            Property::Array { ref array, .. } => array.len(),
+            _ => unreachable!(),
+        };
+        let item = player.new_array_item(&array.borrow()).unwrap();
+        array.borrow_mut().add_array_item(item);
+
+        // Serialize / deserialize
+        let output = unreal::Serializer::to_vec(&player)?;
+        let player = unreal::Deserializer::from_bytes_seed(PlayerSeed(&schema), &output)?;
+
+        assert_eq!(player.names.borrow().len(), names_len + 1);
+        assert_eq!(player.header.generations.last().unwrap().name_len as usize, names_len + 1);
+
+        let properties = &player.get_data(object_id).properties;
+        assert!(!properties.iter().any(|property| property.borrow().name_id() == removed_name_id));
+        let test_property = properties
+            .iter()
+            .find(|property| player.get_name(property.borrow().name_id()) == "m_sTestProperty")
+            .cloned()
+            .unwrap();
+        match *test_property.borrow() {
+            Property::Str { ref string, .. } => assert_eq!(*string.borrow(), "Test"),
+            _ => panic!("wrong property type"),
+        }
+        assert_eq!(test_property.borrow().data_size()?, 9);
+
+        let array_name_id = array.borrow().name_id();
+        let array = player
+            .datas
+            .iter()
+            .flat_map(|data| data.properties.iter())
+            .find(|property| property.borrow().name_id() == array_name_id)
+            .cloned()
+            .unwrap();
+        match *array.borrow() {
+            Property::Array { ref array, .. } => assert_eq!(array.len(), array_len + 1),
+            _ => unreachable!(),
+        };
+
+        // Serialize again
+        let output_2 = unreal::Serializer::to_vec(&player)?;
+        assert!(output == output_2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_object() -> Result<()> {
+        let schema = schema()?;
+        let player_data = player_data()?;
+        let mut player = unreal::Deserializer::from_bytes_seed(PlayerSeed(&schema), &player_data)?;
+
+        let objects_len = player.objects.len();
+        let item_id = player.add_object("BioItemXModdableSaveObject")?;
+        assert_eq!(item_id as usize, objects_len + 1);
+        assert!(player.add_object("UnknownClass").is_err());
+
+        // Serialize / deserialize
+        let output = unreal::Serializer::to_vec(&player)?;
+        let player = unreal::Deserializer::from_bytes_seed(PlayerSeed(&schema), &output)?;
+
+        assert_eq!(player.objects.len(), objects_len + 1);
+        assert_eq!(player.header.generations.last().unwrap().objects_len as usize, objects_len + 1);
+        assert_eq!(
+            player.get_import_path(player.get_object(item_id).class_id),
+            "BIOC_Base.BioItemXModdableSaveObject"
+        );
+
+        // Le couple nom / numéro reste unique
+        let object = player.get_object(item_id);
+        assert!(!player.objects[..objects_len].iter().any(|other| {
+            other.object_name_id == object.object_name_id && other.value_id == object.value_id
+        }));
+
+        // Seulement la propriété `None`
+        let properties = &player.get_data(item_id).properties;
+        assert_eq!(properties.len(), 1);
+        assert!(matches!(*properties[0].borrow(), Property::None { .. }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_arrays() -> Result<()> {
+        // Une propriété lue sans schéma
+        struct PropertySeed<'a>(&'a [Name], &'a Me1Schema);
+
+        impl<'de, 'a> de::DeserializeSeed<'de> for PropertySeed<'a> {
+            type Value = Property;
+
+            fn deserialize<D: Deserializer<'de>>(
+                self, deserializer: D,
+            ) -> Result<Property, D::Error> {
+                deserializer.deserialize_tuple(usize::MAX, self)
+            }
+        }
+
+        impl<'de, 'a> de::Visitor<'de> for PropertySeed<'a> {
+            type Value = Property;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a property")
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Property, A::Error> {
+                Property::visit_seq(self.0, self.1, None, &mut seq)
+            }
+        }
+
+        let schema = schema()?;
+        let player_data = player_data()?;
+        let player = unreal::Deserializer::from_bytes_seed(PlayerSeed(&schema), &player_data)?;
+        let names = player.names.borrow();
+        let empty_schema = Me1Schema::from(indexmap::IndexMap::new());
+
+        let mut kinds = Vec::new();
+        for data in player.datas.iter() {
+            for property in data.properties.iter() {
+                if let Property::Array { array, .. } = &*property.borrow() {
+                    if array.is_empty() {
+                        continue;
+                    }
+                    let input = unreal::Serializer::to_vec(&*property.borrow())?;
+                    let seed = PropertySeed(&names, &empty_schema);
+                    let unknown = unreal::Deserializer::from_bytes_seed(seed, &input)?;
+
+                    // Les mêmes octets, quel que soit le type déduit
+                    assert!(unreal::Serializer::to_vec(&unknown)? == input);
+                    if let Property::Array { array, .. } = unknown {
+                        kinds.push(std::mem::discriminant(&array[0]));
+                    }
+                }
+            }
+        }
+
+        // Les ints et les objets ne se distinguent pas, ils restent bruts, les structs sont lus
+        let int = std::mem::discriminant(&ArrayType::Int(0.into()));
+        let raw = std::mem::discriminant(&ArrayType::Raw(Vec::new().into()));
+        let properties = std::mem::discriminant(&ArrayType::Properties(Vec::new().into()));
+        assert!(!kinds.contains(&int));
+        assert!(kinds.contains(&raw));
+        assert!(kinds.contains(&properties));
+
+        Ok(())
+    }
 }
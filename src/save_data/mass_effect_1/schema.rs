@@ -0,0 +1,66 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone)]
+pub enum ArrayKind {
+    Int,
+    Object,
+    Vector,
+    String,
+    Struct(String),
+    // Absent du schéma, les éléments sont gardés tels quels
+    #[serde(skip_deserializing)]
+    Raw,
+}
+
+// Classes / structs => propriétés => type des éléments des `ArrayProperty`
+#[derive(Deserialize, Deref, From)]
+pub struct Me1Schema(IndexMap<String, IndexMap<String, ArrayKind>>);
+
+impl Me1Schema {
+    pub fn array_kind(&self, owner: Option<&str>, property_name: &str) -> Option<&ArrayKind> {
+        match owner.and_then(|owner| self.get(owner)) {
+            Some(properties) => properties.get(property_name),
+            // Si la classe est inconnue on cherche dans toutes les classes
+            None => self.values().find_map(|properties| properties.get(property_name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_schema() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_schema.ron")?;
+        let _me1_schema: Me1Schema = ron::from_str(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn array_kind_lookup() {
+        let schema: Me1Schema = ron::from_str(
+            r#"Me1Schema({
+                "BioPawn": { "m_Talents": Int },
+                "BioInventory": { "m_Items": Struct("Item") },
+            })"#,
+        )
+        .unwrap();
+
+        assert!(matches!(schema.array_kind(Some("BioPawn"), "m_Talents"), Some(ArrayKind::Int)));
+        // Classe connue : pas de recherche dans les autres classes
+        assert!(schema.array_kind(Some("BioPawn"), "m_Items").is_none());
+        // Classe inconnue ou absente : on cherche partout
+        assert!(matches!(
+            schema.array_kind(Some("Unknown"), "m_Items"),
+            Some(ArrayKind::Struct(_))
+        ));
+        assert!(matches!(schema.array_kind(None, "m_Talents"), Some(ArrayKind::Int)));
+    }
+}
@@ -0,0 +1,681 @@
+use std::cell::Ref;
+
+use anyhow::{Context, Result};
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{
+    player::{
+        ComplexTalent, Item, ItemLevel, ItemMod, Me1LeClass, PawnStats, Player, SimpleTalent,
+    },
+    Me1LeSaveData, Me1LeSaveGame,
+};
+use crate::{
+    save_data::{
+        mass_effect_1::{
+            data::{ArrayType, Property, StructType},
+            player::Player as Me1Player,
+            Me1SaveGame,
+        },
+        shared::{
+            appearance::{HeadMorph, LinearColor},
+            player::{Notoriety, Origin},
+        },
+        List, RcCell, RcRef,
+    },
+    unreal,
+};
+
+// Save ME1LE sur laquelle on applique les données de la save ME1,
+// tout ce qui vient de sa partie est écrasé ou remis à zéro par le `Converter`.
+// C'est `test/ME1LeSave.pcsav` dont on a vidé l'identifiant, les noms, le code de visage,
+// le head morph et le temps de jeu (vérifié par le test `template_has_no_personal_data`)
+const TEMPLATE: &[u8] = include_bytes!("../../../databases/me1_le_template.pcsav");
+
+impl Me1LeSaveGame {
+    pub fn from_me1(me1: &Me1SaveGame) -> Result<Self> {
+        let save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(TEMPLATE)?;
+        {
+            let player = me1.player();
+            let state = me1.state();
+            let converter = Converter { player: &player };
+
+            let mut save_data = save_game.save_data.borrow_mut();
+            *save_data.plot.borrow_mut() = copy(&*state.plot())?;
+            *save_data.base_level_name.borrow_mut() = state.base_level_name().clone();
+            converter.convert(&mut save_data)?;
+        }
+        Ok(save_game)
+    }
+}
+
+struct Converter<'a> {
+    player: &'a Me1Player,
+}
+
+// Le jeu n'écrit pas les propriétés à leur valeur par défaut, une propriété absente de la save
+// ME1 vaut donc la valeur par défaut de sa classe (zéro, chaîne vide, premier élément de l'enum)
+// et ne doit pas laisser en place celle de la save modèle
+impl Converter<'_> {
+    fn convert(&self, save_data: &mut Me1LeSaveData) -> Result<()> {
+        let current_game =
+            self.data(self.player.find_object("CurrentGame").context("no CurrentGame")?);
+        let m_player = self.object(current_game, "m_Player").context("no m_Player")?;
+        let pawn = self.data(m_player);
+
+        // Général
+        let character_id = self.string(current_game, "m_sCharacterID").unwrap_or_default();
+        // Même forme que les noms de fichiers écrits par le jeu
+        *save_data.file_name.borrow_mut() = format!("{}_QuickSave", character_id);
+        *save_data.character_id.borrow_mut() = character_id;
+        save_data
+            .seconds_played
+            .set(self.float(current_game, "m_fPlaythroughTime").unwrap_or_default() as i32);
+        *save_data.map_name.borrow_mut() = String::new();
+        *save_data.parent_map_name.borrow_mut() = String::new();
+
+        if let Some(owner) = self.object(pawn, "Owner").filter(|&id| id > 0) {
+            let owner = self.data(owner);
+            if let Some(StructType::Vector(location)) = self.struct_type(owner, "Location") {
+                save_data.location = copy_ref(&location)?;
+            }
+            if let Some(StructType::Rotator(rotation)) = self.struct_type(owner, "Rotation") {
+                save_data.rotation = copy_ref(&rotation)?;
+            }
+        }
+
+        // La carte de la save d'origine n'a plus de sens
+        if let Some(no_export) = save_data.no_export.borrow().as_ref() {
+            no_export.borrow().legacy_maps.borrow_mut().clear();
+        }
+
+        // Le journal et le codex ME1 ne sont pas encore lus, mieux vaut des listes vides
+        // que ceux de la save modèle
+        *save_data.journal.borrow_mut() = Default::default();
+        *save_data.codex.borrow_mut() = Default::default();
+
+        // Joueur
+        {
+            let mut player = save_data.player.borrow_mut();
+            self.player(&mut player, current_game, pawn)?;
+            *save_data.display_name.borrow_mut() = format!("{} Shepard", player.first_name());
+        }
+
+        // Escouade, les membres absents de la save ME1 sont retirés et le jeu recrée ceux qui
+        // manquent au modèle avec leurs valeurs par défaut
+        let henchmen = self.player.henchmen();
+        save_data
+            .squad
+            .borrow_mut()
+            .retain(|henchman| henchmen.iter().any(|(tag, _)| *henchman.borrow().tag() == *tag));
+        for (tag, object_id) in henchmen {
+            let squad = save_data.squad.borrow();
+            let henchman = match squad.iter().find(|henchman| *henchman.borrow().tag() == tag) {
+                Some(henchman) => henchman,
+                None => continue,
+            };
+
+            let pawn = self.data(object_id);
+            let mut henchman = henchman.borrow_mut();
+            henchman.set_stats(&self.pawn_stats(pawn));
+            henchman.set_level(self.int(pawn, "m_XPLevel").unwrap_or_default());
+            henchman.set_talent_points(self.int(pawn, "m_TalentPoints").unwrap_or_default());
+            *henchman.simple_talents_mut() = self.simple_talents(pawn);
+            *henchman.complex_talents_mut() = self.complex_talents(pawn);
+            *henchman.equipment_mut() = self.items(pawn, "m_aEquipped");
+            *henchman.quick_slots_mut() = self.items(pawn, "m_QuickSlotArray");
+        }
+
+        Ok(())
+    }
+
+    fn player(
+        &self, player: &mut Player, current_game: &List<RcRef<Property>>,
+        pawn: &List<RcRef<Property>>,
+    ) -> Result<()> {
+        // Role-play
+        *player.first_name_mut() = self.string(pawn, "m_FirstName").unwrap_or_default();
+        let gender = self.name(pawn, "m_Gender").unwrap_or_default();
+        player.set_is_female(gender.ends_with("FEMALE"));
+        let origin = self.name(pawn, "m_BackgroundOrigin").unwrap_or_default();
+        *player.origin_mut() = match origin.rsplit('_').next() {
+            Some("SPACER") => Origin::Spacer,
+            Some("COLONY") => Origin::Colonist,
+            Some("EARTHBORN") => Origin::Earthborn,
+            _ => Origin::None,
+        };
+        let notoriety = self.name(pawn, "m_BackgroundNotoriety").unwrap_or_default();
+        *player.notoriety_mut() = match notoriety.rsplit('_').next() {
+            Some("SURVIVOR") => Notoriety::Survivor,
+            Some("WARHERO") | Some("HERO") => Notoriety::Warhero,
+            Some("RUTHLESS") => Notoriety::Ruthless,
+            _ => Notoriety::None,
+        };
+        // Pas de code de visage dans ME1, celui du modèle ne correspond à rien
+        player.face_code_mut().clear();
+
+        // Gameplay
+        let class_base = self.name(pawn, "m_ClassBase").unwrap_or_default();
+        let class_idx = Me1LeClass::variants()
+            .iter()
+            .position(|variant| class_base.ends_with(&variant.to_uppercase()))
+            .unwrap_or_default();
+        *player.player_class_mut() = Me1LeClass::from(class_idx);
+        player.set_stats(&self.pawn_stats(pawn));
+        player.set_specialization_bonus_id(self.int(pawn, "m_SpecializationBonusID").unwrap_or(-1));
+        player.set_level(self.int(pawn, "m_XPLevel").unwrap_or_default());
+        player.set_talent_points(self.int(pawn, "m_TalentPoints").unwrap_or_default());
+        player.reset_progression(self.bool(current_game, "m_IsDriving").unwrap_or_default());
+        *player.simple_talents_mut() = self.simple_talents(pawn);
+        *player.complex_talents_mut() = self.complex_talents(pawn);
+
+        if let Some(StructType::Properties(game_options)) =
+            self.struct_type(current_game, "m_GameOptions")
+        {
+            let difficulty = self.int(&game_options, "m_nCombatDifficulty").unwrap_or_default();
+            if let Some(option) = player.game_options_mut().get_mut(0) {
+                option.set(difficulty);
+            }
+        }
+
+        // Escouade / inventaire
+        let m_squad = self.object(pawn, "m_Squad").map(|id| self.data(id));
+        if let Some(m_squad) = m_squad {
+            player
+                .set_current_xp(self.int(m_squad, "m_nSquadExperience").unwrap_or_default() as f32);
+
+            if let Some(m_inventory) = self.object(m_squad, "m_Inventory").map(|id| self.data(id)) {
+                player.set_credits(self.int(m_inventory, "m_nResourceCredits").unwrap_or_default());
+                player.set_medigel(
+                    self.float(m_inventory, "m_fResourceMedigel").unwrap_or_default() as i32,
+                );
+                player.set_grenades(
+                    self.int(m_inventory, "m_nResourceGrenades").unwrap_or_default() as f32,
+                );
+                player
+                    .set_omnigel(self.float(m_inventory, "m_fResourceSalvage").unwrap_or_default());
+
+                let mut inventory = player.inventory_mut();
+                *inventory.inventory_mut() = self.items(m_inventory, "m_aItem");
+            }
+        }
+
+        {
+            let mut inventory = player.inventory_mut();
+            *inventory.equipment_mut() = self.items(pawn, "m_aEquipped");
+            *inventory.quick_slots_mut() = self.items(pawn, "m_QuickSlotArray");
+            *inventory.buy_pack_mut() = self.items(current_game, "m_savedBuybackItems");
+        }
+
+        // Head morph
+        let head_morph = self
+            .object(current_game, "m_PlayerAppearance")
+            .filter(|&id| id > 0)
+            .and_then(|appearance| self.object(self.data(appearance), "m_oMorphFace"))
+            .filter(|&id| id > 0)
+            .map(|morph_face| self.head_morph(self.data(morph_face)))
+            .transpose()?;
+        *player.head_morph_mut() = head_morph.map(Into::into);
+
+        Ok(())
+    }
+
+    fn pawn_stats(&self, pawn: &List<RcRef<Property>>) -> PawnStats {
+        let int = |name| self.int(pawn, name).unwrap_or_default();
+        let float = |name| self.float(pawn, name).unwrap_or_default();
+        PawnStats {
+            localized_last_name: int("m_LastName"),
+            localized_class_name: int("m_LocalizedClassName"),
+            auto_levelup_template_id: int("m_AutoLevelUpTemplateID"),
+            health_per_level: float("m_HealthPerLevel"),
+            stability: float("m_StabilityCurrent"),
+            stamina: int("m_Stamina"),
+            focus: int("m_Focus"),
+            precision: int("m_Precision"),
+            coordination: int("m_Coordination"),
+            health: float("m_HealthCurrent"),
+            shield: float("m_ShieldCurrent"),
+        }
+    }
+
+    fn simple_talents(&self, pawn: &List<RcRef<Property>>) -> Vec<RcRef<SimpleTalent>> {
+        self.structs(pawn, "m_aSimpleTalents")
+            .iter()
+            .map(|talent| {
+                let mut simple = SimpleTalent::default();
+                simple.set_talent_id(self.int(talent, "m_TalentID").unwrap_or_default());
+                simple.set_current_rank(self.int(talent, "m_Ranks").unwrap_or_default());
+                simple.into()
+            })
+            .collect()
+    }
+
+    fn complex_talents(&self, pawn: &List<RcRef<Property>>) -> Vec<RcRef<ComplexTalent>> {
+        self.structs(pawn, "m_aComplexTalents")
+            .iter()
+            .map(|talent| {
+                let int = |name| self.int(talent, name).unwrap_or_default();
+
+                let mut complex = ComplexTalent::default();
+                complex.set_talent_id(int("m_TalentID"));
+                complex.set_current_rank(int("m_Ranks"));
+                complex.set_max_rank(int("m_MaxRank"));
+                complex.set_level_offset(int("m_LevelOffset"));
+                complex.set_levels_per_rank(int("m_LevelsPerRank"));
+                complex.set_visual_order(int("m_VisualOrder"));
+                *complex.prereq_talent_ids_mut() = self.ints(talent, "m_PrereqTalentIDArray");
+                *complex.prereq_talent_ranks_mut() = self.ints(talent, "m_PrereqTalentRankArray");
+                complex.into()
+            })
+            .collect()
+    }
+
+    fn items(&self, properties: &List<RcRef<Property>>, name: &str) -> Vec<RcRef<Item>> {
+        self.array(properties, name)
+            .iter()
+            .filter_map(|item| match *item {
+                ArrayType::Object(object_id) => Some(self.item(object_id).into()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn item(&self, object_id: i32) -> Item {
+        let mut item = Item::default();
+        // Emplacement vide
+        if object_id <= 0 {
+            return item;
+        }
+
+        let properties = self.data(object_id);
+        item.set_item_id(self.int(properties, "m_nID").unwrap_or_default());
+        *item.item_level_mut() = self.item_level(properties);
+        item.set_manufacturer_id(self.int(properties, "m_manufacturer").unwrap_or_default());
+        item.set_plot_conditional_id(self.int(properties, "m_plotConditionalID").unwrap_or(-1));
+
+        let xmods = self
+            .structs(properties, "m_aSlotSpec")
+            .iter()
+            .flat_map(|slot_spec| self.array(slot_spec, "m_aXMod"))
+            .filter_map(|xmod| match xmod {
+                ArrayType::Object(object_id) if object_id > 0 => Some(object_id),
+                _ => None,
+            })
+            .map(|object_id| {
+                let properties = self.data(object_id);
+                let mut item_mod = ItemMod::default();
+                item_mod.set_item_id(self.int(properties, "m_nID").unwrap_or_default());
+                *item_mod.item_level_mut() = self.item_level(properties);
+                item_mod.set_manufacturer_id(
+                    self.int(properties, "m_manufacturer").unwrap_or_default(),
+                );
+                item_mod.set_plot_conditional_id(
+                    self.int(properties, "m_plotConditionalID").unwrap_or(-1),
+                );
+                item_mod.into()
+            })
+            .collect();
+        *item.attached_mods_mut() = xmods;
+
+        item
+    }
+
+    fn item_level(&self, properties: &List<RcRef<Property>>) -> ItemLevel {
+        let level = self.byte(properties, "m_eSophistication").unwrap_or_default() as usize;
+        if level < ItemLevel::variants().len() {
+            ItemLevel::from(level)
+        } else {
+            ItemLevel::None
+        }
+    }
+
+    fn head_morph(&self, morph_face: &List<RcRef<Property>>) -> Result<HeadMorph> {
+        let hair_mesh = self
+            .object(morph_face, "m_oHairMesh")
+            .filter(|&id| id < 0)
+            .map(|id| self.player.get_import_path(id))
+            .unwrap_or_else(|| String::from("None"));
+
+        let morph_features = self
+            .structs(morph_face, "m_aMorphFeatures")
+            .iter()
+            .filter_map(|feature| {
+                let name = self.name(feature, "sFeatureName")?;
+                Some((name, self.float(feature, "Offset").unwrap_or_default().into()))
+            })
+            .collect();
+
+        let mut offset_bones = IndexMap::new();
+        for bone in self.structs(morph_face, "m_aFinalSkeleton") {
+            if let (Some(name), Some(StructType::Vector(position))) =
+                (self.name(&bone, "nName"), self.struct_type(&bone, "vPos"))
+            {
+                offset_bones.insert(name, copy_ref(&position)?);
+            }
+        }
+
+        let mut lod0_vertices = Vec::new();
+        if let Some(StructType::Properties(vertex_buffer)) =
+            self.struct_type(morph_face, "m_aVertexBuffersLOD0")
+        {
+            for vertex in self.array(&vertex_buffer, "m_vPosition") {
+                if let ArrayType::Vector(vertex) = vertex {
+                    lod0_vertices.push(copy_ref(&vertex)?);
+                }
+            }
+        }
+
+        let mut scalar_parameters = IndexMap::new();
+        let mut vector_parameters = IndexMap::new();
+        let mut texture_parameters = IndexMap::new();
+        if let Some(material) = self.object(morph_face, "m_oMaterialOverrides").filter(|&id| id > 0)
+        {
+            let material = self.data(material);
+            for scalar in self.structs(material, "m_aScalarOverrides") {
+                if let Some(name) = self.name(&scalar, "nName") {
+                    let value = self.float(&scalar, "sValue").unwrap_or_default();
+                    scalar_parameters.insert(name, value.into());
+                }
+            }
+            for color in self.structs(material, "m_aColorOverrides") {
+                if let (Some(name), Some(StructType::LinearColor(value))) =
+                    (self.name(&color, "nName"), self.struct_type(&color, "cValue"))
+                {
+                    let LinearColor { r, g, b, a } = *value.borrow();
+                    vector_parameters.insert(name, LinearColor { r, g, b, a }.into());
+                }
+            }
+            for texture in self.structs(material, "m_aTextureOverrides") {
+                if let Some(name) = self.name(&texture, "nName") {
+                    let path = self
+                        .object(&texture, "m_pTexture")
+                        .filter(|&id| id < 0)
+                        .map(|id| self.player.get_import_path(id))
+                        .unwrap_or_else(|| String::from("None"));
+                    texture_parameters.insert(name, path.into());
+                }
+            }
+        }
+
+        Ok(HeadMorph {
+            hair_mesh: hair_mesh.into(),
+            accessory_mesh: Vec::new().into(),
+            morph_features: RcRef::new(morph_features),
+            offset_bones: offset_bones.into(),
+            lod0_vertices: lod0_vertices.into(),
+            // Les saves ME1 n'ont que le LOD0, les LOD1-3 y sont déjà vides
+            lod1_vertices: Vec::new().into(),
+            lod2_vertices: Vec::new().into(),
+            lod3_vertices: Vec::new().into(),
+            scalar_parameters: scalar_parameters.into(),
+            vector_parameters: vector_parameters.into(),
+            texture_parameters: texture_parameters.into(),
+        })
+    }
+
+    // Lecture de l'arbre de propriétés
+    fn data(&self, object_id: i32) -> &List<RcRef<Property>> {
+        &self.player.get_data(object_id).properties
+    }
+
+    fn find<'a>(
+        &self, properties: &'a List<RcRef<Property>>, name: &str,
+    ) -> Option<Ref<'a, Property>> {
+        self.player.find_property(properties, name).map(|property| property.borrow())
+    }
+
+    fn int(&self, properties: &List<RcRef<Property>>, name: &str) -> Option<i32> {
+        match *self.find(properties, name)? {
+            Property::Int { ref value, .. } | Property::StringRef { ref value, .. } => {
+                Some(value.get())
+            }
+            _ => None,
+        }
+    }
+
+    fn float(&self, properties: &List<RcRef<Property>>, name: &str) -> Option<f32> {
+        match *self.find(properties, name)? {
+            Property::Float { ref value, .. } => Some(value.get()),
+            _ => None,
+        }
+    }
+
+    fn bool(&self, properties: &List<RcRef<Property>>, name: &str) -> Option<bool> {
+        match *self.find(properties, name)? {
+            Property::Bool { ref value, .. } => Some(value.get()),
+            _ => None,
+        }
+    }
+
+    fn byte(&self, properties: &List<RcRef<Property>>, name: &str) -> Option<u8> {
+        match *self.find(properties, name)? {
+            Property::Byte { ref value, .. } => Some(value.get()),
+            _ => None,
+        }
+    }
+
+    fn name(&self, properties: &List<RcRef<Property>>, name: &str) -> Option<String> {
+        match *self.find(properties, name)? {
+            Property::Name { ref value_name_id, .. } => {
+                Some(self.player.get_name(value_name_id.get()))
+            }
+            _ => None,
+        }
+    }
+
+    fn string(&self, properties: &List<RcRef<Property>>, name: &str) -> Option<String> {
+        match *self.find(properties, name)? {
+            Property::Str { ref string, .. } => Some(string.borrow().clone()),
+            _ => None,
+        }
+    }
+
+    fn object(&self, properties: &List<RcRef<Property>>, name: &str) -> Option<i32> {
+        self.player.find_object_id(properties, name)
+    }
+
+    fn struct_type(&self, properties: &List<RcRef<Property>>, name: &str) -> Option<StructType> {
+        match *self.find(properties, name)? {
+            Property::Struct { ref struct_type, .. } => Some(struct_type.clone()),
+            _ => None,
+        }
+    }
+
+    fn array(&self, properties: &List<RcRef<Property>>, name: &str) -> Vec<ArrayType> {
+        match self.find(properties, name).as_deref() {
+            Some(Property::Array { array, .. }) => array.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn structs(
+        &self, properties: &List<RcRef<Property>>, name: &str,
+    ) -> Vec<List<RcRef<Property>>> {
+        self.array(properties, name)
+            .into_iter()
+            .filter_map(|item| match item {
+                ArrayType::Properties(properties) => Some(properties),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn ints(&self, properties: &List<RcRef<Property>>, name: &str) -> Vec<RcCell<i32>> {
+        self.array(properties, name)
+            .into_iter()
+            .filter_map(|item| match item {
+                ArrayType::Int(value) => Some(value.get().into()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// Les deux jeux partagent les mêmes structures, on les copie en passant par la sérialisation
+fn copy<T: Serialize + DeserializeOwned>(value: &T) -> Result<T> {
+    let bytes = unreal::Serializer::to_vec(value)?;
+    Ok(unreal::Deserializer::from_bytes(&bytes)?)
+}
+
+fn copy_ref<T: Serialize + DeserializeOwned>(value: &RcRef<T>) -> Result<RcRef<T>> {
+    copy(value)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::rc::Rc;
+
+    use anyhow::Result;
+    use crc::{Crc, CRC_32_BZIP2};
+
+    use super::*;
+    use crate::save_data::{
+        mass_effect_1::Me1SaveGameSeed,
+        shared::plot::{Codex, Journal},
+    };
+
+    #[test]
+    fn convert_me1_save() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let schema = Rc::new(ron::from_str(&fs::read_to_string("databases/me1_schema.ron")?)?);
+        let me1_save_game =
+            unreal::Deserializer::from_bytes_seed(Me1SaveGameSeed(&schema), &input)?;
+
+        // Convert
+        let me1_le_save_game = Me1LeSaveGame::from_me1(&me1_save_game)?;
+        let mut output = unreal::Serializer::to_vec(&me1_le_save_game)?;
+
+        // Checksum
+        {
+            let checksum_offset = output.len() - 12;
+            let crc = Crc::<u32>::new(&CRC_32_BZIP2);
+            let checksum = crc.checksum(&output[..checksum_offset]);
+
+            // Update checksum
+            let end = checksum_offset + 4;
+            output[checksum_offset..end].swap_with_slice(&mut u32::to_le_bytes(checksum));
+        }
+
+        // Deserialize the converted save
+        let me1_le_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let save_data = me1_le_save_game.save_data.borrow();
+        let player = save_data.player();
+
+        assert_eq!(*player.first_name(), "Clare");
+        assert_eq!(player.level(), 60);
+        assert!(player.is_female());
+        assert!(matches!(*player.player_class(), Me1LeClass::Adept));
+        assert_eq!(player.complex_talents().len(), 13);
+        assert_eq!(player.inventory().inventory().len(), 104);
+        assert_eq!(player.inventory().buy_pack().len(), 20);
+
+        // Rien de la partie de la save modèle
+        let journal = unreal::Serializer::to_vec(&save_data.journal)?;
+        assert_eq!(journal, unreal::Serializer::to_vec(&Journal::default())?);
+        let codex = unreal::Serializer::to_vec(&save_data.codex)?;
+        assert_eq!(codex, unreal::Serializer::to_vec(&Codex::default())?);
+        let henchmen = me1_save_game.player().henchmen();
+        for henchman in save_data.squad().iter() {
+            assert!(henchmen.iter().any(|(tag, _)| *henchman.borrow().tag() == *tag));
+        }
+
+        let head_morph = player.head_morph();
+        let head_morph = head_morph.as_ref().unwrap().borrow();
+        assert_eq!(*head_morph.hair_mesh(), "BIOG_HMF_HIR_PRO.Classy.HMF_HIR_Cls_MDL");
+        assert_eq!(head_morph.lod0_vertices().len(), 2232);
+
+        let me1_state = me1_save_game.state();
+        let me1_plot = me1_state.plot();
+        let plot = save_data.plot();
+        assert_eq!(plot.booleans().len(), me1_plot.booleans().len());
+        assert_eq!(plot.integers().len(), me1_plot.integers().len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn template_has_no_personal_data() -> Result<()> {
+        let template: Me1LeSaveGame = unreal::Deserializer::from_bytes(TEMPLATE)?;
+        let save_data = template.save_data.borrow();
+        let player = save_data.player();
+
+        assert!(save_data.character_id.borrow().is_empty());
+        assert!(save_data.display_name.borrow().is_empty());
+        assert!(save_data.file_name.borrow().is_empty());
+        assert_eq!(save_data.seconds_played.get(), 0);
+        assert!(player.first_name().is_empty());
+        assert!(player.face_code().is_empty());
+        assert!(player.head_morph().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn convert_me1_save_with_missing_properties() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let schema = Rc::new(ron::from_str(&fs::read_to_string("databases/me1_schema.ron")?)?);
+        let mut me1_save_game =
+            unreal::Deserializer::from_bytes_seed(Me1SaveGameSeed(&schema), &input)?;
+
+        // La save modèle a ses propres valeurs, elles ne doivent pas rester
+        let template: Me1LeSaveGame = unreal::Deserializer::from_bytes(TEMPLATE)?;
+        let template_squad: Vec<_> = {
+            let template = template.save_data.borrow();
+            let player = template.player();
+            assert!(player.level() != 0);
+            assert!(player.current_xp() != 0.0);
+            let squad = template.squad();
+            squad.iter().map(|henchman| henchman.borrow().tag().clone()).collect()
+        };
+
+        // Le jeu n'écrit pas les propriétés à leur valeur par défaut
+        let (henchman_tag, henchman_id) = {
+            let mut me1_player = me1_save_game.player_mut();
+            let remove = |player: &mut Me1Player, object_id, names: &[&str]| {
+                for name in names {
+                    let properties = &player.get_data(object_id).properties;
+                    if let Some(property) = player.find_property(properties, name).cloned() {
+                        player.get_data_mut(object_id).properties.remove_property(&property);
+                    }
+                }
+            };
+
+            let current_game = me1_player.find_object("CurrentGame").unwrap();
+            let m_player = me1_player
+                .find_object_id(&me1_player.get_data(current_game).properties, "m_Player");
+            let m_player = m_player.unwrap();
+            let m_squad =
+                me1_player.find_object_id(&me1_player.get_data(m_player).properties, "m_Squad");
+            remove(
+                &mut me1_player,
+                m_player,
+                &["m_XPLevel", "m_LocalizedClassName", "m_AutoLevelUpTemplateID"],
+            );
+            remove(&mut me1_player, m_squad.unwrap(), &["m_nSquadExperience"]);
+
+            let (tag, henchman_id) = me1_player
+                .henchmen()
+                .into_iter()
+                .find(|(tag, _)| template_squad.contains(tag))
+                .unwrap();
+            remove(&mut me1_player, henchman_id, &["m_XPLevel"]);
+            (tag, henchman_id)
+        };
+        assert!(henchman_id > 0);
+
+        let me1_le_save_game = Me1LeSaveGame::from_me1(&me1_save_game)?;
+        let save_data = me1_le_save_game.save_data.borrow();
+        let player = save_data.player();
+        assert_eq!(player.level(), 0);
+        assert_eq!(player.localized_class_name(), 0);
+        assert_eq!(player.auto_levelup_template_id(), 0);
+        assert_eq!(player.current_xp(), 0.0);
+
+        let squad = save_data.squad();
+        let henchman = squad.iter().find(|henchman| *henchman.borrow().tag() == henchman_tag);
+        assert_eq!(henchman.unwrap().borrow().level(), 0);
+
+        Ok(())
+    }
+}
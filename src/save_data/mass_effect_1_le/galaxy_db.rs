@@ -0,0 +1,44 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct DbSystem {
+    pub name: String,
+    pub planets: IndexMap<i32, String>,
+}
+
+#[derive(Deserialize)]
+pub struct DbCluster {
+    pub name: String,
+    pub systems: IndexMap<i32, DbSystem>,
+}
+
+#[derive(Deserialize, Deref)]
+pub struct Me1GalaxyDb(IndexMap<i32, DbCluster>);
+
+impl Me1GalaxyDb {
+    pub fn systems(&self) -> impl Iterator<Item = (&i32, &DbSystem)> {
+        self.values().flat_map(|cluster| cluster.systems.iter())
+    }
+
+    pub fn planets(&self) -> impl Iterator<Item = (&i32, &String)> {
+        self.systems().flat_map(|(_, system)| system.planets.iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_galaxy_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_galaxy_db.ron")?;
+        let _me1_galaxy_db: Me1GalaxyDb = ron::from_str(&input)?;
+
+        Ok(())
+    }
+}
@@ -6,24 +6,62 @@ use crate::save_data::Dummy;
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct ArtPlaceableBehavior {
-    is_dead: bool,
-    generated_treasure: bool,
+    pub is_dead: bool,
+    pub generated_treasure: bool,
     challenge_scaled: bool,
     owner: Option<BaseObject>,
-    health: f32,
-    current_health: f32,
+    pub health: f32,
+    pub current_health: f32,
     enabled: bool,
-    current_fsm_state_name: String,
-    is_destroyed: bool,
-    state_0: String,
-    state_1: String,
-    use_case: u8,
+    pub current_fsm_state_name: String,
+    pub is_destroyed: bool,
+    pub state_0: String,
+    pub state_1: String,
+    pub use_case: u8,
     use_case_override: bool,
     player_only: bool,
-    skill_difficulty: u8,
-    inventory: Option<BaseObject>,
-    skill_game_failed: bool,
-    skill_game_xp_awarded: bool,
+    pub skill_difficulty: u8,
+    pub inventory: Option<BaseObject>,
+    pub skill_game_failed: bool,
+    pub skill_game_xp_awarded: bool,
+}
+
+impl ArtPlaceableBehavior {
+    // Dans les saves seuls les objets à pirater (`use_case` 3) ont une difficulté,
+    // elle est gardée après un piratage réussi (`skill_game_xp_awarded`)
+    pub fn is_locked(&self) -> bool {
+        self.skill_difficulty.get() > 0 && !self.skill_game_xp_awarded.get()
+    }
+
+    // Renvoie la difficulté d'origine, nécessaire pour reverrouiller
+    pub fn unlock(&self) -> u8 {
+        let difficulty = self.skill_difficulty.get();
+        self.skill_difficulty.set(0);
+        self.skill_game_failed.set(false);
+        difficulty
+    }
+
+    pub fn lock(&self, difficulty: u8) {
+        self.skill_difficulty.set(difficulty);
+    }
+
+    // Un échec au mini-jeu de décryptage verrouille le conteneur définitivement
+    pub fn reset_failed_bypass(&self) {
+        self.skill_game_failed.set(false);
+        self.skill_game_xp_awarded.set(false);
+    }
+
+    // Le jeu génère un nouveau butin à la prochaine ouverture,
+    // l'état de l'objet (ouvert / fermé) n'est pas modifié
+    pub fn regenerate_treasure(&self) {
+        self.generated_treasure.set(false);
+    }
+
+    pub fn repair(&self) {
+        self.is_destroyed.set(false);
+        self.is_dead.set(false);
+        self.current_health.set(self.health.get());
+    }
 }
 
 #[rcize_fields]
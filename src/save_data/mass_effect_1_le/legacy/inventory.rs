@@ -1,20 +1,47 @@
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 
-use super::{BaseObject, OptionObjectProxy};
-use crate::save_data::mass_effect_1_le::player::ItemLevel;
+use super::{BaseObject, Object, OptionObjectProxy};
+use crate::{
+    save_data::{
+        mass_effect_1_le::{item_db::DbItem, player::ItemLevel},
+        RcRef,
+    },
+    unreal,
+};
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Shop {
-    last_player_level: i32,
-    is_initialized: bool,
-    inventory: Vec<OptionObjectProxy>,
+    pub last_player_level: i32,
+    pub is_initialized: bool,
+    pub inventory: Vec<OptionObjectProxy>,
+}
+
+impl Shop {
+    // Le marchand régénère son stock à la prochaine visite
+    pub fn restock(&self) {
+        self.is_initialized.set(false);
+        self.last_player_level.set(0);
+    }
+
+    // Dernier objet vendu par le marchand, tous stocks confondus
+    pub fn item_template(&self) -> Option<RcRef<BaseObject>> {
+        self.inventory.borrow().iter().rev().find_map(|stock| {
+            let stock = stock.borrow().proxy.borrow().clone()?;
+            let stock = stock.borrow();
+            match stock._object {
+                Object::Inventory(ref inventory) => inventory.borrow().items().last().cloned(),
+                _ => None,
+            }
+        })
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Inventory {
-    items: Vec<BaseObject>,
+    pub items: Vec<BaseObject>,
     plot_items: Vec<PlotItem>,
     credits: i32,
     grenades: i32,
@@ -22,6 +49,59 @@ pub struct Inventory {
     omnigel: f32,
 }
 
+impl Inventory {
+    // Copie profonde de l'objet, avec un nom unique dans l'inventaire
+    pub fn duplicate_item(&self, idx: usize) -> Result<()> {
+        let item = {
+            let items = self.items.borrow();
+            let item = items.get(idx).context("Item not found in the inventory")?;
+            self.copy_item(item)?
+        };
+        self.items.borrow_mut().insert(idx + 1, item);
+        Ok(())
+    }
+
+    // Ajoute un objet de la base de données à la fin de l'inventaire,
+    // les données propres au type de l'objet sont copiées depuis `template`
+    pub fn add_item(&self, template: &RcRef<BaseObject>, db_item: DbItem) -> Result<()> {
+        let item = self.copy_item(template)?;
+        match item.borrow()._object {
+            Object::Item(ref item) => {
+                let mut item = item.borrow_mut();
+                item.set_item_id(db_item.item_id);
+                item.set_manufacturer_id(db_item.manufacturer_id);
+            }
+            Object::ItemMod(ref item_mod) => {
+                let mut item_mod = item_mod.borrow_mut();
+                item_mod.set_item_id(db_item.item_id);
+                item_mod.set_manufacturer_id(db_item.manufacturer_id);
+            }
+            _ => bail!("`{}` is not an item", item.borrow().owner_name.borrow()),
+        }
+        self.items.borrow_mut().push(item);
+        Ok(())
+    }
+
+    fn copy_item(&self, item: &RcRef<BaseObject>) -> Result<RcRef<BaseObject>> {
+        let bytes = unreal::Serializer::to_vec(item)?;
+        let item: RcRef<BaseObject> = unreal::Deserializer::from_bytes(&bytes)?;
+
+        let owner_name = item.borrow().owner_name.borrow().clone();
+        let (prefix, number) = match owner_name.rsplit_once('_') {
+            Some((prefix, number)) => (prefix.to_owned(), number.parse().unwrap_or(0)),
+            None => (owner_name, 0),
+        };
+        let items = self.items.borrow();
+        let new_name = (number + 1..)
+            .map(|number: u32| format!("{}_{}", prefix, number))
+            .find(|name| !items.iter().any(|item| *item.borrow().owner_name.borrow() == *name))
+            .unwrap();
+        *item.borrow().owner_name.borrow_mut() = new_name;
+
+        Ok(item)
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
@@ -37,9 +117,9 @@ struct PlotItem {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Item {
-    item_id: i32,
-    item_level: ItemLevel,
-    manufacturer_id: i32,
+    pub item_id: i32,
+    pub item_level: ItemLevel,
+    pub manufacturer_id: i32,
     plot_conditional_id: i32,
     slot_specs: Vec<ModdableSlotSpec>,
 }
@@ -55,9 +135,9 @@ struct ModdableSlotSpec {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct ItemMod {
-    item_id: i32,
-    item_level: ItemLevel,
-    manufacturer_id: i32,
+    pub item_id: i32,
+    pub item_level: ItemLevel,
+    pub manufacturer_id: i32,
     plot_conditional_id: i32,
     type_id: i32,
 }
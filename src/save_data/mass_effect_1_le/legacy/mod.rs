@@ -18,14 +18,25 @@ use crate::save_data::{
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, RawUi, RawUiChildren)]
 pub struct Map {
-    levels: IndexMap<String, Level>,
-    world: Option<BaseObject>,
+    pub levels: IndexMap<String, Level>,
+    pub world: Option<BaseObject>,
+}
+
+impl Map {
+    pub fn world_info(&self) -> Option<RcRef<World>> {
+        let world = self.world();
+        let world = world.as_ref()?.borrow();
+        match world._object {
+            Object::World(ref world) => Some(RcRef::clone(world)),
+            _ => None,
+        }
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, RawUi, RawUiChildren)]
 pub struct Level {
-    objects: Vec<BaseObject>,
+    pub objects: Vec<BaseObject>,
     actors: Vec<String>,
 }
 
@@ -149,21 +160,21 @@ pub struct Vehicle {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", name)]
-struct WorldStreamingState {
-    name: String,
-    enabled: u8,
+pub struct WorldStreamingState {
+    pub name: String,
+    pub enabled: u8,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct World {
-    streaming_states: Vec<WorldStreamingState>,
-    destination_area_map: String,
+    pub streaming_states: Vec<WorldStreamingState>,
+    pub destination_area_map: String,
     destination: Vector,
-    cinematics_seen: Vec<String>,
-    scanned_clusters: Vec<i32>,
-    scanned_systems: Vec<i32>,
-    scanned_planets: Vec<i32>,
+    pub cinematics_seen: Vec<String>,
+    pub scanned_clusters: Vec<i32>,
+    pub scanned_systems: Vec<i32>,
+    pub scanned_planets: Vec<i32>,
     journal_sort_method: u8,
     journal_showing_missions: bool,
     journal_last_selected_mission: i32,
@@ -9,18 +9,18 @@ use crate::save_data::{
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct PawnBehavior {
-    is_dead: bool,
-    generated_treasure: bool,
+    pub is_dead: bool,
+    pub generated_treasure: bool,
     challenge_scaled: bool,
     owner: Option<BaseObject>,
-    health: f32,
-    shield: f32,
-    first_name: String,
+    pub health: f32,
+    pub shield: f32,
+    pub first_name: String,
     localized_last_name: i32,
-    health_max: f32,
+    pub health_max: f32,
     health_regen_rate: f32,
     radar_range: f32,
-    level: i32,
+    pub level: i32,
     health_per_level: f32,
     stability: f32,
     gender: u8,
@@ -61,6 +61,16 @@ pub struct PawnBehavior {
     equipment: Vec<OptionObjectProxy>,
 }
 
+impl PawnBehavior {
+    pub fn revive(&self) {
+        self.is_dead.set(false);
+        // `health_max` vaut souvent 0, le jeu recalcule la vie max au chargement
+        if self.health.get() <= 0.0 {
+            self.health.set(self.health_max.get().max(1.0));
+        }
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", talent_id)]
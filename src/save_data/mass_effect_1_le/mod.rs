@@ -1,3 +1,5 @@
+mod convert;
+pub mod galaxy_db;
 pub mod item_db;
 pub mod legacy;
 pub mod player;
@@ -22,6 +24,7 @@ use crate::{save_data::RcRef, unreal};
 use super::{
     shared::{
         plot::{Codex, Journal, PlotTable},
+        teleport::TeleportPoint,
         Rotator, SaveTimeStamp, Vector,
     },
     List,
@@ -218,6 +221,35 @@ pub struct Me1LeSaveData {
     pub no_export: NoExport, // Only serialized for normal savegames, not for character export
 }
 
+impl Me1LeSaveData {
+    pub fn teleport(&self, point: &TeleportPoint) {
+        *self.base_level_name.borrow_mut() = point.base_level_name.clone();
+        *self.map_name.borrow_mut() = point.map_name.clone();
+        *self.parent_map_name.borrow_mut() = point.parent_map_name.clone();
+        point.set_position(&self.location, &self.rotation);
+
+        // Les états de streaming sont dans le `World` de la map, seuls ceux du point restent actifs
+        // Sans état connu pour le point, ceux de la save sont laissés tels quels
+        if point.streaming_states.is_empty() {
+            return;
+        }
+
+        let no_export = self.no_export();
+        let world = no_export.as_ref().and_then(|no_export| {
+            let no_export = no_export.borrow();
+            let map = no_export.legacy_maps().get(&point.base_level_name).cloned();
+            map.and_then(|map| map.borrow().world_info())
+        });
+        if let Some(world) = world {
+            for state in world.borrow().streaming_states().iter() {
+                let state = state.borrow();
+                let enabled = point.streaming_states.contains(&*state.name());
+                state.enabled.set(enabled as u8);
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me1LeVersion(i32);
 
@@ -294,8 +326,15 @@ impl serde::Serialize for NoExport {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct NoExportData {
-    legacy_maps: IndexMap<String, Map>,
-    mako: Vehicle,
+    pub legacy_maps: IndexMap<String, Map>,
+    pub mako: Vehicle,
+}
+
+impl NoExportData {
+    // Seule la map courante a un `World`
+    pub fn world(&self) -> Option<RcRef<World>> {
+        self.legacy_maps().values().find_map(|map| map.borrow().world_info())
+    }
 }
 
 #[rcize_fields]
@@ -303,8 +342,8 @@ pub struct NoExportData {
 pub struct Vehicle {
     first_name: String,
     localized_last_name: i32,
-    health: f32,
-    shield: f32,
+    pub health: f32,
+    pub shield: f32,
 }
 
 #[cfg(test)]
@@ -315,7 +354,7 @@ mod test {
     use anyhow::Result;
     use crc::{Crc, CRC_32_BZIP2};
 
-    use super::*;
+    use super::{item_db::DbItem, *};
     use crate::unreal;
 
     #[test]
@@ -389,6 +428,135 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn teleport_streaming_states() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let save_data = me1_save_game.save_data.borrow();
+
+        let (base_level_name, world) = {
+            let no_export = save_data.no_export();
+            let no_export = no_export.as_ref().unwrap().borrow();
+            let legacy_maps = no_export.legacy_maps();
+            legacy_maps
+                .iter()
+                .find_map(|(name, map)| {
+                    map.borrow().world_info().map(|world| (name.clone(), world))
+                })
+                .unwrap()
+        };
+        let states = || {
+            world
+                .borrow()
+                .streaming_states()
+                .iter()
+                .map(|state| {
+                    let state = state.borrow();
+                    let name = state.name().clone();
+                    (name, state.enabled())
+                })
+                .collect::<Vec<_>>()
+        };
+        let before = states();
+        assert!(!before.is_empty());
+
+        let point = |streaming_states: &str| -> Result<TeleportPoint> {
+            Ok(ron::from_str(&format!(
+                "(name: \"\", base_level_name: \"{}\", location: (0, 0, 0), rotation: (0, 0, 0), \
+                streaming_states: [{}])",
+                base_level_name, streaming_states
+            ))?)
+        };
+
+        // No streaming state for the point, the save ones are untouched
+        save_data.teleport(&point("")?);
+        assert!(states() == before);
+
+        // Only the states of the point are enabled
+        let (name, _) = &before[0];
+        save_data.teleport(&point(&format!("\"{}\"", name))?);
+        for (state, enabled) in states() {
+            assert_eq!(enabled, (&state == name) as u8);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_shop_item() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Premier stock non vide d'un marchand
+        let stock = {
+            let save_data = me1_save_game.save_data.borrow();
+            let no_export = save_data.no_export();
+            let no_export = no_export.as_ref().unwrap().borrow();
+            let legacy_maps = no_export.legacy_maps();
+            let stock = legacy_maps
+                .values()
+                .flat_map(|map| map.borrow().levels().values().cloned().collect::<Vec<_>>())
+                .flat_map(|level| level.borrow().objects().clone())
+                .filter_map(|object| match object.borrow()._object {
+                    Object::Shop(ref shop) => Some(shop.borrow().inventory().clone()),
+                    _ => None,
+                })
+                .flatten()
+                .find_map(|stock| {
+                    let stock = stock.borrow().proxy.borrow().clone()?;
+                    let stock = match stock.borrow()._object {
+                        Object::Inventory(ref inventory) => RcRef::clone(inventory),
+                        _ => return None,
+                    };
+                    let is_empty = stock.borrow().items().is_empty();
+                    (!is_empty).then(|| stock)
+                });
+            stock.unwrap()
+        };
+
+        let len = stock.borrow().items().len();
+        stock.borrow().duplicate_item(len - 1)?;
+
+        let stock = stock.borrow();
+        {
+            let items = stock.items();
+            assert_eq!(items.len(), len + 1);
+            let (last, copy) = (items[len - 1].borrow(), items[len].borrow());
+            assert_ne!(*last.owner_name.borrow(), *copy.owner_name.borrow());
+            assert_eq!(last._class_name, copy._class_name);
+        }
+        assert!(stock.duplicate_item(len + 1).is_err());
+
+        // Ajout d'un objet de la base de données sur le modèle du dernier objet
+        let template = stock.items().last().cloned().unwrap();
+        let db_item = DbItem { item_id: 7, manufacturer_id: 42 };
+        stock.add_item(&template, db_item)?;
+        {
+            let items = stock.items();
+            assert_eq!(items.len(), len + 2);
+            let added = items[len + 1].borrow();
+            assert_ne!(*added.owner_name.borrow(), *template.borrow().owner_name.borrow());
+            let added_item = match added._object {
+                Object::Item(ref item) => {
+                    let item = item.borrow();
+                    DbItem { item_id: item.item_id(), manufacturer_id: item.manufacturer_id() }
+                }
+                Object::ItemMod(ref item_mod) => {
+                    let item_mod = item_mod.borrow();
+                    let manufacturer_id = item_mod.manufacturer_id();
+                    DbItem { item_id: item_mod.item_id(), manufacturer_id }
+                }
+                _ => unreachable!(),
+            };
+            assert!(added_item == db_item);
+        }
+
+        // La sauvegarde reste lisible
+        let output = unreal::Serializer::to_vec(&me1_save_game)?;
+        let _: Me1LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        Ok(())
+    }
+
     // #[test]
     // fn uncompress() -> Result<()> {
     //     let input = fs::read("test/ME1Le_Export.pcsav")?;
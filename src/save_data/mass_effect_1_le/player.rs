@@ -63,6 +63,57 @@ pub struct Player {
     secondary_weapon: String,
 }
 
+impl Player {
+    pub fn set_stats(&self, stats: &PawnStats) {
+        self.localized_last_name.set(stats.localized_last_name);
+        self.localized_class_name.set(stats.localized_class_name);
+        self.auto_levelup_template_id.set(stats.auto_levelup_template_id);
+        self.health_per_level.set(stats.health_per_level);
+        self.stability.set(stats.stability);
+        self.stamina.set(stats.stamina);
+        self.focus.set(stats.focus);
+        self.precision.set(stats.precision);
+        self.coordination.set(stats.coordination);
+        self.health.set(stats.health);
+        self.shield.set(stats.shield);
+    }
+
+    // Champs sans équivalent dans une save ME1, on remet les valeurs d'un nouveau personnage
+    // plutôt que de garder celles de la save modèle
+    pub fn reset_progression(&self, is_driving: bool) {
+        self.spectre_rank.set(0);
+        self.talent_pool_points.set(0);
+        *self.mapped_talent.borrow_mut() = String::from("None");
+        self.armor_overridden.set(false);
+        self.toxic.set(0.0);
+        self.skill_charm.set(0.0);
+        self.skill_intimidate.set(0.0);
+        self.skill_haggle.set(0.0);
+        self.xp_level.set(self.level.get());
+        self.is_driving.set(is_driving);
+        *self.last_power.borrow_mut() = String::from("None");
+        self.hotkeys.borrow_mut().clear();
+        *self.primary_weapon.borrow_mut() = String::from("None");
+        *self.secondary_weapon.borrow_mut() = String::from("None");
+    }
+}
+
+// Statistiques communes au joueur et à l'escouade lues dans une save ME1
+#[derive(Default)]
+pub struct PawnStats {
+    pub localized_last_name: i32,
+    pub localized_class_name: i32,
+    pub auto_levelup_template_id: i32,
+    pub health_per_level: f32,
+    pub stability: f32,
+    pub stamina: i32,
+    pub focus: i32,
+    pub precision: i32,
+    pub coordination: i32,
+    pub health: f32,
+    pub shield: f32,
+}
+
 #[derive(Deserialize, Serialize, Clone, RawUi, PartialEq)]
 pub enum Me1LeClass {
     Soldier,
@@ -91,8 +142,8 @@ pub struct ComplexTalent {
     pub level_offset: i32,
     pub levels_per_rank: i32,
     pub visual_order: i32,
-    prereq_talent_ids: Vec<i32>,
-    prereq_talent_ranks: Vec<i32>,
+    pub prereq_talent_ids: Vec<i32>,
+    pub prereq_talent_ranks: Vec<i32>,
 }
 
 #[rcize_fields]
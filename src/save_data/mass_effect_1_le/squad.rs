@@ -1,13 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use super::player::{ComplexTalent, Item, SimpleTalent};
+use super::player::{ComplexTalent, Item, PawnStats, SimpleTalent};
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", tag)]
 pub struct Henchman {
     pub tag: String,
-    simple_talents: Vec<SimpleTalent>,
+    pub simple_talents: Vec<SimpleTalent>,
     pub complex_talents: Vec<ComplexTalent>,
     pub equipment: Vec<Item>,
     pub quick_slots: Vec<Item>,
@@ -30,8 +30,25 @@ pub struct Henchman {
     attribute_secondary: u8,
     health: f32,
     shield: f32,
-    level: i32,
+    pub level: i32,
     helmet_shown: bool,
     current_quick_slot: u8,
     health_max: f32,
 }
+
+impl Henchman {
+    pub fn set_stats(&self, stats: &PawnStats) {
+        self.localized_last_name.set(stats.localized_last_name);
+        self.localized_class_name.set(stats.localized_class_name);
+        self.auto_levelup_template_id.set(stats.auto_levelup_template_id);
+        self.health_per_level.set(stats.health_per_level);
+        self.stability.set(stats.stability);
+        self.stamina.set(stats.stamina);
+        self.focus.set(stats.focus);
+        self.precision.set(stats.precision);
+        self.coordination.set(stats.coordination);
+        self.health.set(stats.health);
+        self.shield.set(stats.shield);
+        self.talent_pool_points.set(0);
+    }
+}
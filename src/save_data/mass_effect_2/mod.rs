@@ -9,7 +9,10 @@ use anyhow::Result;
 use serde::{de, Deserialize, Deserializer, Serialize};
 
 use super::shared::{
+    dlc_db::DbDlc,
+    level_repair::{LevelRepair, LevelRepairAction, LevelRepairDb},
     plot::{Codex, Journal, PlotTable},
+    teleport::TeleportPoint,
     Door, EndGameState, Kismet, Level, Rotator, SaveTimeStamp, StreamingState, Vector,
 };
 use super::Guid;
@@ -30,9 +33,9 @@ pub struct Me2SaveGame {
     current_loading_tip: i32,
     levels: Vec<Level>,
     streaming_states: Vec<StreamingState>,
-    kismet_records: Vec<Kismet>,
-    doors: Vec<Door>,
-    pawns: Vec<Guid>,
+    pub kismet_records: Vec<Kismet>,
+    pub doors: Vec<Door>,
+    pub pawns: Vec<Guid>,
     pub player: Player,
     squad: Vec<Henchman>,
     pub plot: PlotTable,
@@ -40,7 +43,31 @@ pub struct Me2SaveGame {
     codex: Codex,
     pub me1_plot: PlotTable,
     galaxy_map: GalaxyMap,
-    dependant_dlcs: Vec<DependentDlc>,
+    pub dependant_dlcs: Vec<DependentDlc>,
+}
+
+impl Me2SaveGame {
+    pub fn teleport(&self, point: &TeleportPoint) {
+        *self.base_level_name.borrow_mut() = point.base_level_name.clone();
+        point.set_position(&self.location, &self.rotation);
+        point.set_levels(&self.levels, &self.streaming_states);
+    }
+
+    pub fn level_repair<'a>(&self, level_repair_db: &'a LevelRepairDb) -> Option<&'a LevelRepair> {
+        level_repair_db.find(&self.base_level_name.borrow())
+    }
+
+    pub fn repair_level(&self, repair: &LevelRepair, action: LevelRepairAction) {
+        match action {
+            LevelRepairAction::ResetStreamingStates => {
+                repair.reset_streaming_states(&self.streaming_states)
+            }
+            LevelRepairAction::ClearStaleKismetRecords => {
+                repair.clear_stale_kismet_records(&self.kismet_records)
+            }
+            LevelRepairAction::ResetDoors => repair.reset_doors(&self.doors),
+        }
+    }
 }
 
 #[derive(Serialize, Clone)]
@@ -89,9 +116,9 @@ pub struct Me2LeSaveGame {
     current_loading_tip: i32,
     levels: Vec<Level>,
     streaming_states: Vec<StreamingState>,
-    kismet_records: Vec<Kismet>,
-    doors: Vec<Door>,
-    pawns: Vec<Guid>,
+    pub kismet_records: Vec<Kismet>,
+    pub doors: Vec<Door>,
+    pub pawns: Vec<Guid>,
     pub player: Player,
     me1_import_bonus: Me1ImportBonus,
     squad: Vec<Henchman>,
@@ -100,7 +127,31 @@ pub struct Me2LeSaveGame {
     codex: Codex,
     pub me1_plot: PlotTable,
     galaxy_map: GalaxyMap,
-    dependant_dlcs: Vec<DependentDlc>,
+    pub dependant_dlcs: Vec<DependentDlc>,
+}
+
+impl Me2LeSaveGame {
+    pub fn teleport(&self, point: &TeleportPoint) {
+        *self.base_level_name.borrow_mut() = point.base_level_name.clone();
+        point.set_position(&self.location, &self.rotation);
+        point.set_levels(&self.levels, &self.streaming_states);
+    }
+
+    pub fn level_repair<'a>(&self, level_repair_db: &'a LevelRepairDb) -> Option<&'a LevelRepair> {
+        level_repair_db.find(&self.base_level_name.borrow())
+    }
+
+    pub fn repair_level(&self, repair: &LevelRepair, action: LevelRepairAction) {
+        match action {
+            LevelRepairAction::ResetStreamingStates => {
+                repair.reset_streaming_states(&self.streaming_states)
+            }
+            LevelRepairAction::ClearStaleKismetRecords => {
+                repair.clear_stale_kismet_records(&self.kismet_records)
+            }
+            LevelRepairAction::ResetDoors => repair.reset_doors(&self.doors),
+        }
+    }
 }
 
 #[rcize_fields]
@@ -147,9 +198,18 @@ pub enum Difficulty {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", name)]
-struct DependentDlc {
-    id: i32,
-    name: String,
+pub struct DependentDlc {
+    pub id: i32,
+    pub name: String,
+}
+
+impl DependentDlc {
+    pub fn new(id: i32, dlc: &DbDlc) -> Self {
+        let dependent_dlc = DependentDlc::default();
+        dependent_dlc.id.set(id);
+        *dependent_dlc.name.borrow_mut() = dlc.name.clone();
+        dependent_dlc
+    }
 }
 
 #[cfg(test)]
@@ -256,4 +316,20 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn deserialize_serialize_legendary_ps4() -> Result<()> {
+        let input = fs::read("test/ME2LeSave.ps4sav")?;
+
+        // Deserialize
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Serialize (no checksum on PS4)
+        let output = unreal::Serializer::to_vec(&me2_save_game)?;
+
+        // Check serialized = input
+        assert!(input == output);
+
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 mod galaxy_map;
 pub mod player;
+pub mod player_variable_db;
 pub mod plot;
 pub mod plot_db;
 mod squad;
@@ -11,8 +12,11 @@ use indexmap::IndexMap;
 use serde::{de, Deserialize, Deserializer, Serialize};
 
 use super::shared::{
-    plot::PlotTable as Me1PlotTable, Door, EndGameState, Kismet, Level, Rotator, SaveTimeStamp,
-    StreamingState, Vector,
+    dlc_db::DbDlc,
+    level_repair::{LevelRepair, LevelRepairAction, LevelRepairDb},
+    plot::PlotTable as Me1PlotTable,
+    teleport::TeleportPoint,
+    Door, EndGameState, Kismet, Level, Rotator, SaveTimeStamp, StreamingState, Vector,
 };
 use super::Guid;
 
@@ -33,10 +37,10 @@ pub struct Me3SaveGame {
     current_loading_tip: i32,
     levels: Vec<Level>,
     streaming_states: Vec<StreamingState>,
-    kismet_records: Vec<Kismet>,
-    doors: Vec<Door>,
-    placeables: Vec<Placeable>,
-    pawns: Vec<Guid>,
+    pub kismet_records: Vec<Kismet>,
+    pub doors: Vec<Door>,
+    pub placeables: Vec<Placeable>,
+    pub pawns: Vec<Guid>,
     pub player: Player,
     squad: Vec<Henchman>,
     pub plot: PlotTable,
@@ -45,14 +49,45 @@ pub struct Me3SaveGame {
     _me1_plot: Me1PlotTable,
     pub player_variables: IndexMap<String, i32>,
     galaxy_map: GalaxyMap,
-    dependant_dlcs: Vec<DependentDlc>,
-    treasures: Vec<LevelTreasure>,
-    use_modules: Vec<Guid>,
+    pub dependant_dlcs: Vec<DependentDlc>,
+    pub treasures: Vec<LevelTreasure>,
+    pub use_modules: Vec<Guid>,
     pub conversation_mode: AutoReplyModeOptions,
-    objective_markers: Vec<ObjectiveMarker>,
+    pub objective_markers: Vec<ObjectiveMarker>,
     saved_objective_text: i32,
 }
 
+impl Me3SaveGame {
+    pub fn teleport(&self, point: &TeleportPoint) {
+        *self.base_level_name.borrow_mut() = point.base_level_name.clone();
+        point.set_position(&self.location, &self.rotation);
+        point.set_levels(&self.levels, &self.streaming_states);
+    }
+
+    pub fn level_repair<'a>(&self, level_repair_db: &'a LevelRepairDb) -> Option<&'a LevelRepair> {
+        level_repair_db.find(&self.base_level_name.borrow())
+    }
+
+    pub fn repair_level(&self, repair: &LevelRepair, action: LevelRepairAction) {
+        match action {
+            LevelRepairAction::ResetStreamingStates => {
+                repair.reset_streaming_states(&self.streaming_states)
+            }
+            LevelRepairAction::ClearStaleKismetRecords => {
+                repair.clear_stale_kismet_records(&self.kismet_records)
+            }
+            LevelRepairAction::ResetDoors => repair.reset_doors(&self.doors),
+        }
+    }
+
+    // Le niveau d'un placeable n'est pas connu, ils sont tous remis à zéro
+    pub fn reset_placeables(&self) {
+        for placeable in self.placeables.borrow().iter() {
+            placeable.borrow().reset();
+        }
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me3Version {
     version: i32,
@@ -96,9 +131,21 @@ pub enum Difficulty {
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct Placeable {
-    guid: Guid,
-    is_destroyed: PlaceableState,
-    is_deactivated: PlaceableState,
+    pub guid: Guid,
+    pub is_destroyed: PlaceableState,
+    pub is_deactivated: PlaceableState,
+}
+
+impl Placeable {
+    pub fn is_modified(&self) -> bool {
+        matches!(*self.is_destroyed.borrow(), PlaceableState::Yes)
+            || matches!(*self.is_deactivated.borrow(), PlaceableState::Yes)
+    }
+
+    pub fn reset(&self) {
+        *self.is_destroyed.borrow_mut() = PlaceableState::No;
+        *self.is_deactivated.borrow_mut() = PlaceableState::No;
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone, RawUi)]
@@ -116,20 +163,38 @@ impl Default for PlaceableState {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", name)]
-struct DependentDlc {
-    id: i32,
-    name: String,
-    canonical_name: String,
+pub struct DependentDlc {
+    pub id: i32,
+    pub name: String,
+    pub canonical_name: String,
+}
+
+impl DependentDlc {
+    pub fn new(id: i32, dlc: &DbDlc) -> Self {
+        let dependent_dlc = DependentDlc::default();
+        dependent_dlc.id.set(id);
+        *dependent_dlc.name.borrow_mut() = dlc.name.clone();
+        *dependent_dlc.canonical_name.borrow_mut() = dlc.canonical_name.clone();
+        dependent_dlc
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", level_name)]
-struct LevelTreasure {
-    level_name: String,
-    credits: i32,
-    xp: i32,
-    items: Vec<String>,
+pub struct LevelTreasure {
+    pub level_name: String,
+    pub credits: i32,
+    pub xp: i32,
+    pub items: Vec<String>,
+}
+
+impl LevelTreasure {
+    pub fn clear(&self) {
+        self.credits.set(0);
+        self.xp.set(0);
+        self.items.borrow_mut().clear();
+    }
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -143,16 +208,16 @@ pub enum AutoReplyModeOptions {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
-struct ObjectiveMarker {
-    marker_owned_data: String,
+pub struct ObjectiveMarker {
+    pub marker_owned_data: String,
     marker_offset: Vector,
-    marker_label: i32,
+    pub marker_label: i32,
     bone_to_attach_to: String,
-    marker_icon_type: ObjectiveMarkerIconType,
+    pub marker_icon_type: ObjectiveMarkerIconType,
 }
 
 #[derive(Deserialize, Serialize, Clone, RawUi)]
-enum ObjectiveMarkerIconType {
+pub enum ObjectiveMarkerIconType {
     None,
     Attack,
     Supply,
@@ -204,6 +269,74 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn level_treasure_clear() {
+        let treasure = LevelTreasure::default();
+        *treasure.level_name.borrow_mut() = "BioD_CitHub".to_owned();
+        treasure.credits.set(5000);
+        treasure.xp.set(100);
+        treasure
+            .items
+            .borrow_mut()
+            .push("SFXGameContent.SFXWeapon_Pistol_Carnifex".to_owned().into());
+
+        treasure.clear();
+        assert_eq!(treasure.credits.get(), 0);
+        assert_eq!(treasure.xp.get(), 0);
+        assert!(treasure.items.borrow().is_empty());
+        // Le niveau est gardé pour que le trésor ne réapparaisse pas
+        assert_eq!(*treasure.level_name.borrow(), "BioD_CitHub");
+    }
+
+    #[test]
+    fn placeable_reset() {
+        let placeable = Placeable::default();
+        assert!(!placeable.is_modified());
+
+        *placeable.is_destroyed.borrow_mut() = PlaceableState::Yes;
+        assert!(placeable.is_modified());
+        placeable.reset();
+        assert!(!placeable.is_modified());
+
+        *placeable.is_deactivated.borrow_mut() = PlaceableState::Yes;
+        assert!(placeable.is_modified());
+        placeable.reset();
+        assert!(matches!(*placeable.is_destroyed.borrow(), PlaceableState::No));
+        assert!(matches!(*placeable.is_deactivated.borrow(), PlaceableState::No));
+    }
+
+    #[test]
+    fn world_objects() -> Result<()> {
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Trésor vidé
+        {
+            let treasures = me3_save_game.treasures.borrow();
+            let treasure = treasures[0].borrow();
+            treasure.clear();
+            assert_eq!(treasure.credits.get(), 0);
+            assert_eq!(treasure.xp.get(), 0);
+            assert!(treasure.items.borrow().is_empty());
+        }
+
+        // Placeable détruit et désactivé remis à zéro
+        let placeable = Placeable::default();
+        *placeable.is_destroyed.borrow_mut() = PlaceableState::Yes;
+        *placeable.is_deactivated.borrow_mut() = PlaceableState::Yes;
+        assert!(placeable.is_modified());
+        me3_save_game.placeables.borrow_mut().push(placeable.into());
+
+        me3_save_game.reset_placeables();
+        let placeables = me3_save_game.placeables.borrow();
+        assert!(placeables.iter().all(|placeable| !placeable.borrow().is_modified()));
+
+        // La sauvegarde reste sérialisable
+        let _ = unreal::Serializer::to_vec(&me3_save_game)?;
+
+        Ok(())
+    }
+
     #[test]
     fn deserialize_serialize_xbox360() -> Result<()> {
         let input_pc = fs::read("test/ME3Save.pcsav")?;
@@ -240,4 +373,20 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn deserialize_serialize_legendary_ps4() -> Result<()> {
+        let input = fs::read("test/ME3LeSave.ps4sav")?;
+
+        // Deserialize
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Serialize (no checksum on PS4)
+        let output = unreal::Serializer::to_vec(&me3_save_game)?;
+
+        // Check serialized = input
+        assert!(input == output);
+
+        Ok(())
+    }
 }
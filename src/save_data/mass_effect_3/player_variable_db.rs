@@ -0,0 +1,81 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct DbVariableGroup {
+    pub min: i32,
+    pub max: i32,
+    pub variables: IndexMap<String, String>,
+}
+
+impl DbVariableGroup {
+    pub fn clamp(&self, value: i32) -> i32 {
+        value.clamp(self.min, self.max)
+    }
+}
+
+#[derive(Deserialize, Deref)]
+pub struct Me3PlayerVariableDb(IndexMap<String, DbVariableGroup>);
+
+impl Me3PlayerVariableDb {
+    // Les noms des variables ne sont pas sensibles à la casse
+    pub fn contains_variable(&self, key: &str) -> bool {
+        self.values()
+            .flat_map(|group| group.variables.keys())
+            .any(|db_key| db_key.eq_ignore_ascii_case(key))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    #[test]
+    fn deserialize_player_variable_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_player_variable_db.ron")?;
+        let _me3_player_variable_db: Me3PlayerVariableDb = ron::from_str(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn clamp_to_group_range() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_player_variable_db.ron")?;
+        let player_variable_db: Me3PlayerVariableDb = ron::from_str(&input)?;
+
+        for group in player_variable_db.values() {
+            assert_eq!(group.clamp(group.min - 1), group.min);
+            assert_eq!(group.clamp(group.max + 1), group.max);
+            assert_eq!(group.clamp(group.min), group.min);
+            assert_eq!(group.clamp(group.max), group.max);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn known_weapon_variables() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_player_variable_db.ron")?;
+        let player_variable_db: Me3PlayerVariableDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Les armes du multijoueur ne sont pas dans la base de données
+        let player_variables = me3_save_game.player_variables();
+        let unknown_weapons = player_variables.keys().filter(|key| {
+            key.to_lowercase().contains(".sfxweapon")
+                && !key.contains("_MP")
+                && !key.ends_with(".Flags")
+                && !player_variable_db.contains_variable(key)
+        });
+        assert_eq!(unknown_weapons.count(), 0);
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ pub mod mass_effect_1_le;
 pub mod mass_effect_2;
 pub mod mass_effect_3;
 pub mod shared;
+pub mod stfs;
 
 use std::{
     cell::{Cell, Ref, RefCell, RefMut},
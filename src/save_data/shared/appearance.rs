@@ -1,8 +1,14 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use indexmap::IndexMap;
 use serde::{ser::SerializeTupleStruct, Deserialize, Deserializer, Serialize, Serializer};
 
-use super::Vector;
+use super::{head_morph_conversion::HeadMorphGame, Vector};
+use crate::{save_data::RcCell, unreal};
+
+const GIBBED_ME2_MAGIC: &[u8] = b"GIBBEDMASSEFFECT2HEADMORPH";
+const GIBBED_ME3_MAGIC: &[u8] = b"GIBBEDMASSEFFECT3HEADMORPH";
+// Signature, un octet nul et la version de la sauvegarde
+const GIBBED_HEADER_LEN: usize = 31;
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
@@ -30,6 +36,91 @@ enum PlayerAppearanceType {
     Full,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AppearancePiece {
+    Casual,
+    FullBody,
+    Torso,
+    Shoulder,
+    Arm,
+    Leg,
+    Helmet,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AppearanceTint {
+    Tint1,
+    Tint2,
+    Tint3,
+    PatternColor,
+}
+
+impl Appearance {
+    pub fn is_full_body(&self) -> bool {
+        matches!(*self.combat_appearance.borrow(), PlayerAppearanceType::Full)
+    }
+
+    pub fn set_full_body(&self, is_full_body: bool) {
+        *self.combat_appearance.borrow_mut() =
+            if is_full_body { PlayerAppearanceType::Full } else { PlayerAppearanceType::Parts };
+    }
+
+    fn piece(&self, piece: AppearancePiece) -> &RcCell<i32> {
+        match piece {
+            AppearancePiece::Casual => &self.casual_id,
+            AppearancePiece::FullBody => &self.full_body_id,
+            AppearancePiece::Torso => &self.torso_id,
+            AppearancePiece::Shoulder => &self.shoulder_id,
+            AppearancePiece::Arm => &self.arm_id,
+            AppearancePiece::Leg => &self.leg_id,
+            AppearancePiece::Helmet => &self.helmet_id,
+        }
+    }
+
+    pub fn piece_id(&self, piece: AppearancePiece) -> i32 {
+        self.piece(piece).get()
+    }
+
+    // Choisir une armure complète ou une pièce d'armure change le type d'apparence en combat,
+    // sinon le jeu garde l'ancienne armure
+    pub fn set_piece_id(&self, piece: AppearancePiece, id: i32) {
+        self.piece(piece).set(id);
+        match piece {
+            AppearancePiece::FullBody => self.set_full_body(true),
+            AppearancePiece::Torso
+            | AppearancePiece::Shoulder
+            | AppearancePiece::Arm
+            | AppearancePiece::Leg => self.set_full_body(false),
+            AppearancePiece::Casual | AppearancePiece::Helmet => {}
+        }
+    }
+
+    fn tint(&self, tint: AppearanceTint) -> &RcCell<i32> {
+        match tint {
+            AppearanceTint::Tint1 => &self.tint1_id,
+            AppearanceTint::Tint2 => &self.tint2_id,
+            AppearanceTint::Tint3 => &self.tint3_id,
+            AppearanceTint::PatternColor => &self.pattern_color_id,
+        }
+    }
+
+    pub fn tint_id(&self, tint: AppearanceTint) -> i32 {
+        self.tint(tint).get()
+    }
+
+    pub fn set_tint_id(&self, tint: AppearanceTint, id: i32) {
+        self.tint(tint).set(id);
+    }
+
+    pub fn pattern_id(&self) -> i32 {
+        self.pattern_id.get()
+    }
+
+    pub fn set_pattern_id(&self, id: i32) {
+        self.pattern_id.set(id);
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi, RawUiChildren)]
 pub struct HeadMorph {
@@ -46,6 +137,33 @@ pub struct HeadMorph {
     pub texture_parameters: IndexMap<String, String>,
 }
 
+impl HeadMorph {
+    pub fn is_gibbed(input: &[u8]) -> bool {
+        input.starts_with(GIBBED_ME2_MAGIC) || input.starts_with(GIBBED_ME3_MAGIC)
+    }
+
+    pub fn from_gibbed(input: &[u8]) -> Result<Self> {
+        if !Self::is_gibbed(input) || input.len() < GIBBED_HEADER_LEN {
+            bail!("Not a Gibbed's head morph");
+        }
+        Ok(unreal::Deserializer::from_bytes(&input[GIBBED_HEADER_LEN..])?)
+    }
+
+    pub fn to_gibbed(&self, game: HeadMorphGame) -> Result<Vec<u8>> {
+        let (magic, version) = match game {
+            HeadMorphGame::Me2 => (GIBBED_ME2_MAGIC, 29u32),
+            HeadMorphGame::Me3 => (GIBBED_ME3_MAGIC, 59u32),
+            HeadMorphGame::Me1 => bail!("Gibbed's format does not support Mass Effect 1"),
+        };
+
+        let mut output = magic.to_vec();
+        output.push(0);
+        output.extend(&u32::to_le_bytes(version));
+        output.extend(unreal::Serializer::to_vec(self)?);
+        Ok(output)
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct LinearColor {
     pub r: f32,
@@ -88,7 +206,10 @@ mod test {
     use anyhow::Result;
 
     use super::*;
-    use crate::unreal;
+    use crate::{
+        save_data::{mass_effect_2::Me2LeSaveGame, mass_effect_3::Me3SaveGame},
+        unreal,
+    };
 
     #[test]
     fn gibbed_head_morph() -> Result<()> {
@@ -101,4 +222,54 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn export_gibbed_head_morph() -> Result<()> {
+        let files = [
+            ("test/GibbedME2.me2headmorph", HeadMorphGame::Me2),
+            ("test/GibbedME3.me3headmorph", HeadMorphGame::Me3),
+        ];
+
+        for (file, game) in files {
+            let input = fs::read(file)?;
+            let head_morph = HeadMorph::from_gibbed(&input)?;
+
+            // Check serialized = input
+            let output = head_morph.to_gibbed(game)?;
+            assert!(input == output);
+        }
+
+        assert!(HeadMorph::from_gibbed(b"(hair_mesh: \"\")").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn appearance_pieces() -> Result<()> {
+        // Armure par pièces
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let appearance = me2.player.borrow().appearance.borrow().clone();
+        assert!(!appearance.is_full_body());
+
+        appearance.set_piece_id(AppearancePiece::Helmet, 3);
+        assert!(!appearance.is_full_body());
+        appearance.set_piece_id(AppearancePiece::FullBody, 12);
+        assert!(appearance.is_full_body());
+        assert_eq!(appearance.piece_id(AppearancePiece::FullBody), 12);
+
+        // Armure complète
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let appearance = me3.player.borrow().appearance.borrow().clone();
+        assert!(appearance.is_full_body());
+
+        appearance.set_piece_id(AppearancePiece::Torso, 5);
+        assert!(!appearance.is_full_body());
+        assert_eq!(appearance.piece_id(AppearancePiece::Torso), 5);
+
+        appearance.set_tint_id(AppearanceTint::PatternColor, 4);
+        assert_eq!(appearance.tint_id(AppearanceTint::PatternColor), 4);
+
+        Ok(())
+    }
 }
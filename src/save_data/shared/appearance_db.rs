@@ -0,0 +1,149 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::appearance::AppearancePiece;
+
+#[derive(Deserialize)]
+pub struct DbGenderAppearance {
+    pub casual: IndexMap<i32, String>,
+    pub full_body: IndexMap<i32, String>,
+    pub torso: IndexMap<i32, String>,
+    pub shoulder: IndexMap<i32, String>,
+    pub arm: IndexMap<i32, String>,
+    pub leg: IndexMap<i32, String>,
+    pub helmet: IndexMap<i32, String>,
+}
+
+impl DbGenderAppearance {
+    pub fn pieces(&self, piece: AppearancePiece) -> &IndexMap<i32, String> {
+        match piece {
+            AppearancePiece::Casual => &self.casual,
+            AppearancePiece::FullBody => &self.full_body,
+            AppearancePiece::Torso => &self.torso,
+            AppearancePiece::Shoulder => &self.shoulder,
+            AppearancePiece::Arm => &self.arm,
+            AppearancePiece::Leg => &self.leg,
+            AppearancePiece::Helmet => &self.helmet,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DbTint {
+    pub name: String,
+    // `#rrggbb`, absente tant que la couleur en jeu n'est pas connue
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AppearanceDb {
+    pub female: DbGenderAppearance,
+    pub male: DbGenderAppearance,
+    // Les couleurs du motif utilisent la même table que les teintes
+    pub tints: IndexMap<i32, DbTint>,
+    pub patterns: IndexMap<i32, String>,
+}
+
+impl AppearanceDb {
+    pub fn gender(&self, is_female: bool) -> &DbGenderAppearance {
+        if is_female {
+            &self.female
+        } else {
+            &self.male
+        }
+    }
+}
+
+// Noms pour une liste déroulante et IDs correspondants,
+// l'ID actuel est ajouté s'il n'est pas dans la base de données
+pub fn options(names: &IndexMap<i32, String>, current_id: i32) -> (Vec<String>, Vec<i32>) {
+    let mut options: Vec<(String, i32)> =
+        names.iter().map(|(&id, name)| (name.clone(), id)).collect();
+    if !names.contains_key(&current_id) {
+        options.insert(0, (format!("Unknown ({})", current_id), current_id));
+    }
+    options.into_iter().unzip()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_2::{Me2LeSaveGame, Me2SaveGame},
+            mass_effect_3::Me3SaveGame,
+            shared::appearance::{Appearance, AppearanceTint},
+        },
+        unreal,
+    };
+
+    #[test]
+    fn deserialize_appearance_db() -> Result<()> {
+        for file in ["databases/me2_appearance_db.ron", "databases/me3_appearance_db.ron"] {
+            let input = fs::read_to_string(file)?;
+            let _appearance_db: AppearanceDb = ron::from_str(&input)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn appearance_options() -> Result<()> {
+        let names: IndexMap<i32, String> =
+            [(1, "N7".to_owned()), (2, "Kestrel".to_owned())].into_iter().collect();
+        let (labels, ids) = options(&names, 2);
+        assert_eq!(labels, vec!["N7".to_owned(), "Kestrel".to_owned()]);
+        assert_eq!(ids, vec![1, 2]);
+
+        let (labels, ids) = options(&names, 7);
+        assert_eq!(labels[0], "Unknown (7)");
+        assert_eq!(ids, vec![7, 1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn fixture_ids_are_named() -> Result<()> {
+        fn check(appearance_db: &AppearanceDb, appearance: &Appearance, is_female: bool) {
+            let gender = appearance_db.gender(is_female);
+            for piece in [
+                AppearancePiece::Casual,
+                AppearancePiece::FullBody,
+                AppearancePiece::Torso,
+                AppearancePiece::Shoulder,
+                AppearancePiece::Arm,
+                AppearancePiece::Leg,
+                AppearancePiece::Helmet,
+            ] {
+                assert!(gender.pieces(piece).contains_key(&appearance.piece_id(piece)));
+            }
+            for tint in [
+                AppearanceTint::Tint1,
+                AppearanceTint::Tint2,
+                AppearanceTint::Tint3,
+                AppearanceTint::PatternColor,
+            ] {
+                assert!(appearance_db.tints.contains_key(&appearance.tint_id(tint)));
+            }
+            assert!(appearance_db.patterns.contains_key(&appearance.pattern_id()));
+        }
+
+        let me2_db: AppearanceDb =
+            ron::from_str(&fs::read_to_string("databases/me2_appearance_db.ron")?)?;
+        let me2: Me2SaveGame = unreal::Deserializer::from_bytes(&fs::read("test/ME2Save.pcsav")?)?;
+        check(&me2_db, &me2.player.borrow().appearance.borrow(), me2.player().is_female());
+        let me2_le: Me2LeSaveGame =
+            unreal::Deserializer::from_bytes(&fs::read("test/ME2LeSave.pcsav")?)?;
+        check(&me2_db, &me2_le.player.borrow().appearance.borrow(), me2_le.player().is_female());
+
+        let me3_db: AppearanceDb =
+            ron::from_str(&fs::read_to_string("databases/me3_appearance_db.ron")?)?;
+        let me3: Me3SaveGame = unreal::Deserializer::from_bytes(&fs::read("test/ME3Save.pcsav")?)?;
+        check(&me3_db, &me3.player.borrow().appearance.borrow(), me3.player().is_female());
+
+        Ok(())
+    }
+}
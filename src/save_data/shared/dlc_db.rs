@@ -0,0 +1,88 @@
+use anyhow::{ensure, Result};
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::plot::BitVec;
+
+#[derive(Deserialize)]
+pub struct DbDlc {
+    pub name: String,
+    // ME3 uniquement
+    #[serde(default)]
+    pub canonical_name: String,
+    // Plots mis à jour par le DLC, à vérifier avant de retirer la dépendance
+    #[serde(default)]
+    pub plots: IndexMap<usize, String>,
+}
+
+impl DbDlc {
+    pub fn plots_set<'a>(&'a self, booleans: &'a BitVec) -> impl Iterator<Item = &'a String> {
+        self.plots
+            .iter()
+            .filter(move |(&idx, _)| booleans.get(idx).map(|plot| *plot).unwrap_or(false))
+            .map(|(_, label)| label)
+    }
+}
+
+#[derive(Deserialize, Deref)]
+pub struct DlcDb(IndexMap<i32, DbDlc>);
+
+// Un DLC ajouté à la main doit avoir un ID et ne pas être déjà requis par la save
+pub fn check_custom_dlc_id(id: i32, required_ids: &[i32]) -> Result<()> {
+    ensure!(id != 0, "The DLC ID cannot be 0");
+    ensure!(!required_ids.contains(&id), "The DLC {} is already required by the save", id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::{
+        save_data::{mass_effect_2::Me2LeSaveGame, mass_effect_3::Me3SaveGame},
+        unreal,
+    };
+
+    #[test]
+    fn deserialize_dlc_db() -> Result<()> {
+        let files = ["databases/me2_dlc_db.ron", "databases/me3_dlc_db.ron"];
+
+        for file in files {
+            let input = fs::read_to_string(file)?;
+            let _dlc_db: DlcDb = ron::from_str(&input)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn custom_dlc_id() {
+        let required_ids = [95, 100];
+        assert!(check_custom_dlc_id(0, &required_ids).is_err());
+        assert!(check_custom_dlc_id(100, &required_ids).is_err());
+        assert!(check_custom_dlc_id(1234, &required_ids).is_ok());
+    }
+
+    #[test]
+    fn known_dependent_dlcs() -> Result<()> {
+        let input = fs::read_to_string("databases/me2_dlc_db.ron")?;
+        let me2_dlc_db: DlcDb = ron::from_str(&input)?;
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2_save_game: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        for dlc in me2_save_game.dependant_dlcs().iter() {
+            assert!(me2_dlc_db.contains_key(&dlc.borrow().id()));
+        }
+
+        let input = fs::read_to_string("databases/me3_dlc_db.ron")?;
+        let me3_dlc_db: DlcDb = ron::from_str(&input)?;
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        for dlc in me3_save_game.dependant_dlcs().iter() {
+            let dlc = dlc.borrow();
+            let db_dlc = me3_dlc_db.get(&dlc.id()).unwrap();
+            assert_eq!(*dlc.canonical_name(), db_dlc.canonical_name);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+// Ni 0 ni O, pour éviter les confusions à la saisie
+const ALPHABET: &str = "123456789ABCDEFGHIJKLMNPQRSTUVWXYZ";
+const SEPARATOR: char = '.';
+const GROUP_LEN: usize = 3;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FaceCodeGame {
+    Me1,
+    Me2,
+    Me3,
+}
+
+impl FaceCodeGame {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FaceCodeGame::Me1 => "Mass Effect",
+            FaceCodeGame::Me2 => "Mass Effect 2",
+            FaceCodeGame::Me3 => "Mass Effect 3",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DbSlider {
+    pub name: String,
+    pub values: usize,
+    // Les curseurs continus sont mis à l'échelle d'un jeu à l'autre,
+    // les listes (coiffures, couleurs, ...) gardent le même index
+    #[serde(default)]
+    pub continuous: bool,
+}
+
+#[derive(Deserialize)]
+pub struct FaceCodeLayout {
+    pub male: Vec<DbSlider>,
+    pub female: Vec<DbSlider>,
+}
+
+impl FaceCodeLayout {
+    pub fn sliders(&self, is_female: bool) -> &[DbSlider] {
+        if is_female {
+            &self.female
+        } else {
+            &self.male
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FaceSlider {
+    pub name: String,
+    // De 1 à `values`, comme dans le créateur de personnage
+    pub value: usize,
+    pub values: usize,
+}
+
+// Décode un code en valeurs de curseurs, les séparateurs sont ignorés
+pub fn decode(face_code: &str, sliders: &[DbSlider]) -> Result<Vec<FaceSlider>> {
+    let chars: Vec<char> =
+        face_code.chars().filter(|c| *c != SEPARATOR).map(|c| c.to_ascii_uppercase()).collect();
+
+    if chars.len() != sliders.len() {
+        bail!("Expected {} characters, found {}", sliders.len(), chars.len());
+    }
+
+    chars
+        .into_iter()
+        .zip(sliders)
+        .enumerate()
+        .map(|(idx, (c, slider))| {
+            let value = match ALPHABET.find(c) {
+                Some(value) => value + 1,
+                None => bail!("Invalid character `{}` at position {}", c, idx + 1),
+            };
+            if value > slider.values {
+                bail!("{}: {} is out of range (1 - {})", slider.name, value, slider.values);
+            }
+            Ok(FaceSlider { name: slider.name.clone(), value, values: slider.values })
+        })
+        .collect()
+}
+
+pub fn encode(sliders: &[FaceSlider]) -> String {
+    let chars: Vec<char> = sliders
+        .iter()
+        .map(|slider| {
+            let value = slider.value.clamp(1, ALPHABET.len());
+            ALPHABET.as_bytes()[value - 1] as char
+        })
+        .collect();
+
+    chars
+        .chunks(GROUP_LEN)
+        .map(|group| group.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(&SEPARATOR.to_string())
+}
+
+// Curseurs retrouvés par leur nom, ceux absents du jeu source prennent la première valeur
+pub fn translate(sliders: &[FaceSlider], target: &[DbSlider]) -> Vec<FaceSlider> {
+    target
+        .iter()
+        .map(|db_slider| {
+            let value = sliders
+                .iter()
+                .find(|slider| slider.name == db_slider.name)
+                .map(|slider| {
+                    if db_slider.continuous && slider.values > 1 && db_slider.values > 1 {
+                        let ratio = (slider.value - 1) as f32 / (slider.values - 1) as f32;
+                        (ratio * (db_slider.values - 1) as f32).round() as usize + 1
+                    } else {
+                        slider.value.min(db_slider.values)
+                    }
+                })
+                .unwrap_or(1);
+
+            FaceSlider { name: db_slider.name.clone(), value, values: db_slider.values }
+        })
+        .collect()
+}
+
+#[derive(Deserialize, Deref)]
+pub struct FaceCodeDb(IndexMap<FaceCodeGame, FaceCodeLayout>);
+
+impl FaceCodeDb {
+    pub fn decode(
+        &self, face_code: &str, game: FaceCodeGame, is_female: bool,
+    ) -> Result<Vec<FaceSlider>> {
+        match self.get(&game) {
+            Some(layout) => decode(face_code, layout.sliders(is_female)),
+            None => bail!("No face code layout for {}", game.name()),
+        }
+    }
+
+    pub fn translate(
+        &self, face_code: &str, from: FaceCodeGame, to: FaceCodeGame, is_female: bool,
+    ) -> Result<String> {
+        let sliders = self.decode(face_code, from, is_female)?;
+        match self.get(&to) {
+            Some(layout) => Ok(encode(&translate(&sliders, layout.sliders(is_female)))),
+            None => bail!("No face code layout for {}", to.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    fn face_code_db() -> Result<FaceCodeDb> {
+        let input = fs::read_to_string("databases/face_code_db.ron")?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn decode_encode() -> Result<()> {
+        let face_code_db = face_code_db()?;
+
+        // Codes des sauvegardes de test (Shepard femme)
+        let codes = [
+            (FaceCodeGame::Me1, "7H3.8GD.I13.D2G.EG1.17E.4AG.611.C31.1AB.235.21G.1"),
+            (FaceCodeGame::Me3, "743.8GD.I13.D2G.EG1.17E.4AG.611.C31.1A7.2G6.212"),
+        ];
+
+        for (game, code) in codes {
+            let sliders = face_code_db.decode(code, game, true)?;
+            assert_eq!(encode(&sliders), code);
+
+            // Casse et séparateurs ignorés
+            let sliders_2 =
+                face_code_db.decode(&code.replace('.', "").to_lowercase(), game, true)?;
+            assert_eq!(sliders, sliders_2);
+        }
+
+        // Texte libre
+        assert!(face_code_db.decode("Karlitos", FaceCodeGame::Me2, true).is_err());
+        // Caractère invalide
+        let code = "0H3.8GD.I13.D2G.EG1.17E.4AG.611.C31.1AB.235.21G.1";
+        assert!(face_code_db.decode(code, FaceCodeGame::Me1, true).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn translate_face_code() -> Result<()> {
+        let face_code_db = face_code_db()?;
+        let code = "7H3.8GD.I13.D2G.EG1.17E.4AG.611.C31.1AB.235.21G.1";
+
+        // ME1 et ME2 partagent le même créateur de personnage
+        let me2 = face_code_db.translate(code, FaceCodeGame::Me1, FaceCodeGame::Me2, true)?;
+        assert_eq!(me2, code);
+
+        let me3 = face_code_db.translate(code, FaceCodeGame::Me1, FaceCodeGame::Me3, true)?;
+        let me3_sliders = face_code_db.decode(&me3, FaceCodeGame::Me3, true)?;
+        let me1_sliders = face_code_db.decode(code, FaceCodeGame::Me1, true)?;
+        for slider in &me3_sliders {
+            if let Some(me1_slider) = me1_sliders.iter().find(|me1| me1.name == slider.name) {
+                if me1_slider.values == slider.values {
+                    assert_eq!(me1_slider.value, slider.value);
+                }
+            }
+        }
+
+        // Mise à l'échelle d'un curseur continu
+        let sliders = [FaceSlider { name: "Nose Height".into(), value: 16, values: 31 }];
+        let target = [DbSlider { name: "Nose Height".into(), values: 11, continuous: true }];
+        assert_eq!(translate(&sliders, &target)[0].value, 6);
+
+        Ok(())
+    }
+}
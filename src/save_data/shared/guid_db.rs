@@ -0,0 +1,90 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::save_data::Guid;
+
+#[derive(Deserialize)]
+pub struct DbGuid {
+    pub level: String,
+    pub description: String,
+}
+
+impl DbGuid {
+    pub fn label(&self) -> String {
+        format!("{} - {}", self.level, self.description)
+    }
+}
+
+// Guid au format "xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx" => objet
+#[derive(Deserialize, Deref)]
+pub struct GuidDb(IndexMap<String, DbGuid>);
+
+impl GuidDb {
+    pub fn find(&self, guid: &Guid) -> Option<&DbGuid> {
+        let guid = guid.hyphenated();
+        self.iter()
+            .find_map(|(db_guid, object)| db_guid.eq_ignore_ascii_case(&guid).then(|| object))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        save_data::{mass_effect_2::Me2SaveGame, mass_effect_3::Me3SaveGame},
+        unreal,
+    };
+
+    #[test]
+    fn deserialize_guid_db() -> Result<()> {
+        let files = ["databases/me2_guid_db.ron", "databases/me3_guid_db.ron"];
+
+        for file in files {
+            let input = fs::read_to_string(file)?;
+            let _guid_db: GuidDb = ron::from_str(&input)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn db_doors_in_saves() -> Result<()> {
+        let me2_save_game: Me2SaveGame =
+            unreal::Deserializer::from_bytes(&fs::read("test/ME2Save.pcsav")?)?;
+        let me3_save_game: Me3SaveGame =
+            unreal::Deserializer::from_bytes(&fs::read("test/ME3Save.pcsav")?)?;
+        let files = [
+            ("databases/me2_guid_db.ron", me2_save_game.doors()),
+            ("databases/me3_guid_db.ron", me3_save_game.doors()),
+        ];
+
+        for (file, doors) in files {
+            let guid_db: GuidDb = ron::from_str(&fs::read_to_string(file)?)?;
+            assert!(!guid_db.is_empty());
+
+            // Chaque porte de la base est une vraie porte de la save
+            let found = doors.iter().filter(|door| guid_db.find(&door.borrow().guid()).is_some());
+            assert_eq!(found.count(), guid_db.len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn find_guid() -> Result<()> {
+        let input = r#"GuidDb({
+            "B95F415E-BABA-428A-9558-57C5AC4834BC": (level: "BioD_OmgHub", description: "Door"),
+        })"#;
+        let guid_db: GuidDb = ron::from_str(input)?;
+
+        let guid = Guid::from(Uuid::parse_str("b95f415e-baba-428a-9558-57c5ac4834bc")?);
+        let object = guid_db.find(&guid).unwrap();
+        assert_eq!(object.label(), "BioD_OmgHub - Door");
+
+        assert!(guid_db.find(&Guid::default()).is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use super::appearance::HeadMorph;
+
+// Les versions Legendary utilisent les mêmes modèles que les originales
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum HeadMorphGame {
+    Me1,
+    Me2,
+    Me3,
+}
+
+impl HeadMorphGame {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HeadMorphGame::Me1 => "Mass Effect",
+            HeadMorphGame::Me2 => "Mass Effect 2",
+            HeadMorphGame::Me3 => "Mass Effect 3",
+        }
+    }
+}
+
+// Une coiffure et les textures qui vont avec, les textures du scalp en font partie
+#[derive(Deserialize)]
+pub struct DbHair {
+    pub mesh: String,
+    // Paramètre de texture => texture
+    #[serde(default)]
+    pub textures: IndexMap<String, String>,
+}
+
+#[derive(Deserialize)]
+pub struct DbHeadMorphConversion {
+    pub from: HeadMorphGame,
+    pub to: HeadMorphGame,
+    // Coiffures
+    #[serde(default)]
+    pub hairs: IndexMap<String, DbHair>,
+    // Accessoires
+    #[serde(default)]
+    pub meshes: IndexMap<String, String>,
+    // Noms des paramètres de texture
+    #[serde(default)]
+    pub texture_parameters: IndexMap<String, String>,
+    // Textures référencées par les paramètres
+    #[serde(default)]
+    pub textures: IndexMap<String, String>,
+}
+
+// Les noms de packages changent de casse d'un jeu à l'autre
+fn remap(map: &IndexMap<String, String>, value: &str) -> Option<String> {
+    map.iter().find_map(|(from, to)| from.eq_ignore_ascii_case(value).then(|| to.clone()))
+}
+
+impl DbHeadMorphConversion {
+    fn apply(&self, head_morph: &HeadMorph) {
+        let hair = self.hairs.iter().find_map(|(from, hair)| {
+            from.eq_ignore_ascii_case(&head_morph.hair_mesh.borrow()).then(|| hair)
+        });
+
+        for accessory in head_morph.accessory_mesh.borrow().iter() {
+            let mesh = remap(&self.meshes, &accessory.borrow());
+            if let Some(mesh) = mesh {
+                *accessory.borrow_mut() = mesh;
+            }
+        }
+
+        let mut texture_parameters = head_morph.texture_parameters.borrow_mut();
+        *texture_parameters = texture_parameters
+            .drain(..)
+            .map(|(name, texture)| {
+                let name = remap(&self.texture_parameters, &name).unwrap_or(name);
+                let new_texture = remap(&self.textures, &texture.borrow());
+                if let Some(new_texture) = new_texture {
+                    *texture.borrow_mut() = new_texture;
+                }
+                (name, texture)
+            })
+            .collect();
+
+        // Les textures de la coiffure passent après les renommages
+        if let Some(hair) = hair {
+            *head_morph.hair_mesh.borrow_mut() = hair.mesh.clone();
+            for (name, texture) in &hair.textures {
+                match texture_parameters.get(name) {
+                    Some(parameter) => *parameter.borrow_mut() = texture.clone(),
+                    None => {
+                        texture_parameters.insert(name.clone(), texture.clone().into());
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Deref)]
+pub struct HeadMorphConversionDb(Vec<DbHeadMorphConversion>);
+
+impl HeadMorphConversionDb {
+    // `reference` est le head morph actuel de la sauvegarde cible, s'il existe,
+    // le jeu ne supporte pas un nombre de sommets différent de celui de son modèle
+    pub fn convert(
+        &self, head_morph: &HeadMorph, from: HeadMorphGame, to: HeadMorphGame,
+        reference: Option<&HeadMorph>,
+    ) -> Result<()> {
+        if let Some(reference) = reference {
+            let lods = [
+                (&head_morph.lod0_vertices, &reference.lod0_vertices),
+                (&head_morph.lod1_vertices, &reference.lod1_vertices),
+                (&head_morph.lod2_vertices, &reference.lod2_vertices),
+                (&head_morph.lod3_vertices, &reference.lod3_vertices),
+            ];
+            for (lod, (vertices, reference)) in lods.iter().enumerate() {
+                let (len, expected) = (vertices.borrow().len(), reference.borrow().len());
+                if len != expected {
+                    bail!(
+                        "LOD{} has {} vertices, {} expected. This head morph is not compatible with {}",
+                        lod,
+                        len,
+                        expected,
+                        to.name()
+                    );
+                }
+            }
+        }
+
+        if from != to {
+            match self.iter().find(|conversion| conversion.from == from && conversion.to == to) {
+                Some(conversion) => conversion.apply(head_morph),
+                None => bail!("No conversion from {} to {}", from.name(), to.name()),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{mass_effect_1_le::Me1LeSaveGame, mass_effect_3::Me3SaveGame},
+        unreal,
+    };
+
+    fn conversion_db() -> Result<HeadMorphConversionDb> {
+        let input = fs::read_to_string("databases/head_morph_conversion_db.ron")?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn convert_head_morph() -> Result<()> {
+        let conversion_db = conversion_db()?;
+
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let me1_head_morph = me1.save_data.borrow().player.borrow().head_morph.borrow().clone();
+        let me1_head_morph = me1_head_morph.unwrap();
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let me3_head_morph =
+            me3.player.borrow().appearance.borrow().head_morph.borrow().clone().unwrap();
+
+        // ME1 => ME2, mêmes modèles
+        let head_morph = me1_head_morph.borrow().clone();
+        conversion_db.convert(
+            &head_morph,
+            HeadMorphGame::Me1,
+            HeadMorphGame::Me2,
+            Some(&me1_head_morph.borrow()),
+        )?;
+        assert_eq!(*head_morph.hair_mesh.borrow(), *me1_head_morph.borrow().hair_mesh.borrow());
+
+        // ME1 => ME3, le modèle de ME3 n'a pas le même nombre de sommets
+        let result = conversion_db.convert(
+            &head_morph,
+            HeadMorphGame::Me1,
+            HeadMorphGame::Me3,
+            Some(&me3_head_morph.borrow()),
+        );
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn convert_me1_le_to_me3() -> Result<()> {
+        let conversion_db = conversion_db()?;
+
+        // Même Shepard dans les deux saves
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let head_morph = me1.save_data.borrow().player.borrow().head_morph.borrow().clone();
+        let head_morph = head_morph.unwrap().borrow().clone();
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let me3_head_morph =
+            me3.player.borrow().appearance.borrow().head_morph.borrow().clone().unwrap();
+        let me3_head_morph = me3_head_morph.borrow();
+
+        // Pas de head morph dans la sauvegarde cible
+        conversion_db.convert(&head_morph, HeadMorphGame::Me1, HeadMorphGame::Me3, None)?;
+
+        // Coiffure et textures de la coiffure de ME3
+        assert_eq!(*head_morph.hair_mesh.borrow(), *me3_head_morph.hair_mesh.borrow());
+        let texture_parameters = head_morph.texture_parameters.borrow();
+        let me3_texture_parameters = me3_head_morph.texture_parameters.borrow();
+        for name in [
+            "HED_Scalp_Diff",
+            "HED_Scalp_Norm",
+            "HED_Scalp_Spec",
+            "HED_Tang",
+            "HAIR_Diff",
+            "HAIR_Norm",
+            "HAIR_Mask",
+            "HAIR_Tang",
+            "HAIR_SpecShift",
+            "HAIR_SpecShift2",
+        ] {
+            assert_eq!(
+                *texture_parameters.get(name).unwrap().borrow(),
+                *me3_texture_parameters.get(name).unwrap().borrow()
+            );
+        }
+        // Le reste du visage est gardé
+        assert_eq!(
+            *texture_parameters.get("HED_Scar").unwrap().borrow(),
+            "BIOG_HMF_HED_PROMorph_R.Base.HMF_HED_PROCustom_Blank_Norm"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rename_texture_parameters() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let head_morph = me1.save_data.borrow().player.borrow().head_morph.borrow().clone();
+        let head_morph = head_morph.unwrap().borrow().clone();
+
+        let conversion = DbHeadMorphConversion {
+            from: HeadMorphGame::Me2,
+            to: HeadMorphGame::Me3,
+            hairs: IndexMap::new(),
+            meshes: IndexMap::new(),
+            texture_parameters: [("HED_Scar".to_owned(), "HED_Scar_Renamed".to_owned())]
+                .into_iter()
+                .collect(),
+            textures: [(
+                "biog_hmf_hed_promorph_r.base.hmf_hed_procustom_blank_norm".to_owned(),
+                "BIOG_HMF_HED_PROMorph_R.Scars.HMF_HED_PROCustom_Scr10".to_owned(),
+            )]
+            .into_iter()
+            .collect(),
+        };
+        conversion.apply(&head_morph);
+
+        let texture_parameters = head_morph.texture_parameters.borrow();
+        assert!(!texture_parameters.contains_key("HED_Scar"));
+        assert_eq!(
+            *texture_parameters.get("HED_Scar_Renamed").unwrap().borrow(),
+            "BIOG_HMF_HED_PROMorph_R.Scars.HMF_HED_PROCustom_Scr10"
+        );
+
+        Ok(())
+    }
+}
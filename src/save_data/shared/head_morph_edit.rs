@@ -0,0 +1,342 @@
+use std::cmp::Ordering;
+
+use anyhow::{bail, Result};
+
+use super::{appearance::HeadMorph, Vector};
+use crate::save_data::RcRef;
+
+// Sommets considérés sur l'axe de symétrie
+const CENTER_EPSILON: f32 = 0.01;
+// Sources les plus proches envisagées pour chaque sommet cible
+const MIRROR_CANDIDATES: usize = 8;
+
+// Le visage regarde vers +X, la gauche du personnage est du côté -Y
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HeadMorphSide {
+    Left,
+    Right,
+}
+
+impl HeadMorphSide {
+    fn sign(self) -> f32 {
+        match self {
+            HeadMorphSide::Left => -1.0,
+            HeadMorphSide::Right => 1.0,
+        }
+    }
+}
+
+fn lerp(from: f32, to: f32, ratio: f32) -> f32 {
+    from + (to - from) * ratio
+}
+
+fn lods(head_morph: &HeadMorph) -> [&RcRef<Vec<RcRef<Vector>>>; 4] {
+    [
+        &head_morph.lod0_vertices,
+        &head_morph.lod1_vertices,
+        &head_morph.lod2_vertices,
+        &head_morph.lod3_vertices,
+    ]
+}
+
+fn compare_distance(a: &f32, b: &f32) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+// Paires (cible, source) calculées une seule fois, avant toute modification,
+// une source n'est attribuée qu'à une seule cible en commençant par les paires les plus proches
+fn mirror_pairs(positions: &[(f32, f32, f32)], source: HeadMorphSide) -> Vec<(usize, usize)> {
+    let sign = source.sign();
+    let side = |sign: f32| -> Vec<usize> {
+        (0..positions.len()).filter(|&idx| positions[idx].1 * sign > CENTER_EPSILON).collect()
+    };
+    let (sources, targets) = (side(sign), side(-sign));
+
+    let mut candidates = Vec::new();
+    for &target in &targets {
+        let (x, y, z) = positions[target];
+        let mut nearest: Vec<(f32, usize)> = sources
+            .iter()
+            .map(|&source| {
+                let (sx, sy, sz) = positions[source];
+                ((sx - x).powi(2) + (-sy - y).powi(2) + (sz - z).powi(2), source)
+            })
+            .collect();
+
+        let len = nearest.len().min(MIRROR_CANDIDATES);
+        if len == 0 {
+            break;
+        }
+        nearest.select_nth_unstable_by(len - 1, |a, b| compare_distance(&a.0, &b.0));
+        candidates
+            .extend(nearest[..len].iter().map(|&(distance, source)| (distance, target, source)));
+    }
+    candidates.sort_by(|a, b| compare_distance(&a.0, &b.0));
+
+    let mut paired = vec![false; positions.len()];
+    let mut pairs = Vec::new();
+    for (_, target, source) in candidates {
+        if !paired[target] && !paired[source] {
+            paired[target] = true;
+            paired[source] = true;
+            pairs.push((target, source));
+        }
+    }
+
+    // Cibles dont toutes les sources proches sont déjà prises
+    for &target in &targets {
+        if paired[target] {
+            continue;
+        }
+        let (x, y, z) = positions[target];
+        let nearest = sources
+            .iter()
+            .filter(|&&source| !paired[source])
+            .map(|&source| {
+                let (sx, sy, sz) = positions[source];
+                ((sx - x).powi(2) + (-sy - y).powi(2) + (sz - z).powi(2), source)
+            })
+            .min_by(|a, b| compare_distance(&a.0, &b.0));
+
+        match nearest {
+            Some((_, source)) => {
+                paired[target] = true;
+                paired[source] = true;
+                pairs.push((target, source));
+            }
+            None => break,
+        }
+    }
+    pairs
+}
+
+// Les sommets cibles sans source (côtés de tailles différentes) ne sont pas modifiés
+fn symmetrize_vertices(vertices: &[RcRef<Vector>], source: HeadMorphSide) {
+    let positions: Vec<(f32, f32, f32)> = vertices
+        .iter()
+        .map(|vertex| {
+            let vertex = vertex.borrow();
+            (vertex.x.get(), vertex.y.get(), vertex.z.get())
+        })
+        .collect();
+
+    for (vertex, &(_, y, _)) in vertices.iter().zip(&positions) {
+        if y.abs() <= CENTER_EPSILON {
+            vertex.borrow().y.set(0.0);
+        }
+    }
+
+    for (target, source) in mirror_pairs(&positions, source) {
+        let (x, y, z) = positions[source];
+        let vertex = vertices[target].borrow();
+        vertex.x.set(x);
+        vertex.y.set(-y);
+        vertex.z.set(z);
+    }
+}
+
+impl HeadMorph {
+    // Copie un côté du visage sur l'autre, symétrie par rapport au plan XZ :
+    // X vers l'avant, Y de gauche à droite du personnage, Z vers le haut
+    pub fn symmetrize(&self, source: HeadMorphSide) {
+        for vertices in lods(self) {
+            symmetrize_vertices(&vertices.borrow(), source);
+        }
+
+        // Les noms des os ne suivent pas toujours leur position,
+        // c'est leur côté qui désigne la source d'une paire gauche / droite
+        let sign = source.sign();
+        let offset_bones = self.offset_bones.borrow();
+        for (name, bone) in offset_bones.iter() {
+            let name = name.to_lowercase();
+            if !name.contains("left") {
+                continue;
+            }
+
+            let pair_name = name.replace("left", "right");
+            let pair = offset_bones.iter().find(|(other, _)| other.to_lowercase() == pair_name);
+            if let Some((_, pair)) = pair {
+                let (from, to) = if bone.borrow().y.get() * sign >= pair.borrow().y.get() * sign {
+                    (bone, pair)
+                } else {
+                    (pair, bone)
+                };
+
+                let (from, to) = (from.borrow(), to.borrow());
+                to.x.set(from.x.get());
+                to.y.set(-from.y.get());
+                to.z.set(from.z.get());
+            }
+        }
+    }
+
+    // `ratio` de 0 (ce head morph) à 1 (`other`), les valeurs absentes de l'un des deux
+    // ne sont pas modifiées, sauf les curseurs qui valent 0 par défaut
+    pub fn blend(&self, other: &HeadMorph, ratio: f32) -> Result<()> {
+        for (lod, (vertices, other)) in lods(self).iter().zip(lods(other)).enumerate() {
+            let (len, other_len) = (vertices.borrow().len(), other.borrow().len());
+            if len != other_len {
+                bail!("LOD{} has {} vertices, {} expected", lod, other_len, len);
+            }
+        }
+
+        let blend_vector = |vector: &RcRef<Vector>, other: &RcRef<Vector>| {
+            let (vector, other) = (vector.borrow(), other.borrow());
+            vector.x.set(lerp(vector.x.get(), other.x.get(), ratio));
+            vector.y.set(lerp(vector.y.get(), other.y.get(), ratio));
+            vector.z.set(lerp(vector.z.get(), other.z.get(), ratio));
+        };
+
+        for (vertices, other) in lods(self).iter().zip(lods(other)) {
+            for (vertex, other) in vertices.borrow().iter().zip(other.borrow().iter()) {
+                blend_vector(vertex, other);
+            }
+        }
+
+        for (name, bone) in self.offset_bones.borrow().iter() {
+            if let Some(other) = other.offset_bones.borrow().get(name) {
+                blend_vector(bone, other);
+            }
+        }
+
+        {
+            let mut morph_features = self.morph_features.borrow_mut();
+            for (name, feature) in morph_features.iter() {
+                let other = other.morph_features.borrow().get(name).map(|other| other.get());
+                feature.set(lerp(feature.get(), other.unwrap_or_default(), ratio));
+            }
+            for (name, other) in other.morph_features.borrow().iter() {
+                if !morph_features.contains_key(name) {
+                    morph_features.insert(name.clone(), (other.get() * ratio).into());
+                }
+            }
+        }
+
+        for (name, scalar) in self.scalar_parameters.borrow().iter() {
+            if let Some(other) = other.scalar_parameters.borrow().get(name) {
+                scalar.set(lerp(scalar.get(), other.get(), ratio));
+            }
+        }
+
+        for (name, color) in self.vector_parameters.borrow().iter() {
+            if let Some(other) = other.vector_parameters.borrow().get(name) {
+                let other = other.borrow();
+                let mut color = color.borrow_mut();
+                color.r = lerp(color.r, other.r, ratio);
+                color.g = lerp(color.g, other.g, ratio);
+                color.b = lerp(color.b, other.b, ratio);
+                color.a = lerp(color.a, other.a, ratio);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{mass_effect_2::Me2LeSaveGame, mass_effect_3::Me3SaveGame},
+        unreal,
+    };
+
+    fn me2_head_morph() -> Result<HeadMorph> {
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let head_morph =
+            me2.player.borrow().appearance.borrow().head_morph.borrow().clone().unwrap();
+        let head_morph = head_morph.borrow().clone();
+        Ok(head_morph)
+    }
+
+    fn position(vector: &RcRef<Vector>) -> (f32, f32, f32) {
+        let vector = vector.borrow();
+        (vector.x.get(), vector.y.get(), vector.z.get())
+    }
+
+    #[test]
+    fn symmetrize_head_morph() -> Result<()> {
+        let head_morph = me2_head_morph()?;
+        head_morph.symmetrize(HeadMorphSide::Left);
+
+        // Les sommets du côté droit ont leur symétrique à gauche, chacun le sien
+        let original = me2_head_morph()?;
+        let vertices = head_morph.lod0_vertices.borrow();
+        let positions: Vec<_> = vertices.iter().map(position).collect();
+        let (left, right) = (
+            positions.iter().filter(|(_, y, _)| *y < 0.0).count(),
+            positions.iter().filter(|(_, y, _)| *y > 0.0).count(),
+        );
+        let mirrored = positions
+            .iter()
+            .filter(|&&(x, y, z)| y > 0.0 && positions.contains(&(x, -y, z)))
+            .count();
+        assert_eq!(mirrored, left.min(right));
+
+        let original = original.lod0_vertices.borrow();
+        let original_positions: Vec<_> = original.iter().map(position).collect();
+        let pairs = mirror_pairs(&original_positions, HeadMorphSide::Left);
+        assert_eq!(pairs.len(), left.min(right));
+        let (mut targets, mut sources): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+        targets.sort_unstable();
+        targets.dedup();
+        sources.sort_unstable();
+        sources.dedup();
+        assert_eq!((targets.len(), sources.len()), (left.min(right), left.min(right)));
+
+        // Le côté source n'est pas modifié
+        for (vertex, original) in positions.iter().zip(original.iter().map(position)) {
+            if original.1 < -CENTER_EPSILON {
+                assert_eq!(*vertex, original);
+            }
+        }
+
+        // Os
+        let offset_bones = head_morph.offset_bones.borrow();
+        let (left, right) =
+            (position(&offset_bones["brow_Left"]), position(&offset_bones["brow_right"]));
+        assert_eq!(left, (right.0, -right.1, right.2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn blend_head_morphs() -> Result<()> {
+        let head_morph = me2_head_morph()?;
+        let other = me2_head_morph()?;
+        let first_vertex = position(&head_morph.lod0_vertices.borrow()[0]);
+
+        {
+            let vertices = other.lod0_vertices.borrow();
+            let vertex = vertices[0].borrow();
+            vertex.x.set(first_vertex.0 + 2.0);
+        }
+        let (name, feature) = other
+            .morph_features
+            .borrow()
+            .first()
+            .map(|(name, feature)| (name.clone(), feature.get()))
+            .unwrap();
+        other.morph_features.borrow()[&name].set(feature + 1.0);
+
+        head_morph.blend(&other, 0.25)?;
+        let vertex = position(&head_morph.lod0_vertices.borrow()[0]);
+        assert!((vertex.0 - (first_vertex.0 + 0.5)).abs() < 1e-4);
+        let blended = head_morph.morph_features.borrow()[&name].get();
+        assert!((blended - (feature + 0.25)).abs() < 1e-4);
+
+        // Modèles différents
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let me3_head_morph =
+            me3.player.borrow().appearance.borrow().head_morph.borrow().clone().unwrap();
+        assert!(head_morph.blend(&me3_head_morph.borrow(), 0.5).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use super::head_morph_conversion::HeadMorphGame;
+
+// Vue de face en SVG
+#[derive(Deserialize, Serialize, Clone, Default, PartialEq, Debug)]
+pub struct HeadMorphThumbnail {
+    pub view_box: String,
+    pub path: String,
+}
+
+// Enregistré à côté du head morph dans la bibliothèque
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+pub struct HeadMorphMeta {
+    pub name: String,
+    pub game: HeadMorphGame,
+    pub is_female: bool,
+    pub tags: Vec<String>,
+    pub thumbnail: HeadMorphThumbnail,
+}
+
+impl HeadMorphMeta {
+    // Deux head morphs du même nom se remplacent
+    pub fn file_stem(&self) -> String {
+        self.name
+            .trim()
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+            .collect()
+    }
+
+    // Le filtre porte sur le nom et les tags
+    pub fn matches(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        filter.is_empty()
+            || self.name.to_lowercase().contains(&filter)
+            || self.tags.iter().any(|tag| tag.to_lowercase().contains(&filter))
+    }
+}
+
+// Tags séparés par des virgules
+pub fn parse_tags(tags: &str) -> Vec<String> {
+    tags.split(',').map(str::trim).filter(|tag| !tag.is_empty()).map(String::from).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn head_morph_meta() -> Result<()> {
+        let meta = HeadMorphMeta {
+            name: " Jane Shepard (ME3) ".to_owned(),
+            game: HeadMorphGame::Me3,
+            is_female: true,
+            tags: parse_tags("red hair, ,freckles,"),
+            thumbnail: HeadMorphThumbnail::default(),
+        };
+
+        assert_eq!(meta.tags, vec!["red hair".to_owned(), "freckles".to_owned()]);
+        assert_eq!(meta.file_stem(), "Jane_Shepard__ME3_");
+
+        assert!(meta.matches(""));
+        assert!(meta.matches("jane"));
+        assert!(meta.matches("FRECK"));
+        assert!(!meta.matches("scar"));
+
+        // Format du fichier `.meta.ron`
+        let ron = ron::to_string(&meta)?;
+        let meta_2: HeadMorphMeta = ron::from_str(&ron)?;
+        assert_eq!(meta, meta_2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt::Write;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::{appearance::HeadMorph, Vector};
+use crate::save_data::RcRef;
+
+#[derive(Deserialize)]
+pub struct DbTopology {
+    pub name: String,
+    // Triangles, index des sommets à partir de 0
+    pub faces: Vec<(usize, usize, usize)>,
+}
+
+impl DbTopology {
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges: Vec<_> = self
+            .faces
+            .iter()
+            .flat_map(|&(a, b, c)| [(a, b), (b, c), (c, a)])
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+}
+
+// Nombre de sommets => topologie du modèle de base
+#[derive(Deserialize, Deref)]
+pub struct HeadMorphTopologyDb(IndexMap<usize, DbTopology>);
+
+fn lods(head_morph: &HeadMorph) -> [(&'static str, &RcRef<Vec<RcRef<Vector>>>); 4] {
+    [
+        ("lod0", &head_morph.lod0_vertices),
+        ("lod1", &head_morph.lod1_vertices),
+        ("lod2", &head_morph.lod2_vertices),
+        ("lod3", &head_morph.lod3_vertices),
+    ]
+}
+
+// Un objet par LOD, sans faces si la topologie du modèle n'est pas connue,
+// les sommets sont dans l'ordre du jeu
+pub fn export_obj(head_morph: &HeadMorph, topology_db: &HeadMorphTopologyDb) -> String {
+    let mut obj = String::from("# Trilogy Save Editor - Head morph\n");
+    let mut offset = 0;
+
+    for (name, vertices) in lods(head_morph) {
+        let vertices = vertices.borrow();
+        if vertices.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(obj, "o {}", name);
+        for vertex in vertices.iter() {
+            let vertex = vertex.borrow();
+            let _ = writeln!(obj, "v {} {} {}", vertex.x.get(), vertex.y.get(), vertex.z.get());
+        }
+
+        if let Some(topology) = topology_db.get(&vertices.len()) {
+            let _ = writeln!(obj, "# {}", topology.name);
+            for &(a, b, c) in &topology.faces {
+                let _ = writeln!(obj, "f {} {} {}", offset + a + 1, offset + b + 1, offset + c + 1);
+            }
+        }
+        offset += vertices.len();
+    }
+    obj
+}
+
+// Remplace la position des sommets, les LODs absents du fichier ne sont pas modifiés
+pub fn import_obj(head_morph: &HeadMorph, obj: &str) -> Result<()> {
+    let mut objects: IndexMap<String, Vec<(f32, f32, f32)>> = IndexMap::new();
+    let mut current = String::from("lod0");
+
+    for (idx, line) in obj.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("o") | Some("g") => {
+                current = tokens.next().unwrap_or_default().to_lowercase();
+            }
+            Some("v") => {
+                let mut coord = || -> Result<f32> {
+                    let coord = tokens.next().context("Missing coordinate")?;
+                    Ok(coord.parse()?)
+                };
+                let vertex = (|| Ok::<_, anyhow::Error>((coord()?, coord()?, coord()?)))()
+                    .with_context(|| format!("Invalid vertex at line {}", idx + 1))?;
+                objects.entry(current.clone()).or_default().push(vertex);
+            }
+            _ => {}
+        }
+    }
+
+    if objects.is_empty() {
+        bail!("No vertex found");
+    }
+
+    // Les logiciels 3D renomment les objets (`lod0.001`, `lod0_mesh`...)
+    let lods = lods(head_morph);
+    let mut updates: Vec<(&str, &String, _, _)> = Vec::new();
+    for (name, positions) in &objects {
+        let (lod, vertices) = match lods.iter().find(|(lod, _)| name.starts_with(lod)) {
+            Some(lod) => lod,
+            None => bail!("Unknown object `{}`, expected lod0 to lod3", name),
+        };
+        if let Some((_, other, ..)) = updates.iter().find(|(other_lod, ..)| other_lod == lod) {
+            bail!("`{}` and `{}` are both {}", other, name, lod);
+        }
+
+        let expected = vertices.borrow().len();
+        if positions.len() != expected {
+            bail!("{}: {} vertices found, {} expected", name, positions.len(), expected);
+        }
+        updates.push((lod, name, vertices, positions));
+    }
+
+    for (_, _, vertices, positions) in updates {
+        for (vertex, &(x, y, z)) in vertices.borrow().iter().zip(positions) {
+            let vertex = vertex.borrow();
+            vertex.x.set(x);
+            vertex.y.set(y);
+            vertex.z.set(z);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    fn topology_db() -> Result<HeadMorphTopologyDb> {
+        let input = fs::read_to_string("databases/head_morph_topology_db.ron")?;
+        Ok(ron::from_str(&input)?)
+    }
+
+    #[test]
+    fn closed_topologies() -> Result<()> {
+        let topology_db = topology_db()?;
+        assert!(topology_db.contains_key(&2232));
+        assert!(topology_db.contains_key(&2390));
+
+        for (&vertices, topology) in topology_db.iter() {
+            assert!(topology.faces.iter().all(|&(a, b, c)| a.max(b).max(c) < vertices));
+
+            // Surface fermée : chaque arête orientée a son opposée dans une autre face
+            let mut edges: Vec<_> =
+                topology.faces.iter().flat_map(|&(a, b, c)| [(a, b), (b, c), (c, a)]).collect();
+            edges.sort_unstable();
+            let len = edges.len();
+            edges.dedup();
+            assert_eq!(edges.len(), len);
+            assert!(edges.iter().all(|&(a, b)| edges.binary_search(&(b, a)).is_ok()));
+            assert_eq!(topology.edges().len(), len / 2);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn export_import_obj() -> Result<()> {
+        let topology_db = topology_db()?;
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let head_morph =
+            me3.player.borrow().appearance.borrow().head_morph.borrow().clone().unwrap();
+        let head_morph = head_morph.borrow();
+
+        let obj = export_obj(&head_morph, &topology_db);
+        assert!(obj.contains("\no lod0\n"));
+
+        // Faces du modèle de base, indexées à partir de 1
+        let faces = obj.lines().filter(|line| line.starts_with("f ")).count();
+        let topology = topology_db.get(&head_morph.lod0_vertices.borrow().len()).unwrap();
+        assert_eq!(faces, topology.faces.len());
+        let (a, b, c) = topology.faces[0];
+        assert!(obj.contains(&format!("\nf {} {} {}\n", a + 1, b + 1, c + 1)));
+
+        // Sans topologie, les sommets seuls
+        let obj_without_faces = export_obj(&head_morph, &HeadMorphTopologyDb(IndexMap::new()));
+        assert!(!obj_without_faces.contains("\nf "));
+
+        // Un sommet déplacé, objet renommé par un logiciel 3D
+        let mut lines: Vec<String> = obj.lines().map(String::from).collect();
+        let first_vertex = lines.iter().position(|line| line.starts_with("v ")).unwrap();
+        lines[first_vertex] = String::from("v 1.5 -2 3.25");
+        let lod0 = lines.iter().position(|line| line == "o lod0").unwrap();
+        lines[lod0] = String::from("o lod0.001");
+        import_obj(&head_morph, &lines.join("\n"))?;
+
+        let vertices = head_morph.lod0_vertices.borrow();
+        let vertex = vertices[0].borrow();
+        assert_eq!((vertex.x.get(), vertex.y.get(), vertex.z.get()), (1.5, -2.0, 3.25));
+
+        // Nombre de sommets différent
+        lines.remove(first_vertex);
+        assert!(import_obj(&head_morph, &lines.join("\n")).is_err());
+        assert_eq!(vertices[0].borrow().x.get(), 1.5);
+        lines.insert(first_vertex, String::from("v 1.5 -2 3.25"));
+
+        // Deux objets pour le même LOD
+        lines.insert(lod0, String::from("o lod0"));
+        lines.insert(lod0 + 1, String::from("v 0 0 0"));
+        assert!(import_obj(&head_morph, &lines.join("\n")).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::{appearance::HeadMorph, head_morph_conversion::HeadMorphGame};
+
+#[derive(Deserialize)]
+pub struct DbGenderRules {
+    pub lod_vertices: Vec<usize>,
+    // Cheveux et accessoires
+    pub mesh_prefixes: Vec<String>,
+    pub texture_prefixes: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct DbHeadMorphRules {
+    pub offset_bones: Vec<String>,
+    pub morph_features: Vec<String>,
+    pub texture_parameters: Vec<String>,
+    pub female: DbGenderRules,
+    pub male: DbGenderRules,
+}
+
+// Les erreurs font planter le jeu au chargement, les avertissements
+// concernent des noms que le jeu ignore ou que la base de données ne connaît pas encore
+#[derive(Default, Debug)]
+pub struct HeadMorphReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl HeadMorphReport {
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+}
+
+fn is_known(names: &[String], name: &str) -> bool {
+    names.iter().any(|known| known.eq_ignore_ascii_case(name))
+}
+
+fn has_prefix(prefixes: &[String], path: &str) -> bool {
+    let path = path.to_ascii_lowercase();
+    prefixes.iter().any(|prefix| path.starts_with(&prefix.to_ascii_lowercase()))
+}
+
+fn is_none(path: &str) -> bool {
+    path.is_empty() || path == "None"
+}
+
+#[derive(Deserialize, Deref)]
+pub struct HeadMorphValidationDb(IndexMap<HeadMorphGame, DbHeadMorphRules>);
+
+impl HeadMorphValidationDb {
+    pub fn validate(
+        &self, head_morph: &HeadMorph, game: HeadMorphGame, is_female: bool,
+    ) -> HeadMorphReport {
+        let mut report = HeadMorphReport::default();
+        let rules = match self.get(&game) {
+            Some(rules) => rules,
+            None => return report,
+        };
+        let gender = if is_female { &rules.female } else { &rules.male };
+
+        // Sommets
+        let lods = [
+            &head_morph.lod0_vertices,
+            &head_morph.lod1_vertices,
+            &head_morph.lod2_vertices,
+            &head_morph.lod3_vertices,
+        ];
+        if gender.lod_vertices.is_empty() {
+            report.warnings.push(
+                "The vertex count of this head model is not known, LODs are not checked".to_owned(),
+            );
+        }
+        for (lod, (vertices, &expected)) in lods.iter().zip(&gender.lod_vertices).enumerate() {
+            let len = vertices.borrow().len();
+            if len != expected {
+                report
+                    .errors
+                    .push(format!("LOD{} has {} vertices, {} expected", lod, len, expected));
+            }
+        }
+
+        // Os
+        let offset_bones = head_morph.offset_bones.borrow();
+        for bone in offset_bones.keys() {
+            if !is_known(&rules.offset_bones, bone) {
+                report.errors.push(format!("Unknown bone `{}`", bone));
+            }
+        }
+        // Le créateur de personnage enregistre toujours tous les os du modèle
+        for bone in &rules.offset_bones {
+            if !offset_bones.keys().any(|key| key.eq_ignore_ascii_case(bone)) {
+                report.warnings.push(format!("Missing bone `{}`", bone));
+            }
+        }
+
+        // Modèles
+        let hair_mesh = head_morph.hair_mesh.borrow();
+        if !is_none(&hair_mesh) && !has_prefix(&gender.mesh_prefixes, &hair_mesh) {
+            report
+                .errors
+                .push(format!("Hair mesh `{}` is not available for this gender", hair_mesh));
+        }
+        for accessory in head_morph.accessory_mesh.borrow().iter() {
+            let accessory = accessory.borrow();
+            if !is_none(&accessory) && !has_prefix(&gender.mesh_prefixes, &accessory) {
+                report
+                    .errors
+                    .push(format!("Accessory `{}` is not available for this gender", accessory));
+            }
+        }
+
+        // Textures
+        for (name, texture) in head_morph.texture_parameters.borrow().iter() {
+            if !is_known(&rules.texture_parameters, name) {
+                report.warnings.push(format!("Unknown texture parameter `{}`", name));
+            }
+            let texture = texture.borrow();
+            if !is_none(&texture) && !has_prefix(&gender.texture_prefixes, &texture) {
+                report.errors.push(format!(
+                    "{}: texture `{}` is not available for this gender",
+                    name, texture
+                ));
+            }
+        }
+
+        // Curseurs
+        for feature in head_morph.morph_features.borrow().keys() {
+            if !is_known(&rules.morph_features, feature) {
+                report.warnings.push(format!("Unknown morph feature `{}`", feature));
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_1_le::Me1LeSaveGame,
+            mass_effect_2::{Me2LeSaveGame, Me2SaveGame},
+            mass_effect_3::Me3SaveGame,
+        },
+        unreal,
+    };
+
+    #[test]
+    fn validate_head_morph() -> Result<()> {
+        let input = fs::read_to_string("databases/head_morph_validation_db.ron")?;
+        let validation_db: HeadMorphValidationDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let me1_head_morph = me1.save_data.borrow().player.borrow().head_morph.borrow().clone();
+        let me1_head_morph = me1_head_morph.unwrap();
+
+        let input = fs::read("test/ME2LeSave.pcsav")?;
+        let me2: Me2LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let me2_head_morph =
+            me2.player.borrow().appearance.borrow().head_morph.borrow().clone().unwrap();
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let me3: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let me3_head_morph =
+            me3.player.borrow().appearance.borrow().head_morph.borrow().clone().unwrap();
+
+        // Head morphs des sauvegardes de test (Shepard femme)
+        let head_morphs = [
+            (HeadMorphGame::Me1, &me1_head_morph),
+            (HeadMorphGame::Me2, &me2_head_morph),
+            (HeadMorphGame::Me3, &me3_head_morph),
+        ];
+        for (game, head_morph) in head_morphs {
+            let report = validation_db.validate(&head_morph.borrow(), game, true);
+            assert!(report.is_empty(), "{:?}", report);
+        }
+
+        // ME1 => ME3 : pas le même modèle
+        let report = validation_db.validate(&me1_head_morph.borrow(), HeadMorphGame::Me3, true);
+        assert_eq!(report.errors.len(), 1);
+
+        // Cheveux féminins sur un Shepard homme, nombre de sommets inconnu
+        let report = validation_db.validate(&me3_head_morph.borrow(), HeadMorphGame::Me3, false);
+        assert!(report.errors.iter().any(|error| error.starts_with("Hair mesh")));
+        assert!(report.warnings.iter().any(|warning| warning.starts_with("The vertex count")));
+
+        // Os manquant
+        let head_morph = me3_head_morph.borrow().clone();
+        head_morph.offset_bones.borrow_mut().shift_remove("Sneer");
+        let report = validation_db.validate(&head_morph, HeadMorphGame::Me3, true);
+        assert_eq!(report.warnings, vec!["Missing bone `Sneer`".to_owned()]);
+
+        // Os et curseur inconnus
+        let head_morph = me2_head_morph.borrow().clone();
+        head_morph.offset_bones.borrow_mut().insert("tail".to_owned(), Default::default());
+        head_morph.morph_features.borrow_mut().insert("tail_length".to_owned(), 1.0.into());
+        let report = validation_db.validate(&head_morph, HeadMorphGame::Me2, true);
+        assert_eq!(report.errors, vec!["Unknown bone `tail`".to_owned()]);
+        assert_eq!(report.warnings, vec!["Unknown morph feature `tail_length`".to_owned()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn full_bone_lists() -> Result<()> {
+        let input = fs::read_to_string("databases/head_morph_validation_db.ron")?;
+        let validation_db: HeadMorphValidationDb = ron::from_str(&input)?;
+
+        let me1: Me1LeSaveGame =
+            unreal::Deserializer::from_bytes(&fs::read("test/ME1LeExport.pcsav")?)?;
+        let me1_head_morph = me1.save_data.borrow().player.borrow().head_morph.borrow().clone();
+        let me2: Me2SaveGame = unreal::Deserializer::from_bytes(&fs::read("test/ME2Save.pcsav")?)?;
+        let me2_head_morph = me2.player.borrow().appearance.borrow().head_morph.borrow().clone();
+        let me3: Me3SaveGame =
+            unreal::Deserializer::from_be_bytes(&fs::read("test/ME3Save360.xbsav")?)?;
+        let me3_head_morph = me3.player.borrow().appearance.borrow().head_morph.borrow().clone();
+
+        let head_morphs = [
+            (HeadMorphGame::Me1, me1_head_morph.unwrap().borrow().clone()),
+            (HeadMorphGame::Me2, me2_head_morph.unwrap().borrow().clone()),
+            (HeadMorphGame::Me3, me3_head_morph.unwrap().borrow().clone()),
+            (
+                HeadMorphGame::Me2,
+                HeadMorph::from_gibbed(&fs::read("test/GibbedME2.me2headmorph")?)?,
+            ),
+            (
+                HeadMorphGame::Me3,
+                HeadMorph::from_gibbed(&fs::read("test/GibbedME3.me3headmorph")?)?,
+            ),
+        ];
+        for (game, head_morph) in head_morphs {
+            let rules = validation_db.get(&game).unwrap();
+            let offset_bones = head_morph.offset_bones.borrow();
+            assert_eq!(offset_bones.len(), rules.offset_bones.len());
+            assert!(offset_bones.keys().all(|bone| is_known(&rules.offset_bones, bone)));
+        }
+
+        Ok(())
+    }
+}
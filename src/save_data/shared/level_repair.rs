@@ -0,0 +1,141 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::{Door, Kismet, StreamingState};
+use crate::save_data::RcRef;
+
+#[derive(Deserialize, Default)]
+pub struct LevelRepair {
+    // Nom de l'état => actif, les états absents ne sont pas modifiés
+    #[serde(default)]
+    pub streaming_states: IndexMap<String, bool>,
+    // Guids des enregistrements kismet à supprimer
+    #[serde(default)]
+    pub stale_kismet_records: Vec<String>,
+    // Guid de la porte => état par défaut
+    #[serde(default)]
+    pub doors: IndexMap<String, u8>,
+}
+
+impl LevelRepair {
+    pub fn reset_streaming_states(&self, streaming_states: &RcRef<Vec<RcRef<StreamingState>>>) {
+        let mut streaming_states = streaming_states.borrow_mut();
+        for (name, &is_active) in &self.streaming_states {
+            match streaming_states.iter().find(|state| *state.borrow().name.borrow() == *name) {
+                Some(state) => state.borrow().is_active.set(is_active),
+                None => {
+                    let state = StreamingState::default();
+                    *state.name.borrow_mut() = name.clone();
+                    state.is_active.set(is_active);
+                    streaming_states.push(state.into());
+                }
+            }
+        }
+    }
+
+    pub fn clear_stale_kismet_records(&self, kismet_records: &RcRef<Vec<RcRef<Kismet>>>) {
+        kismet_records.borrow_mut().retain(|record| {
+            let guid = record.borrow().guid.borrow().hyphenated();
+            !self.stale_kismet_records.iter().any(|stale| stale.eq_ignore_ascii_case(&guid))
+        });
+    }
+
+    pub fn reset_doors(&self, doors: &RcRef<Vec<RcRef<Door>>>) {
+        for door in doors.borrow().iter() {
+            let door = door.borrow();
+            let guid = door.guid.borrow().hyphenated();
+            let default_state = self.doors.iter().find_map(|(door_guid, &state)| {
+                door_guid.eq_ignore_ascii_case(&guid).then(|| state)
+            });
+
+            if let Some(state) = default_state {
+                door.current_state.set(state);
+                door.old_state.set(state);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum LevelRepairAction {
+    ResetStreamingStates,
+    ClearStaleKismetRecords,
+    ResetDoors,
+}
+
+#[derive(Deserialize, Deref)]
+pub struct LevelRepairDb(IndexMap<String, LevelRepair>);
+
+impl LevelRepairDb {
+    // La casse du nom de la map varie d'une sauvegarde à l'autre ("Biop_Nor" / "BioP_Nor")
+    pub fn find(&self, base_level_name: &str) -> Option<&LevelRepair> {
+        self.iter()
+            .find_map(|(name, repair)| name.eq_ignore_ascii_case(base_level_name).then(|| repair))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::unreal;
+
+    #[test]
+    fn deserialize_level_repair_db() -> Result<()> {
+        let files = ["databases/me2_level_repair_db.ron", "databases/me3_level_repair_db.ron"];
+
+        for file in files {
+            let input = fs::read_to_string(file)?;
+            let _level_repair_db: LevelRepairDb = ron::from_str(&input)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn repair_level() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_level_repair_db.ron")?;
+        let level_repair_db: LevelRepairDb = ron::from_str(&input)?;
+        let repair = level_repair_db.find("biop_nor").unwrap();
+
+        // Un état de streaming bloqué et une porte restée ouverte
+        let (name, &is_active) = repair.streaming_states.first().unwrap();
+        let state = StreamingState::default();
+        *state.name.borrow_mut() = name.clone();
+        state.is_active.set(!is_active);
+        let streaming_states: RcRef<Vec<RcRef<StreamingState>>> = vec![state.into()].into();
+
+        let (guid, &door_state) = repair.doors.first().unwrap();
+        let door: Door = unreal::Deserializer::from_bytes(&unreal::Serializer::to_vec(&(
+            Uuid::parse_str(guid)?.as_fields(),
+            3u8,
+            3u8,
+        ))?)?;
+        let doors: RcRef<Vec<RcRef<Door>>> = vec![door.into()].into();
+
+        repair.reset_streaming_states(&streaming_states);
+        repair.reset_doors(&doors);
+
+        let streaming_states = streaming_states.borrow();
+        assert_eq!(streaming_states.len(), repair.streaming_states.len());
+        assert_eq!(streaming_states[0].borrow().is_active.get(), is_active);
+
+        let doors = doors.borrow();
+        let door = doors[0].borrow();
+        assert_eq!(door.current_state.get(), door_state);
+        assert_eq!(door.old_state.get(), door_state);
+
+        // Suppression d'un enregistrement kismet
+        let record = Kismet::default();
+        let guid = record.guid.borrow().hyphenated();
+        let kismet_records: RcRef<Vec<RcRef<Kismet>>> = vec![record.into()].into();
+        let repair = LevelRepair { stale_kismet_records: vec![guid], ..Default::default() };
+        repair.clear_stale_kismet_records(&kismet_records);
+        assert!(kismet_records.borrow().is_empty());
+
+        Ok(())
+    }
+}
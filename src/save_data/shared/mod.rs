@@ -1,6 +1,17 @@
 pub mod appearance;
+pub mod appearance_db;
+pub mod dlc_db;
+pub mod face_code;
+pub mod guid_db;
+pub mod head_morph_conversion;
+pub mod head_morph_edit;
+pub mod head_morph_library;
+pub mod head_morph_obj;
+pub mod head_morph_validation;
+pub mod level_repair;
 pub mod player;
 pub mod plot;
+pub mod teleport;
 
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -58,6 +69,12 @@ pub struct Vector {
     z: f32,
 }
 
+impl Vector {
+    pub fn position(&self) -> (f32, f32, f32) {
+        (self.x.get(), self.y.get(), self.z.get())
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
@@ -95,15 +112,15 @@ pub struct StreamingState {
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct Kismet {
-    guid: Guid,
-    value: bool,
+    pub guid: Guid,
+    pub value: bool,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct Door {
-    guid: Guid,
-    current_state: u8,
-    old_state: u8,
+    pub guid: Guid,
+    pub current_state: u8,
+    pub old_state: u8,
 }
@@ -36,7 +36,7 @@ pub struct PlotTable {
 }
 
 #[rcize_fields]
-#[derive(Deserialize, Serialize, Clone, RawUi)]
+#[derive(Deserialize, Serialize, Clone, Default, RawUi)]
 pub struct Journal {
     quest_progress_counter: i32,
     quest_progress: Vec<PlotQuest>,
@@ -44,7 +44,7 @@ pub struct Journal {
 }
 
 #[rcize_fields]
-#[derive(Deserialize, Serialize, Clone, RawUi)]
+#[derive(Deserialize, Serialize, Clone, Default, RawUi)]
 pub struct Codex {
     codex_entries: Vec<PlotCodex>,
     codex_ids: Vec<i32>,
@@ -0,0 +1,136 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::{Level, Rotator, StreamingState, Vector};
+use crate::save_data::RcRef;
+
+#[derive(Deserialize)]
+pub struct TeleportPoint {
+    pub name: String,
+    pub base_level_name: String,
+    // ME1 uniquement
+    #[serde(default)]
+    pub map_name: String,
+    #[serde(default)]
+    pub parent_map_name: String,
+    pub location: (f32, f32, f32),
+    pub rotation: (i32, i32, i32),
+    // Nom du niveau => visible
+    #[serde(default)]
+    pub levels: IndexMap<String, bool>,
+    #[serde(default)]
+    pub streaming_states: Vec<String>,
+}
+
+impl TeleportPoint {
+    pub fn set_position(&self, location: &RcRef<Vector>, rotation: &RcRef<Rotator>) {
+        let (x, y, z) = self.location;
+        let location = location.borrow();
+        location.x.set(x);
+        location.y.set(y);
+        location.z.set(z);
+
+        let (pitch, yaw, roll) = self.rotation;
+        let rotation = rotation.borrow();
+        rotation.pitch.set(pitch);
+        rotation.yaw.set(yaw);
+        rotation.roll.set(roll);
+    }
+
+    // Les niveaux et les états de streaming de l'ancienne map ne doivent plus être chargés,
+    // seuls ceux du point sont conservés
+    pub fn set_levels(
+        &self, levels: &RcRef<Vec<RcRef<Level>>>,
+        streaming_states: &RcRef<Vec<RcRef<StreamingState>>>,
+    ) {
+        *levels.borrow_mut() = self
+            .levels
+            .iter()
+            .map(|(name, &visible)| {
+                let level = Level::default();
+                *level.name.borrow_mut() = name.clone();
+                level.should_be_loaded.set(true);
+                level.should_be_visible.set(visible);
+                level.into()
+            })
+            .collect();
+
+        *streaming_states.borrow_mut() = self
+            .streaming_states
+            .iter()
+            .map(|name| {
+                let state = StreamingState::default();
+                *state.name.borrow_mut() = name.clone();
+                state.is_active.set(true);
+                state.into()
+            })
+            .collect();
+    }
+}
+
+#[derive(Deserialize, Deref)]
+pub struct TeleportDb(Vec<TeleportPoint>);
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::unreal;
+
+    #[test]
+    fn deserialize_teleport_db() -> Result<()> {
+        let files = [
+            "databases/me1_le_teleport_db.ron",
+            "databases/me2_teleport_db.ron",
+            "databases/me3_teleport_db.ron",
+        ];
+
+        for file in files {
+            let input = fs::read_to_string(file)?;
+            let _teleport_db: TeleportDb = ron::from_str(&input)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn teleport() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_teleport_db.ron")?;
+        let teleport_db: TeleportDb = ron::from_str(&input)?;
+        let point = teleport_db.iter().find(|point| point.base_level_name == "BioP_Nor").unwrap();
+
+        let location: RcRef<Vector> = Vector::default().into();
+        let rotation: RcRef<Rotator> =
+            unreal::Deserializer::from_bytes(&unreal::Serializer::to_vec(&(0i32, 0i32, 0i32))?)?;
+        point.set_position(&location, &rotation);
+        assert_eq!(location.borrow().x.get(), point.location.0);
+        assert_eq!(rotation.borrow().yaw.get(), point.rotation.1);
+
+        // Une map quelconque avec un état actif
+        let old_level = Level::default();
+        *old_level.name.borrow_mut() = String::from("BioA_CitHub");
+        let old_state = StreamingState::default();
+        *old_state.name.borrow_mut() = String::from("stream_Kaidan");
+        old_state.is_active.set(true);
+        let levels: RcRef<Vec<RcRef<Level>>> = vec![old_level.into()].into();
+        let streaming_states: RcRef<Vec<RcRef<StreamingState>>> = vec![old_state.into()].into();
+
+        point.set_levels(&levels, &streaming_states);
+
+        let levels = levels.borrow();
+        assert_eq!(levels.len(), point.levels.len());
+        assert!(levels.iter().all(|level| level.borrow().should_be_loaded.get()));
+
+        // L'état de l'ancienne map est supprimé
+        let streaming_states = streaming_states.borrow();
+        assert_eq!(streaming_states.len(), point.streaming_states.len());
+        assert!(streaming_states.iter().all(|state| state.borrow().is_active.get()));
+        assert!(!streaming_states
+            .iter()
+            .any(|state| *state.borrow().name.borrow() == "stream_Kaidan"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,628 @@
+use anyhow::{bail, ensure, Context, Result};
+use sha1::{Digest, Sha1};
+
+// Package STFS Xbox 360 (CON / LIVE / PIRS)
+const BLOCK_SIZE: usize = 0x1000;
+const HASHES_PER_TABLE: usize = 0xAA;
+const HASH_ENTRY_SIZE: usize = 0x18;
+const FILE_ENTRY_SIZE: usize = 0x40;
+const END_OF_CHAIN: usize = 0xFF_FFFF;
+
+// Header
+const HEADER_HASH: usize = 0x32C;
+const HEADER_SIZE: usize = 0x340;
+const HASHED_HEADER_START: usize = 0x344;
+// Volume descriptor
+const BLOCK_SEPARATION: usize = 0x37B;
+const FILE_TABLE_BLOCK_COUNT: usize = 0x37C;
+const FILE_TABLE_BLOCK_NUMBER: usize = 0x37E;
+const TOP_HASH_TABLE_HASH: usize = 0x381;
+const ALLOCATED_BLOCK_COUNT: usize = 0x395;
+const UNALLOCATED_BLOCK_COUNT: usize = 0x399;
+
+// Block status
+const BLOCK_ALLOCATED: u8 = 0x80;
+
+#[derive(Clone)]
+struct FileEntry {
+    address: usize,
+    table_block: usize,
+    is_directory: bool,
+    is_consecutive: bool,
+    start_block: usize,
+    block_count: usize,
+    size: usize,
+}
+
+#[derive(Clone)]
+pub struct StfsPackage {
+    data: Vec<u8>,
+    // 1 table de hash par niveau si 0, 2 si 1
+    table_shift: usize,
+    first_table_address: usize,
+    top_level: usize,
+    entries: Vec<FileEntry>,
+    save_file: Option<usize>,
+}
+
+impl StfsPackage {
+    pub fn is_stfs(input: &[u8]) -> bool {
+        matches!(input.get(..4), Some(b"CON ") | Some(b"LIVE") | Some(b"PIRS"))
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        ensure!(Self::is_stfs(&data), "Not a STFS package");
+        ensure!(data.len() > UNALLOCATED_BLOCK_COUNT + 4, "Truncated STFS package");
+
+        let header_size = read_u32_be(&data, HEADER_SIZE) as usize;
+        let first_table_address = (header_size + 0xFFF) & !0xFFF;
+        let table_shift = (!data[BLOCK_SEPARATION] & 1) as usize;
+
+        let mut package = StfsPackage {
+            data,
+            table_shift,
+            first_table_address,
+            top_level: 0,
+            entries: Vec::new(),
+            save_file: None,
+        };
+        package.top_level = match package.allocated_block_count() {
+            count if count <= HASHES_PER_TABLE => 0,
+            count if count <= HASHES_PER_TABLE.pow(2) => 1,
+            _ => 2,
+        };
+        package.entries = package.read_file_table()?;
+        Ok(package)
+    }
+
+    // Le premier fichier accepté par `parse` est la save
+    pub fn find_save_file<T>(&mut self, mut parse: impl FnMut(Vec<u8>) -> Result<T>) -> Result<T> {
+        let mut error = None;
+        for idx in 0..self.entries.len() {
+            if self.entries[idx].is_directory {
+                continue;
+            }
+
+            match parse(self.read_file(idx)?) {
+                Ok(value) => {
+                    self.save_file = Some(idx);
+                    return Ok(value);
+                }
+                // On garde la première erreur, les fichiers suivants sont rarement des saves
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+        match error {
+            Some(err) => Err(err.context("No save found in the package")),
+            None => bail!("No file in the package"),
+        }
+    }
+
+    // Remplace la save puis met à jour les tables de hash et le hash du header
+    // La signature du package ne peut pas être refaite, il faut le resigner avec un autre outil
+    // Le package n'est modifié que si l'écriture réussit
+    pub fn write_save_file(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut package = self.clone();
+        package.replace_save_file(bytes)?;
+        *self = package;
+        Ok(())
+    }
+
+    fn replace_save_file(&mut self, bytes: &[u8]) -> Result<()> {
+        let idx = self.save_file.context("No save found in the package")?;
+        let entry = self.entries[idx].clone();
+
+        let mut blocks = self.block_chain(&entry)?;
+        let block_count = ((bytes.len() + BLOCK_SIZE - 1) / BLOCK_SIZE).max(1);
+
+        // Blocs en trop
+        let mut unallocated_block_count = self.unallocated_block_count();
+        for &block in blocks.iter().skip(block_count) {
+            self.set_hash_entry(block, Some(0), END_OF_CHAIN)?;
+            unallocated_block_count += 1;
+        }
+        blocks.truncate(block_count);
+
+        // Blocs manquants, ajoutés à la fin du package
+        let mut allocated_block_count = self.allocated_block_count();
+        while blocks.len() < block_count {
+            let block = allocated_block_count;
+            // Au-delà il faudrait un 3ème niveau de tables, une save n'est jamais aussi grosse
+            ensure!(block < HASHES_PER_TABLE.pow(2), "Not enough space left in the package");
+
+            let end = self.block_address(block) + BLOCK_SIZE;
+            if self.data.len() < end {
+                self.data.resize(end, 0);
+            }
+            if block % HASHES_PER_TABLE == 0 {
+                if block == HASHES_PER_TABLE && self.top_level == 0 {
+                    self.add_top_level()?;
+                }
+                // Nouvelle table de hash
+                let address = self.primary_table_address(0, block);
+                self.data[address..address + (BLOCK_SIZE << self.table_shift)].fill(0);
+            }
+            self.set_hash_entry(block, Some(BLOCK_ALLOCATED), END_OF_CHAIN)?;
+            blocks.push(block);
+            allocated_block_count += 1;
+        }
+        write_u32_be(&mut self.data, ALLOCATED_BLOCK_COUNT, allocated_block_count as u32);
+        write_u32_be(&mut self.data, UNALLOCATED_BLOCK_COUNT, unallocated_block_count as u32);
+
+        // Données
+        for (i, &block) in blocks.iter().enumerate() {
+            let address = self.block_address(block);
+            let chunk = bytes.chunks(BLOCK_SIZE).nth(i).unwrap_or_default();
+
+            let data = &mut self.data[address..address + BLOCK_SIZE];
+            data.fill(0);
+            data[..chunk.len()].copy_from_slice(chunk);
+
+            let next = blocks.get(i + 1).copied().unwrap_or(END_OF_CHAIN);
+            self.set_hash_entry(block, None, next)?;
+        }
+
+        // Entrée du fichier
+        let is_consecutive = blocks.windows(2).all(|pair| pair[1] == pair[0] + 1);
+        {
+            let data = &mut self.data[entry.address..entry.address + FILE_ENTRY_SIZE];
+            data[0x28] = (data[0x28] & !0x40) | if is_consecutive { 0x40 } else { 0 };
+            write_u24_le(data, 0x29, block_count);
+            write_u24_le(data, 0x2C, block_count);
+            write_u32_be(data, 0x34, bytes.len() as u32);
+        }
+        self.set_hash_entry(entry.table_block, None, self.next_block(entry.table_block)?)?;
+        self.entries[idx] = FileEntry { is_consecutive, block_count, size: bytes.len(), ..entry };
+
+        self.rehash()
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    fn read_file(&self, idx: usize) -> Result<Vec<u8>> {
+        let entry = &self.entries[idx];
+
+        let mut file = Vec::with_capacity(entry.block_count * BLOCK_SIZE);
+        for block in self.block_chain(entry)? {
+            file.extend_from_slice(self.slice(self.block_address(block), BLOCK_SIZE)?);
+        }
+        ensure!(file.len() >= entry.size, "Truncated file in the package");
+        file.truncate(entry.size);
+        Ok(file)
+    }
+
+    fn read_file_table(&self) -> Result<Vec<FileEntry>> {
+        let block_count = u16::from_le_bytes([
+            self.data[FILE_TABLE_BLOCK_COUNT],
+            self.data[FILE_TABLE_BLOCK_COUNT + 1],
+        ]) as usize;
+        let start_block = read_u24_le(&self.data, FILE_TABLE_BLOCK_NUMBER);
+
+        let mut entries = Vec::new();
+        let mut block = start_block;
+        for _ in 0..block_count {
+            let block_address = self.block_address(block);
+            for address in (block_address..block_address + BLOCK_SIZE).step_by(FILE_ENTRY_SIZE) {
+                let entry = self.slice(address, FILE_ENTRY_SIZE)?;
+
+                let flags = entry[0x28];
+                if flags & 0x3F == 0 {
+                    continue;
+                }
+
+                entries.push(FileEntry {
+                    address,
+                    table_block: block,
+                    is_directory: flags & 0x80 != 0,
+                    is_consecutive: flags & 0x40 != 0,
+                    block_count: read_u24_le(entry, 0x29),
+                    start_block: read_u24_le(entry, 0x2F),
+                    size: read_u32_be(entry, 0x34) as usize,
+                });
+            }
+            block = self.next_block(block)?;
+        }
+        Ok(entries)
+    }
+
+    fn block_chain(&self, entry: &FileEntry) -> Result<Vec<usize>> {
+        if entry.is_consecutive {
+            return Ok((entry.start_block..entry.start_block + entry.block_count).collect());
+        }
+
+        let mut blocks = Vec::with_capacity(entry.block_count);
+        let mut block = entry.start_block;
+        for _ in 0..entry.block_count {
+            ensure!(block != END_OF_CHAIN, "Broken block chain in the package");
+            blocks.push(block);
+            block = self.next_block(block)?;
+        }
+        Ok(blocks)
+    }
+
+    // La table de niveau 0 passe sous une table de niveau 1
+    fn add_top_level(&mut self) -> Result<()> {
+        let address = self.primary_table_address(1, 0);
+        self.data[address..address + (BLOCK_SIZE << self.table_shift)].fill(0);
+
+        // La table active est maintenant indiquée par l'entrée de la table parente
+        if self.table_shift == 1 && self.data[BLOCK_SEPARATION] & 2 != 0 {
+            self.data[address + 0x14] |= 0x40;
+            self.data[BLOCK_SEPARATION] &= !2;
+        }
+        self.top_level = 1;
+        Ok(())
+    }
+
+    fn rehash(&mut self) -> Result<()> {
+        let allocated_block_count = self.allocated_block_count();
+
+        // Chaque table est hashée dans la table du niveau supérieur
+        for level in 0..self.top_level {
+            let blocks_per_table = HASHES_PER_TABLE.pow(level as u32 + 1);
+            let table_count = (allocated_block_count + blocks_per_table - 1) / blocks_per_table;
+            for table in 0..table_count {
+                let block = table * blocks_per_table;
+                let hash = sha1(self.slice(self.table_address(level, block)?, BLOCK_SIZE)?);
+                let parent = self.hash_entry_address(level + 1, block)?;
+                self.data[parent..parent + 0x14].copy_from_slice(&hash);
+            }
+        }
+
+        let top_hash = sha1(self.slice(self.table_address(self.top_level, 0)?, BLOCK_SIZE)?);
+        self.data[TOP_HASH_TABLE_HASH..TOP_HASH_TABLE_HASH + 0x14].copy_from_slice(&top_hash);
+
+        let header_hash =
+            sha1(self.slice(HASHED_HEADER_START, self.first_table_address - HASHED_HEADER_START)?);
+        self.data[HEADER_HASH..HEADER_HASH + 0x14].copy_from_slice(&header_hash);
+        Ok(())
+    }
+
+    fn set_hash_entry(&mut self, block: usize, status: Option<u8>, next: usize) -> Result<()> {
+        let hash = sha1(self.slice(self.block_address(block), BLOCK_SIZE)?);
+        let address = self.hash_entry_address(0, block)?;
+
+        let entry = &mut self.data[address..address + HASH_ENTRY_SIZE];
+        entry[..0x14].copy_from_slice(&hash);
+        if let Some(status) = status {
+            entry[0x14] = status;
+        }
+        write_u24_be(entry, 0x15, next);
+        Ok(())
+    }
+
+    fn next_block(&self, block: usize) -> Result<usize> {
+        let address = self.hash_entry_address(0, block)?;
+        Ok(read_u24_be(self.slice(address, HASH_ENTRY_SIZE)?, 0x15))
+    }
+
+    fn allocated_block_count(&self) -> usize {
+        read_u32_be(&self.data, ALLOCATED_BLOCK_COUNT) as usize
+    }
+
+    fn unallocated_block_count(&self) -> usize {
+        read_u32_be(&self.data, UNALLOCATED_BLOCK_COUNT) as usize
+    }
+
+    // Adresses
+    fn block_address(&self, block: usize) -> usize {
+        let shift = self.table_shift;
+        let mut backing_block = (((block + 0xAA) / 0xAA) << shift) + block;
+        if block >= 0xAA {
+            backing_block += ((block + 0x70E4) / 0x70E4) << shift;
+            if block >= 0x70E4 {
+                backing_block += 1 << shift;
+            }
+        }
+        self.first_table_address + (backing_block << 0xC)
+    }
+
+    fn primary_table_address(&self, level: usize, block: usize) -> usize {
+        let shift = self.table_shift;
+        let (step0, step1) = if shift == 0 { (0xAB, 0x718F) } else { (0xAC, 0x723A) };
+
+        let backing_block = match level {
+            0 if block < 0xAA => 0,
+            0 => {
+                let backing_block = (block / 0xAA) * step0 + (((block / 0x70E4) + 1) << shift);
+                if block / 0x70E4 == 0 {
+                    backing_block
+                } else {
+                    backing_block + (1 << shift)
+                }
+            }
+            1 if block < 0x70E4 => step0,
+            1 => (1 << shift) + (block / 0x70E4) * step1,
+            _ => step1,
+        };
+        self.first_table_address + (backing_block << 0xC)
+    }
+
+    fn table_address(&self, level: usize, block: usize) -> Result<usize> {
+        let shift = self.table_shift;
+        let mut address = self.primary_table_address(level, block);
+
+        // Les packages avec 2 tables par niveau utilisent la table active
+        if shift == 1 {
+            let is_secondary = if level == self.top_level {
+                self.data[BLOCK_SEPARATION] & 2 != 0
+            } else {
+                let parent = self.hash_entry_address(level + 1, block)?;
+                self.slice(parent, HASH_ENTRY_SIZE)?[0x14] & 0x40 != 0
+            };
+            if is_secondary {
+                address += BLOCK_SIZE;
+            }
+        }
+        Ok(address)
+    }
+
+    fn hash_entry_address(&self, level: usize, block: usize) -> Result<usize> {
+        let idx = (block / HASHES_PER_TABLE.pow(level as u32)) % HASHES_PER_TABLE;
+        Ok(self.table_address(level, block)? + idx * HASH_ENTRY_SIZE)
+    }
+
+    fn slice(&self, address: usize, len: usize) -> Result<&[u8]> {
+        self.data.get(address..address + len).context("Truncated STFS package")
+    }
+}
+
+fn sha1(data: &[u8]) -> [u8; 0x14] {
+    Sha1::digest(data).into()
+}
+
+fn read_u24_be(data: &[u8], offset: usize) -> usize {
+    u32::from_be_bytes([0, data[offset], data[offset + 1], data[offset + 2]]) as usize
+}
+
+fn read_u24_le(data: &[u8], offset: usize) -> usize {
+    u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], 0]) as usize
+}
+
+fn read_u32_be(data: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
+}
+
+fn write_u24_be(data: &mut [u8], offset: usize, value: usize) {
+    data[offset..offset + 3].copy_from_slice(&u32::to_be_bytes(value as u32)[1..]);
+}
+
+fn write_u24_le(data: &mut [u8], offset: usize, value: usize) {
+    data[offset..offset + 3].copy_from_slice(&u32::to_le_bytes(value as u32)[..3]);
+}
+
+fn write_u32_be(data: &mut [u8], offset: usize, value: u32) {
+    data[offset..offset + 4].copy_from_slice(&u32::to_be_bytes(value));
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_1::{Me1SaveGame, Me1SaveGameSeed},
+            mass_effect_3::Me3SaveGame,
+        },
+        unreal,
+    };
+
+    // Pas de vrai package CON dans les fixtures, ils sont construits à partir des saves Xbox 360
+
+    // Package CON minimal (1 table de hash par niveau si `shift` vaut 0, 2 sinon) :
+    // la table des fichiers puis la save
+    fn con_package(file_name: &str, file: &[u8], shift: usize, is_secondary: bool) -> Vec<u8> {
+        const FIRST_TABLE_ADDRESS: usize = 0xA000;
+
+        let file_block_count = (file.len() + BLOCK_SIZE - 1) / BLOCK_SIZE;
+        let block_count = 1 + file_block_count;
+        let tables = 1 << shift;
+        let block_address = |block: usize| FIRST_TABLE_ADDRESS + (tables + block) * BLOCK_SIZE;
+        let table_address = FIRST_TABLE_ADDRESS + is_secondary as usize * BLOCK_SIZE;
+
+        let mut data = vec![0; block_address(block_count)];
+        data[..4].copy_from_slice(b"CON ");
+        write_u32_be(&mut data, HEADER_SIZE, 0x971A);
+        data[0x379] = 0x24;
+        data[BLOCK_SEPARATION] = (shift == 0) as u8 | if is_secondary { 2 } else { 0 };
+        data[FILE_TABLE_BLOCK_COUNT] = 1;
+        write_u32_be(&mut data, ALLOCATED_BLOCK_COUNT, block_count as u32);
+
+        // Table des fichiers
+        {
+            let entry = &mut data[block_address(0)..];
+            entry[..file_name.len()].copy_from_slice(file_name.as_bytes());
+            entry[0x28] = file_name.len() as u8 | 0x40;
+            write_u24_le(entry, 0x29, file_block_count);
+            write_u24_le(entry, 0x2C, file_block_count);
+            write_u24_le(entry, 0x2F, 1);
+            entry[0x32..0x34].copy_from_slice(&[0xFF, 0xFF]);
+            write_u32_be(entry, 0x34, file.len() as u32);
+        }
+
+        // Save
+        let start = block_address(1);
+        data[start..start + file.len()].copy_from_slice(file);
+
+        // Table de hash
+        for block in 0..block_count {
+            let address = block_address(block);
+            let hash = sha1(&data[address..address + BLOCK_SIZE]);
+
+            let next =
+                if block == 0 || block + 1 == block_count { END_OF_CHAIN } else { block + 1 };
+            let entry = &mut data[table_address + block * HASH_ENTRY_SIZE..];
+            entry[..0x14].copy_from_slice(&hash);
+            entry[0x14] = BLOCK_ALLOCATED;
+            write_u24_be(entry, 0x15, next);
+        }
+
+        let top_hash = sha1(&data[table_address..table_address + BLOCK_SIZE]);
+        data[TOP_HASH_TABLE_HASH..TOP_HASH_TABLE_HASH + 0x14].copy_from_slice(&top_hash);
+        let header_hash = sha1(&data[HASHED_HEADER_START..FIRST_TABLE_ADDRESS]);
+        data[HEADER_HASH..HEADER_HASH + 0x14].copy_from_slice(&header_hash);
+
+        data
+    }
+
+    fn check_hashes(package: &StfsPackage) -> Result<()> {
+        let allocated_block_count = package.allocated_block_count();
+        for block in 0..allocated_block_count {
+            let address = package.hash_entry_address(0, block)?;
+            let hash = sha1(package.slice(package.block_address(block), BLOCK_SIZE)?);
+            if package.data[address + 0x14] == BLOCK_ALLOCATED {
+                assert!(package.data[address..address + 0x14] == hash);
+            }
+        }
+
+        for level in 0..package.top_level {
+            let blocks_per_table = HASHES_PER_TABLE.pow(level as u32 + 1);
+            for block in (0..allocated_block_count).step_by(blocks_per_table) {
+                let hash = sha1(package.slice(package.table_address(level, block)?, BLOCK_SIZE)?);
+                let parent = package.hash_entry_address(level + 1, block)?;
+                assert!(package.data[parent..parent + 0x14] == hash);
+            }
+        }
+
+        let top_hash =
+            sha1(package.slice(package.table_address(package.top_level, 0)?, BLOCK_SIZE)?);
+        assert!(package.data[TOP_HASH_TABLE_HASH..TOP_HASH_TABLE_HASH + 0x14] == top_hash);
+        let header_hash = sha1(&package.data[HASHED_HEADER_START..package.first_table_address]);
+        assert!(package.data[HEADER_HASH..HEADER_HASH + 0x14] == header_hash);
+        Ok(())
+    }
+
+    fn open_save(input: Vec<u8>) -> Result<StfsPackage> {
+        let mut package = StfsPackage::from_bytes(input)?;
+        package.find_save_file(|bytes| {
+            unreal::Deserializer::from_be_bytes::<Me3SaveGame>(&bytes)?;
+            Ok(())
+        })?;
+        Ok(package)
+    }
+
+    fn write_read_back(package: StfsPackage, save: &[u8]) -> Result<StfsPackage> {
+        let mut package = package;
+        package.write_save_file(save)?;
+
+        let package = open_save(package.to_vec())?;
+        assert!(package.read_file(package.save_file.unwrap())? == save);
+        check_hashes(&package)?;
+        Ok(package)
+    }
+
+    #[test]
+    fn open_write_con_package() -> Result<()> {
+        let save = fs::read("test/ME3Save360.xbsav")?;
+        let mut bigger_save = save.clone();
+        bigger_save.extend(vec![0xAB; 3 * BLOCK_SIZE]);
+
+        for (shift, is_secondary) in [(0, false), (1, false), (1, true)] {
+            let input = con_package("ME3Save360.xbsav", &save, shift, is_secondary);
+
+            // Open
+            let mut package = open_save(input.clone())?;
+            assert_eq!(package.table_shift, shift);
+            assert!(package.read_file(package.save_file.unwrap())? == save);
+
+            // Write the same save
+            package.write_save_file(&save)?;
+            assert!(package.to_vec() == input);
+
+            // Bigger then smaller save
+            let package = write_read_back(package, &bigger_save)?;
+            write_read_back(package, &save)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn write_multi_level_package() -> Result<()> {
+        let save = fs::read("test/ME3Save360.xbsav")?;
+        // Plus de blocs qu'une table de hash peut en contenir
+        let mut huge_save = save.clone();
+        huge_save.extend(vec![0xCD; (HASHES_PER_TABLE + 10) * BLOCK_SIZE]);
+
+        for (shift, is_secondary) in [(0, false), (1, true)] {
+            let input = con_package("ME3Save360.xbsav", &save, shift, is_secondary);
+            let package = open_save(input)?;
+            assert_eq!(package.top_level, 0);
+
+            let package = write_read_back(package, &huge_save)?;
+            assert_eq!(package.top_level, 1);
+
+            // The hash tables stay on 2 levels
+            let package = write_read_back(package, &save)?;
+            assert_eq!(package.top_level, 1);
+            write_read_back(package, &huge_save)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn open_write_me1_con_package() -> Result<()> {
+        let schema =
+            std::rc::Rc::new(ron::from_str(&fs::read_to_string("databases/me1_schema.ron")?)?);
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let mut me1_save_game: Me1SaveGame =
+            unreal::Deserializer::from_bytes_seed(Me1SaveGameSeed(&schema), &input)?;
+        me1_save_game.set_xbox360(true);
+        let save = unreal::Serializer::to_be_vec(&me1_save_game)?;
+
+        let mut input = con_package("ME1Save360.xbsav", &save, 1, false);
+        // Certificat + signature RSA du header, non recalculable
+        let signature = (0x4..0x22C).map(|i| i as u8).collect::<Vec<_>>();
+        input[0x4..0x22C].copy_from_slice(&signature);
+
+        let mut package = StfsPackage::from_bytes(input)?;
+        let me1_save_game = package.find_save_file(|bytes| {
+            Ok(unreal::Deserializer::from_be_bytes_seed(Me1SaveGameSeed(&schema), &bytes)?)
+        })?;
+        assert!(me1_save_game.is_xbox360());
+
+        // Write the save back
+        let output = unreal::Serializer::to_be_vec(&me1_save_game)?;
+        assert!(output == save);
+        let mut bigger_save = save.clone();
+        bigger_save.extend(vec![0xAB; 2 * BLOCK_SIZE]);
+        package.write_save_file(&bigger_save)?;
+        check_hashes(&package)?;
+
+        // The signature is kept as is, the package has to be re-signed
+        let output = package.to_vec();
+        assert!(output[0x4..0x22C] == signature);
+        let package = StfsPackage::from_bytes(output)?;
+        check_hashes(&package)?;
+        Ok(())
+    }
+
+    #[test]
+    fn failed_write_or_parse() -> Result<()> {
+        let save = fs::read("test/ME3Save360.xbsav")?;
+        let input = con_package("ME3Save360.xbsav", &save, 0, false);
+
+        // The parsing error is kept
+        let mut package = StfsPackage::from_bytes(input.clone())?;
+        let err = package.find_save_file(|_| -> Result<()> { bail!("Not a save") }).unwrap_err();
+        assert!(format!("{:#}", err).contains("Not a save"));
+
+        // A failed write leaves the package untouched
+        let mut package = open_save(input.clone())?;
+        let idx = package.save_file.unwrap();
+        package.entries[idx].is_consecutive = false;
+        let address = package.hash_entry_address(0, 2)?;
+        write_u24_be(&mut package.data, address + 0x15, END_OF_CHAIN);
+        let before = package.to_vec();
+        assert!(package.write_save_file(&save).is_err());
+        assert!(package.to_vec() == before);
+
+        Ok(())
+    }
+}
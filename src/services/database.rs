@@ -5,9 +5,18 @@ use yew::{prelude::*, ContextProvider};
 
 use crate::{
     save_data::{
-        mass_effect_1::plot_db::Me1PlotDb, mass_effect_1_le::item_db::Me1ItemDb,
-        mass_effect_1_le::player_class_db::Me1LePlayerClassDb, mass_effect_2::plot_db::Me2PlotDb,
-        mass_effect_3::plot_db::Me3PlotDb, shared::plot::RawPlotDb,
+        mass_effect_1::{plot_db::Me1PlotDb, schema::Me1Schema},
+        mass_effect_1_le::galaxy_db::Me1GalaxyDb,
+        mass_effect_1_le::item_db::Me1ItemDb,
+        mass_effect_1_le::player_class_db::Me1LePlayerClassDb,
+        mass_effect_2::plot_db::Me2PlotDb,
+        mass_effect_3::{player_variable_db::Me3PlayerVariableDb, plot_db::Me3PlotDb},
+        shared::{
+            appearance_db::AppearanceDb, dlc_db::DlcDb, face_code::FaceCodeDb, guid_db::GuidDb,
+            head_morph_conversion::HeadMorphConversionDb, head_morph_obj::HeadMorphTopologyDb,
+            head_morph_validation::HeadMorphValidationDb, level_repair::LevelRepairDb,
+            plot::RawPlotDb, teleport::TeleportDb,
+        },
     },
     services::rpc,
 };
@@ -17,10 +26,28 @@ pub enum Type {
     Me1Plot,
     Me1RawPlot,
     Me1Items,
+    Me1Schema,
+    Me1Galaxy,
+    Me1LeTeleport,
     Me2Plot,
     Me2RawPlot,
+    Me2Teleport,
+    Me2LevelRepair,
+    Me2Guid,
+    Me2Dlc,
     Me3Plot,
     Me3RawPlot,
+    Me3Teleport,
+    Me3LevelRepair,
+    Me3Guid,
+    Me3Dlc,
+    Me3PlayerVariables,
+    FaceCode,
+    HeadMorphConversion,
+    HeadMorphTopology,
+    HeadMorphValidation,
+    Me2Appearance,
+    Me3Appearance,
 }
 
 pub enum Database {
@@ -28,10 +55,28 @@ pub enum Database {
     Me1Plot(Me1PlotDb),
     Me1RawPlot(RawPlotDb),
     Me1Items(Me1ItemDb),
+    Me1Schema(Me1Schema),
+    Me1Galaxy(Me1GalaxyDb),
+    Me1LeTeleport(TeleportDb),
     Me2Plot(Me2PlotDb),
     Me2RawPlot(RawPlotDb),
+    Me2Teleport(TeleportDb),
+    Me2LevelRepair(LevelRepairDb),
+    Me2Guid(GuidDb),
+    Me2Dlc(DlcDb),
     Me3Plot(Me3PlotDb),
     Me3RawPlot(RawPlotDb),
+    Me3Teleport(TeleportDb),
+    Me3LevelRepair(LevelRepairDb),
+    Me3Guid(GuidDb),
+    Me3Dlc(DlcDb),
+    Me3PlayerVariables(Me3PlayerVariableDb),
+    FaceCode(FaceCodeDb),
+    HeadMorphConversion(HeadMorphConversionDb),
+    HeadMorphTopology(HeadMorphTopologyDb),
+    HeadMorphValidation(HeadMorphValidationDb),
+    Me2Appearance(AppearanceDb),
+    Me3Appearance(AppearanceDb),
 }
 
 pub enum Msg {
@@ -46,10 +91,28 @@ pub struct Databases {
     me1_plot: Option<Rc<Me1PlotDb>>,
     me1_raw_plot: Option<Rc<RawPlotDb>>,
     me1_item_db: Option<Rc<Me1ItemDb>>,
+    me1_schema: Option<Rc<Me1Schema>>,
+    me1_galaxy_db: Option<Rc<Me1GalaxyDb>>,
+    me1_le_teleport: Option<Rc<TeleportDb>>,
     me2_plot: Option<Rc<Me2PlotDb>>,
     me2_raw_plot: Option<Rc<RawPlotDb>>,
+    me2_teleport: Option<Rc<TeleportDb>>,
+    me2_level_repair: Option<Rc<LevelRepairDb>>,
+    me2_guid: Option<Rc<GuidDb>>,
+    me2_dlc: Option<Rc<DlcDb>>,
     me3_plot: Option<Rc<Me3PlotDb>>,
     me3_raw_plot: Option<Rc<RawPlotDb>>,
+    me3_teleport: Option<Rc<TeleportDb>>,
+    me3_level_repair: Option<Rc<LevelRepairDb>>,
+    me3_guid: Option<Rc<GuidDb>>,
+    me3_dlc: Option<Rc<DlcDb>>,
+    me3_player_variables: Option<Rc<Me3PlayerVariableDb>>,
+    face_code: Option<Rc<FaceCodeDb>>,
+    head_morph_conversion: Option<Rc<HeadMorphConversionDb>>,
+    head_morph_topology: Option<Rc<HeadMorphTopologyDb>>,
+    head_morph_validation: Option<Rc<HeadMorphValidationDb>>,
+    me2_appearance: Option<Rc<AppearanceDb>>,
+    me3_appearance: Option<Rc<AppearanceDb>>,
     load_callback: Callback<Type>,
 }
 
@@ -82,6 +145,27 @@ impl Databases {
         self.me1_item_db
     }
 
+    pub fn get_me1_schema(self) -> Option<Rc<Me1Schema>> {
+        if self.me1_schema.is_none() {
+            self.load_database(Type::Me1Schema);
+        }
+        self.me1_schema
+    }
+
+    pub fn get_me1_galaxy_db(self) -> Option<Rc<Me1GalaxyDb>> {
+        if self.me1_galaxy_db.is_none() {
+            self.load_database(Type::Me1Galaxy);
+        }
+        self.me1_galaxy_db
+    }
+
+    pub fn get_me1_le_teleport(self) -> Option<Rc<TeleportDb>> {
+        if self.me1_le_teleport.is_none() {
+            self.load_database(Type::Me1LeTeleport);
+        }
+        self.me1_le_teleport
+    }
+
     pub fn get_me2_plot(self) -> Option<Rc<Me2PlotDb>> {
         if self.me2_plot.is_none() {
             self.load_database(Type::Me2Plot);
@@ -96,6 +180,34 @@ impl Databases {
         self.me2_raw_plot
     }
 
+    pub fn get_me2_teleport(self) -> Option<Rc<TeleportDb>> {
+        if self.me2_teleport.is_none() {
+            self.load_database(Type::Me2Teleport);
+        }
+        self.me2_teleport
+    }
+
+    pub fn get_me2_level_repair(self) -> Option<Rc<LevelRepairDb>> {
+        if self.me2_level_repair.is_none() {
+            self.load_database(Type::Me2LevelRepair);
+        }
+        self.me2_level_repair
+    }
+
+    pub fn get_me2_guid(self) -> Option<Rc<GuidDb>> {
+        if self.me2_guid.is_none() {
+            self.load_database(Type::Me2Guid);
+        }
+        self.me2_guid
+    }
+
+    pub fn get_me2_dlc(self) -> Option<Rc<DlcDb>> {
+        if self.me2_dlc.is_none() {
+            self.load_database(Type::Me2Dlc);
+        }
+        self.me2_dlc
+    }
+
     pub fn get_me3_plot(self) -> Option<Rc<Me3PlotDb>> {
         if self.me3_plot.is_none() {
             self.load_database(Type::Me3Plot);
@@ -110,6 +222,83 @@ impl Databases {
         self.me3_raw_plot
     }
 
+    pub fn get_me3_teleport(self) -> Option<Rc<TeleportDb>> {
+        if self.me3_teleport.is_none() {
+            self.load_database(Type::Me3Teleport);
+        }
+        self.me3_teleport
+    }
+
+    pub fn get_me3_level_repair(self) -> Option<Rc<LevelRepairDb>> {
+        if self.me3_level_repair.is_none() {
+            self.load_database(Type::Me3LevelRepair);
+        }
+        self.me3_level_repair
+    }
+
+    pub fn get_me3_guid(self) -> Option<Rc<GuidDb>> {
+        if self.me3_guid.is_none() {
+            self.load_database(Type::Me3Guid);
+        }
+        self.me3_guid
+    }
+
+    pub fn get_me3_dlc(self) -> Option<Rc<DlcDb>> {
+        if self.me3_dlc.is_none() {
+            self.load_database(Type::Me3Dlc);
+        }
+        self.me3_dlc
+    }
+
+    pub fn get_me3_player_variables(self) -> Option<Rc<Me3PlayerVariableDb>> {
+        if self.me3_player_variables.is_none() {
+            self.load_database(Type::Me3PlayerVariables);
+        }
+        self.me3_player_variables
+    }
+
+    pub fn get_face_code(self) -> Option<Rc<FaceCodeDb>> {
+        if self.face_code.is_none() {
+            self.load_database(Type::FaceCode);
+        }
+        self.face_code
+    }
+
+    pub fn get_head_morph_conversion(self) -> Option<Rc<HeadMorphConversionDb>> {
+        if self.head_morph_conversion.is_none() {
+            self.load_database(Type::HeadMorphConversion);
+        }
+        self.head_morph_conversion
+    }
+
+    pub fn get_head_morph_topology(self) -> Option<Rc<HeadMorphTopologyDb>> {
+        if self.head_morph_topology.is_none() {
+            self.load_database(Type::HeadMorphTopology);
+        }
+        self.head_morph_topology
+    }
+
+    pub fn get_head_morph_validation(self) -> Option<Rc<HeadMorphValidationDb>> {
+        if self.head_morph_validation.is_none() {
+            self.load_database(Type::HeadMorphValidation);
+        }
+        self.head_morph_validation
+    }
+
+    pub fn get_me2_appearance(self) -> Option<Rc<AppearanceDb>> {
+        if self.me2_appearance.is_none() {
+            self.load_database(Type::Me2Appearance);
+        }
+        self.me2_appearance
+    }
+
+    pub fn get_me3_appearance(self) -> Option<Rc<AppearanceDb>> {
+        if self.me3_appearance.is_none() {
+            self.load_database(Type::Me3Appearance);
+        }
+        self.me3_appearance
+    }
+
     fn load_database(&self, db_type: Type) {
         self.load_callback.emit(db_type);
     }
@@ -122,20 +311,56 @@ impl PartialEq for Databases {
             me1_plot,
             me1_raw_plot,
             me1_item_db,
+            me1_schema,
+            me1_galaxy_db,
+            me1_le_teleport,
             me2_plot,
             me2_raw_plot,
+            me2_teleport,
+            me2_level_repair,
+            me2_guid,
+            me2_dlc,
             me3_plot,
             me3_raw_plot,
+            me3_teleport,
+            me3_level_repair,
+            me3_guid,
+            me3_dlc,
+            me3_player_variables,
+            face_code,
+            head_morph_conversion,
+            head_morph_topology,
+            head_morph_validation,
+            me2_appearance,
+            me3_appearance,
             load_callback: _,
         } = self;
         me1_le_player_classes.is_some() == other.me1_le_player_classes.is_some()
             && me1_plot.is_some() == other.me1_plot.is_some()
             && me1_raw_plot.is_some() == other.me1_raw_plot.is_some()
             && me1_item_db.is_some() == other.me1_item_db.is_some()
+            && me1_schema.is_some() == other.me1_schema.is_some()
+            && me1_galaxy_db.is_some() == other.me1_galaxy_db.is_some()
+            && me1_le_teleport.is_some() == other.me1_le_teleport.is_some()
             && me2_plot.is_some() == other.me2_plot.is_some()
             && me2_raw_plot.is_some() == other.me2_raw_plot.is_some()
+            && me2_teleport.is_some() == other.me2_teleport.is_some()
+            && me2_level_repair.is_some() == other.me2_level_repair.is_some()
+            && me2_guid.is_some() == other.me2_guid.is_some()
+            && me2_dlc.is_some() == other.me2_dlc.is_some()
             && me3_plot.is_some() == other.me3_plot.is_some()
             && me3_raw_plot.is_some() == other.me3_raw_plot.is_some()
+            && me3_teleport.is_some() == other.me3_teleport.is_some()
+            && me3_level_repair.is_some() == other.me3_level_repair.is_some()
+            && me3_guid.is_some() == other.me3_guid.is_some()
+            && me3_dlc.is_some() == other.me3_dlc.is_some()
+            && me3_player_variables.is_some() == other.me3_player_variables.is_some()
+            && face_code.is_some() == other.face_code.is_some()
+            && head_morph_conversion.is_some() == other.head_morph_conversion.is_some()
+            && head_morph_topology.is_some() == other.head_morph_topology.is_some()
+            && head_morph_validation.is_some() == other.head_morph_validation.is_some()
+            && me2_appearance.is_some() == other.me2_appearance.is_some()
+            && me3_appearance.is_some() == other.me3_appearance.is_some()
     }
 }
 
@@ -183,6 +408,22 @@ impl Component for DatabaseProvider {
                         let db = ron::from_str(&response)?;
                         Ok(Database::Me1Items(db))
                     }),
+                    Type::Me1Schema => Self::load_db(ctx, "databases/me1_schema.ron", |response| {
+                        let db = ron::from_str(&response)?;
+                        Ok(Database::Me1Schema(db))
+                    }),
+                    Type::Me1Galaxy => {
+                        Self::load_db(ctx, "databases/me1_galaxy_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me1Galaxy(db))
+                        })
+                    }
+                    Type::Me1LeTeleport => {
+                        Self::load_db(ctx, "databases/me1_le_teleport_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me1LeTeleport(db))
+                        })
+                    }
                     Type::Me2Plot => Self::load_db(ctx, "databases/me2_plot_db.ron", |response| {
                         let db = ron::from_str(&response)?;
                         Ok(Database::Me2Plot(db))
@@ -193,6 +434,26 @@ impl Component for DatabaseProvider {
                             Ok(Database::Me2RawPlot(db))
                         })
                     }
+                    Type::Me2Teleport => {
+                        Self::load_db(ctx, "databases/me2_teleport_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me2Teleport(db))
+                        })
+                    }
+                    Type::Me2LevelRepair => {
+                        Self::load_db(ctx, "databases/me2_level_repair_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me2LevelRepair(db))
+                        })
+                    }
+                    Type::Me2Guid => Self::load_db(ctx, "databases/me2_guid_db.ron", |response| {
+                        let db = ron::from_str(&response)?;
+                        Ok(Database::Me2Guid(db))
+                    }),
+                    Type::Me2Dlc => Self::load_db(ctx, "databases/me2_dlc_db.ron", |response| {
+                        let db = ron::from_str(&response)?;
+                        Ok(Database::Me2Dlc(db))
+                    }),
                     Type::Me3Plot => Self::load_db(ctx, "databases/me3_plot_db.ron", |response| {
                         let db = ron::from_str(&response)?;
                         Ok(Database::Me3Plot(db))
@@ -203,6 +464,68 @@ impl Component for DatabaseProvider {
                             Ok(Database::Me3RawPlot(db))
                         })
                     }
+                    Type::Me3Teleport => {
+                        Self::load_db(ctx, "databases/me3_teleport_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me3Teleport(db))
+                        })
+                    }
+                    Type::Me3LevelRepair => {
+                        Self::load_db(ctx, "databases/me3_level_repair_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me3LevelRepair(db))
+                        })
+                    }
+                    Type::Me3Guid => Self::load_db(ctx, "databases/me3_guid_db.ron", |response| {
+                        let db = ron::from_str(&response)?;
+                        Ok(Database::Me3Guid(db))
+                    }),
+                    Type::Me3Dlc => Self::load_db(ctx, "databases/me3_dlc_db.ron", |response| {
+                        let db = ron::from_str(&response)?;
+                        Ok(Database::Me3Dlc(db))
+                    }),
+                    Type::Me3PlayerVariables => {
+                        Self::load_db(ctx, "databases/me3_player_variable_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me3PlayerVariables(db))
+                        })
+                    }
+                    Type::FaceCode => {
+                        Self::load_db(ctx, "databases/face_code_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::FaceCode(db))
+                        })
+                    }
+                    Type::HeadMorphConversion => {
+                        Self::load_db(ctx, "databases/head_morph_conversion_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::HeadMorphConversion(db))
+                        })
+                    }
+                    Type::HeadMorphTopology => {
+                        Self::load_db(ctx, "databases/head_morph_topology_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::HeadMorphTopology(db))
+                        })
+                    }
+                    Type::HeadMorphValidation => {
+                        Self::load_db(ctx, "databases/head_morph_validation_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::HeadMorphValidation(db))
+                        })
+                    }
+                    Type::Me2Appearance => {
+                        Self::load_db(ctx, "databases/me2_appearance_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me2Appearance(db))
+                        })
+                    }
+                    Type::Me3Appearance => {
+                        Self::load_db(ctx, "databases/me3_appearance_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me3Appearance(db))
+                        })
+                    }
                 }
                 false
             }
@@ -220,18 +543,72 @@ impl Component for DatabaseProvider {
                     Database::Me1Items(db) => {
                         self.dbs.me1_item_db = Some(db.into());
                     }
+                    Database::Me1Schema(db) => {
+                        self.dbs.me1_schema = Some(db.into());
+                    }
+                    Database::Me1Galaxy(db) => {
+                        self.dbs.me1_galaxy_db = Some(db.into());
+                    }
+                    Database::Me1LeTeleport(db) => {
+                        self.dbs.me1_le_teleport = Some(db.into());
+                    }
                     Database::Me2Plot(db) => {
                         self.dbs.me2_plot = Some(db.into());
                     }
                     Database::Me2RawPlot(db) => {
                         self.dbs.me2_raw_plot = Some(db.into());
                     }
+                    Database::Me2Teleport(db) => {
+                        self.dbs.me2_teleport = Some(db.into());
+                    }
+                    Database::Me2LevelRepair(db) => {
+                        self.dbs.me2_level_repair = Some(db.into());
+                    }
+                    Database::Me2Guid(db) => {
+                        self.dbs.me2_guid = Some(db.into());
+                    }
+                    Database::Me2Dlc(db) => {
+                        self.dbs.me2_dlc = Some(db.into());
+                    }
                     Database::Me3Plot(db) => {
                         self.dbs.me3_plot = Some(db.into());
                     }
                     Database::Me3RawPlot(db) => {
                         self.dbs.me3_raw_plot = Some(db.into());
                     }
+                    Database::Me3Teleport(db) => {
+                        self.dbs.me3_teleport = Some(db.into());
+                    }
+                    Database::Me3LevelRepair(db) => {
+                        self.dbs.me3_level_repair = Some(db.into());
+                    }
+                    Database::Me3Guid(db) => {
+                        self.dbs.me3_guid = Some(db.into());
+                    }
+                    Database::Me3Dlc(db) => {
+                        self.dbs.me3_dlc = Some(db.into());
+                    }
+                    Database::Me3PlayerVariables(db) => {
+                        self.dbs.me3_player_variables = Some(db.into());
+                    }
+                    Database::FaceCode(db) => {
+                        self.dbs.face_code = Some(db.into());
+                    }
+                    Database::HeadMorphConversion(db) => {
+                        self.dbs.head_morph_conversion = Some(db.into());
+                    }
+                    Database::HeadMorphTopology(db) => {
+                        self.dbs.head_morph_topology = Some(db.into());
+                    }
+                    Database::HeadMorphValidation(db) => {
+                        self.dbs.head_morph_validation = Some(db.into());
+                    }
+                    Database::Me2Appearance(db) => {
+                        self.dbs.me2_appearance = Some(db.into());
+                    }
+                    Database::Me3Appearance(db) => {
+                        self.dbs.me3_appearance = Some(db.into());
+                    }
                 }
                 true
             }
@@ -86,6 +86,30 @@ pub async fn export_head_morph_dialog() -> Result<Option<PathBuf>> {
     call("export_head_morph_dialog").await
 }
 
+pub async fn import_head_morph_obj() -> Result<Option<RpcFile>> {
+    call("import_head_morph_obj").await
+}
+
+pub async fn export_head_morph_obj_dialog() -> Result<Option<PathBuf>> {
+    call("export_head_morph_obj_dialog").await
+}
+
+pub async fn head_morph_library() -> Result<Vec<RpcFile>> {
+    call("head_morph_library").await
+}
+
+pub async fn add_to_head_morph_library(entry: HeadMorphLibraryEntry) -> Result<()> {
+    call_with_params("add_to_head_morph_library", entry).await
+}
+
+pub async fn open_head_morph_from_library(meta_path: PathBuf) -> Result<RpcFile> {
+    call_with_params("open_head_morph_from_library", meta_path).await
+}
+
+pub async fn remove_from_head_morph_library(meta_path: PathBuf) -> Result<()> {
+    call_with_params("remove_from_head_morph_library", meta_path).await
+}
+
 pub async fn load_database(path: &str) -> Result<RpcFile> {
     call_with_params("load_database", path).await
 }
@@ -97,6 +121,13 @@ pub struct DialogParams {
     pub filters: Vec<(&'static str, Vec<&'static str>)>,
 }
 
+#[derive(Serialize)]
+pub struct HeadMorphLibraryEntry {
+    pub file_stem: String,
+    pub meta: Base64File,
+    pub head_morph: Base64File,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct RpcFile {
     pub path: PathBuf,
@@ -5,20 +5,30 @@ use crc::{Crc, CRC_32_BZIP2};
 use gloo::utils;
 use ron::ser::PrettyConfig;
 use serde::Deserialize;
-use yew::{prelude::*, ContextProvider};
+use yew::{context::ContextHandle, prelude::*, ContextProvider};
 
 use crate::{
-    gui::Theme,
+    gui::{format_code, Theme},
     save_data::mass_effect_1_le::Me1LeMagicNumber,
     save_data::{
-        mass_effect_1::{Me1MagicNumber, Me1SaveGame},
+        mass_effect_1::{schema::Me1Schema, Me1MagicNumber, Me1SaveGame, Me1SaveGameSeed},
         mass_effect_1_le::{Me1LeSaveData, Me1LeSaveGame, Me1LeVersion},
         mass_effect_2::{Me2LeSaveGame, Me2LeVersion, Me2SaveGame, Me2Version},
         mass_effect_3::{Me3SaveGame, Me3Version},
-        shared::appearance::HeadMorph,
+        shared::{
+            appearance::HeadMorph,
+            head_morph_conversion::{HeadMorphConversionDb, HeadMorphGame},
+            head_morph_library::HeadMorphMeta,
+            head_morph_obj::{self, HeadMorphTopologyDb},
+            head_morph_validation::HeadMorphValidationDb,
+        },
+        stfs::StfsPackage,
         RcRef,
     },
-    services::rpc::{self, Base64File, DialogParams, RpcFile},
+    services::{
+        database::Databases,
+        rpc::{self, Base64File, DialogParams, HeadMorphLibraryEntry, RpcFile},
+    },
     unreal,
 };
 
@@ -26,29 +36,78 @@ use super::drop_handler::DropHandler;
 
 #[derive(Clone)]
 pub enum SaveGame {
-    MassEffect1 { file_path: PathBuf, save_game: RcRef<Me1SaveGame> },
-    MassEffect1Le { file_path: PathBuf, save_game: RcRef<Me1LeSaveGame> },
-    MassEffect1LePs4 { file_path: PathBuf, save_game: RcRef<Me1LeSaveData> },
-    MassEffect2 { file_path: PathBuf, save_game: RcRef<Me2SaveGame> },
-    MassEffect2Le { file_path: PathBuf, save_game: RcRef<Me2LeSaveGame> },
-    MassEffect3 { file_path: PathBuf, save_game: RcRef<Me3SaveGame> },
+    MassEffect1 {
+        file_path: PathBuf,
+        save_game: RcRef<Me1SaveGame>,
+        package: Option<RcRef<StfsPackage>>,
+    },
+    MassEffect1Le {
+        file_path: PathBuf,
+        save_game: RcRef<Me1LeSaveGame>,
+    },
+    MassEffect1LePs4 {
+        file_path: PathBuf,
+        save_game: RcRef<Me1LeSaveData>,
+    },
+    MassEffect2 {
+        file_path: PathBuf,
+        save_game: RcRef<Me2SaveGame>,
+        package: Option<RcRef<StfsPackage>>,
+    },
+    MassEffect2Le {
+        file_path: PathBuf,
+        save_game: RcRef<Me2LeSaveGame>,
+    },
+    MassEffect2LePs4 {
+        file_path: PathBuf,
+        save_game: RcRef<Me2LeSaveGame>,
+    },
+    MassEffect3 {
+        file_path: PathBuf,
+        save_game: RcRef<Me3SaveGame>,
+        package: Option<RcRef<StfsPackage>>,
+    },
+    MassEffect3LePs4 {
+        file_path: PathBuf,
+        save_game: RcRef<Me3SaveGame>,
+    },
 }
 
 pub enum Action {
     OpenSave,
     SaveSave,
     ReloadSave,
+    ConvertToLegendary,
     ImportHeadMorph(Callback<HeadMorph>),
+    ImportHeadMorphFromSave(
+        Rc<HeadMorphConversionDb>,
+        HeadMorphGame,
+        Option<RcRef<HeadMorph>>,
+        Callback<HeadMorph>,
+    ),
     ExportHeadMorph(RcRef<HeadMorph>),
+    ImportHeadMorphObj(RcRef<HeadMorph>, Callback<()>),
+    ExportHeadMorphObj(RcRef<HeadMorph>, Rc<HeadMorphTopologyDb>),
+    LoadHeadMorphLibrary(Callback<Vec<(PathBuf, HeadMorphMeta)>>),
+    AddHeadMorphToLibrary(HeadMorphMeta, RcRef<HeadMorph>, Callback<()>),
+    RemoveHeadMorphFromLibrary(PathBuf, Callback<()>),
+    ImportHeadMorphFromLibrary(PathBuf, Callback<HeadMorph>),
 }
 
 pub enum Msg {
+    DatabaseLoaded(Databases),
     Action(Action),
-    SaveOpened(SaveGame),
+    SaveOpened(SaveGame, &'static str),
     SaveDropped(Result<(String, Vec<u8>)>),
-    SaveSaved,
+    SaveSaved(&'static str),
+    SaveConverted(SaveGame),
     HeadMorphImported(HeadMorph, Callback<HeadMorph>),
     HeadMorphExported,
+    HeadMorphObjImported(Callback<()>),
+    HeadMorphLibraryLoaded(Vec<(PathBuf, HeadMorphMeta)>, Callback<Vec<(PathBuf, HeadMorphMeta)>>),
+    HeadMorphLibraryChanged(&'static str, Callback<()>),
+    SaveAnyway,
+    CancelSave,
     Error(Error),
     Noop,
 }
@@ -85,6 +144,13 @@ impl PartialEq for SaveHandler {
 pub struct SaveHandlerProvider {
     _drop_handler: DropHandler,
     save_handler: SaveHandler,
+    _db_handle: ContextHandle<Databases>,
+    me1_schema: Option<Rc<Me1Schema>>,
+    // La save de la ligne de commande attend le schéma ME1 pour être ouverte
+    command_line_save_pending: bool,
+    validation_db: Option<Rc<HeadMorphValidationDb>>,
+    // Problèmes du head morph en attente de confirmation avant la sauvegarde
+    save_warning: Option<(HeadMorphGame, Vec<String>)>,
 }
 
 impl Component for SaveHandlerProvider {
@@ -95,22 +161,52 @@ impl Component for SaveHandlerProvider {
         let _drop_handler = DropHandler::new(ctx.link().callback(Msg::SaveDropped));
         let save_handler =
             SaveHandler { save_game: None, callback: ctx.link().callback(Msg::Action) };
-        Self::open_command_line_save(ctx);
 
-        SaveHandlerProvider { _drop_handler, save_handler }
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        let me1_schema = databases.clone().get_me1_schema();
+        if me1_schema.is_some() {
+            Self::open_command_line_save(ctx, me1_schema.clone());
+        }
+
+        SaveHandlerProvider {
+            _drop_handler,
+            save_handler,
+            _db_handle,
+            command_line_save_pending: me1_schema.is_none(),
+            me1_schema,
+            validation_db: databases.get_head_morph_validation(),
+            save_warning: None,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.me1_schema = dbs.clone().get_me1_schema();
+                self.validation_db = dbs.get_head_morph_validation();
+                if self.command_line_save_pending && self.me1_schema.is_some() {
+                    self.command_line_save_pending = false;
+                    Self::open_command_line_save(ctx, self.me1_schema.clone());
+                }
+                false
+            }
             // Actions
             Msg::Action(action) => {
                 match action {
                     Action::OpenSave => {
                         let last_dir = self.save_handler.save_game.is_some();
-                        Self::open_save(ctx, last_dir);
+                        Self::open_save(ctx, last_dir, self.me1_schema.clone());
                     }
                     Action::SaveSave => {
                         if let Some(ref save_game) = self.save_handler.save_game {
+                            self.save_warning = self.validate_head_morph(save_game);
+                            if self.save_warning.is_some() {
+                                return true;
+                            }
                             Self::save_save(ctx, save_game);
                         }
                     }
@@ -122,35 +218,82 @@ impl Component for SaveHandlerProvider {
                                 | SaveGame::MassEffect1LePs4 { file_path, .. }
                                 | SaveGame::MassEffect2 { file_path, .. }
                                 | SaveGame::MassEffect2Le { file_path, .. }
-                                | SaveGame::MassEffect3 { file_path, .. } => {
-                                    Self::reload_save(ctx, file_path.clone())
+                                | SaveGame::MassEffect2LePs4 { file_path, .. }
+                                | SaveGame::MassEffect3 { file_path, .. }
+                                | SaveGame::MassEffect3LePs4 { file_path, .. } => {
+                                    Self::reload_save(
+                                        ctx,
+                                        file_path.clone(),
+                                        self.me1_schema.clone(),
+                                    )
                                 }
                             }
                         }
                     }
+                    Action::ConvertToLegendary => {
+                        if let Some(ref save_game) = self.save_handler.save_game {
+                            Self::convert_to_legendary(ctx, save_game);
+                        }
+                    }
                     Action::ImportHeadMorph(callback) => Self::import_head_morph(ctx, callback),
+                    Action::ImportHeadMorphFromSave(conversion_db, to, reference, callback) => {
+                        Self::import_head_morph_from_save(
+                            ctx,
+                            conversion_db,
+                            to,
+                            reference,
+                            callback,
+                            self.me1_schema.clone(),
+                        )
+                    }
                     Action::ExportHeadMorph(head_morph) => Self::export_head_morph(ctx, head_morph),
+                    Action::ImportHeadMorphObj(head_morph, callback) => {
+                        Self::import_head_morph_obj(ctx, head_morph, callback)
+                    }
+                    Action::ExportHeadMorphObj(head_morph, topology_db) => {
+                        Self::export_head_morph_obj(ctx, head_morph, topology_db)
+                    }
+                    Action::LoadHeadMorphLibrary(callback) => {
+                        Self::load_head_morph_library(ctx, callback)
+                    }
+                    Action::AddHeadMorphToLibrary(meta, head_morph, callback) => {
+                        Self::add_head_morph_to_library(ctx, meta, head_morph, callback)
+                    }
+                    Action::RemoveHeadMorphFromLibrary(meta_path, callback) => {
+                        Self::remove_head_morph_from_library(ctx, meta_path, callback)
+                    }
+                    Action::ImportHeadMorphFromLibrary(meta_path, callback) => {
+                        Self::import_head_morph_from_library(ctx, meta_path, callback)
+                    }
                 }
                 false
             }
             // Messages
-            Msg::SaveOpened(save_game) => {
+            Msg::SaveOpened(save_game, notification) => {
                 self.save_handler.save_game = Some(save_game.into());
                 self.change_theme();
-                ctx.props().onnotification.emit("Opened");
+                ctx.props().onnotification.emit(notification);
                 true
             }
             Msg::SaveDropped(result) => {
                 match result {
-                    Ok((file_name, bytes)) => Self::open_dropped_file(ctx, file_name, bytes),
+                    Ok((file_name, bytes)) => {
+                        Self::open_dropped_file(ctx, file_name, bytes, self.me1_schema.as_ref())
+                    }
                     Err(err) => ctx.props().onerror.emit(err),
                 }
                 false
             }
-            Msg::SaveSaved => {
-                ctx.props().onnotification.emit("Saved");
+            Msg::SaveSaved(notification) => {
+                ctx.props().onnotification.emit(notification);
                 false
             }
+            Msg::SaveConverted(save_game) => {
+                self.save_handler.save_game = Some(save_game.into());
+                self.change_theme();
+                ctx.props().onnotification.emit("Converted");
+                true
+            }
             Msg::HeadMorphImported(head_morph, callback) => {
                 callback.emit(head_morph);
                 ctx.props().onnotification.emit("Imported");
@@ -160,6 +303,31 @@ impl Component for SaveHandlerProvider {
                 ctx.props().onnotification.emit("Exported");
                 false
             }
+            Msg::HeadMorphObjImported(callback) => {
+                callback.emit(());
+                ctx.props().onnotification.emit("Imported");
+                false
+            }
+            Msg::HeadMorphLibraryLoaded(library, callback) => {
+                callback.emit(library);
+                false
+            }
+            Msg::HeadMorphLibraryChanged(notification, callback) => {
+                callback.emit(());
+                ctx.props().onnotification.emit(notification);
+                false
+            }
+            Msg::SaveAnyway => {
+                self.save_warning = None;
+                if let Some(ref save_game) = self.save_handler.save_game {
+                    Self::save_save(ctx, save_game);
+                }
+                true
+            }
+            Msg::CancelSave => {
+                self.save_warning = None;
+                true
+            }
             Msg::Error(err) => {
                 ctx.props().onerror.emit(err);
                 false
@@ -173,23 +341,28 @@ impl Component for SaveHandlerProvider {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let save_warning =
+            self.save_warning.as_ref().map(|warning| Self::save_warning(ctx, warning));
         html! {
-            <ContextProvider<SaveHandler> context={self.save_handler.clone()}>
-                { ctx.props().children.clone() }
-            </ContextProvider<SaveHandler>>
+            <>
+                <ContextProvider<SaveHandler> context={self.save_handler.clone()}>
+                    { ctx.props().children.clone() }
+                </ContextProvider<SaveHandler>>
+                { for save_warning }
+            </>
         }
     }
 }
 
 impl SaveHandlerProvider {
-    fn open_save(ctx: &Context<Self>, last_dir: bool) {
+    fn open_save(ctx: &Context<Self>, last_dir: bool, me1_schema: Option<Rc<Me1Schema>>) {
         ctx.link().send_future(async move {
             let handle_save = async {
                 let has_rpc_file = rpc::open_save(last_dir).await?;
                 let result = match has_rpc_file {
                     Some(rpc_file) => {
                         let RpcFile { path, file } = rpc_file;
-                        Self::deserialize(path, file.decode()?).map(Some)?
+                        Self::deserialize(path, file.decode()?, me1_schema.as_ref()).map(Some)?
                     }
                     None => None,
                 };
@@ -197,21 +370,21 @@ impl SaveHandlerProvider {
             };
 
             match handle_save.await.context("Failed to open the save") {
-                Ok(Some(save_game)) => Msg::SaveOpened(save_game),
+                Ok(Some((save_game, notification))) => Msg::SaveOpened(save_game, notification),
                 Ok(None) => Msg::Noop,
                 Err(err) => Msg::Error(err),
             }
         });
     }
 
-    fn open_command_line_save(ctx: &Context<Self>) {
+    fn open_command_line_save(ctx: &Context<Self>, me1_schema: Option<Rc<Me1Schema>>) {
         ctx.link().send_future(async move {
             let handle_save = async {
                 let has_rpc_file = rpc::open_command_line_save().await?;
                 let result = match has_rpc_file {
                     Some(rpc_file) => {
                         let RpcFile { path, file } = rpc_file;
-                        Self::deserialize(path, file.decode()?).map(Some)?
+                        Self::deserialize(path, file.decode()?, me1_schema.as_ref()).map(Some)?
                     }
                     None => None,
                 };
@@ -219,19 +392,21 @@ impl SaveHandlerProvider {
             };
 
             match handle_save.await.context("Failed to open the save") {
-                Ok(Some(save_game)) => Msg::SaveOpened(save_game),
+                Ok(Some((save_game, notification))) => Msg::SaveOpened(save_game, notification),
                 Ok(None) => Msg::Noop,
                 Err(err) => Msg::Error(err),
             }
         });
     }
 
-    fn open_dropped_file(ctx: &Context<Self>, file_name: String, bytes: Vec<u8>) {
+    fn open_dropped_file(
+        ctx: &Context<Self>, file_name: String, bytes: Vec<u8>, me1_schema: Option<&Rc<Me1Schema>>,
+    ) {
         ctx.link().send_message({
-            let deserialize = || Self::deserialize(file_name.into(), bytes);
+            let deserialize = || Self::deserialize(file_name.into(), bytes, me1_schema);
 
             match deserialize().context("Failed to open the save") {
-                Ok(save_game) => Msg::SaveOpened(save_game),
+                Ok((save_game, notification)) => Msg::SaveOpened(save_game, notification),
                 Err(err) => Msg::Error(err),
             }
         });
@@ -239,9 +414,18 @@ impl SaveHandlerProvider {
 
     fn save_save(ctx: &Context<Self>, save_game: &Rc<SaveGame>) {
         let (path, filters) = match save_game.as_ref() {
-            SaveGame::MassEffect1 { ref file_path, .. } => {
-                (file_path.clone(), vec![("Mass Effect 1 save", vec!["MassEffectSave"])])
+            SaveGame::MassEffect1 { ref file_path, package: Some(_), .. }
+            | SaveGame::MassEffect2 { ref file_path, package: Some(_), .. }
+            | SaveGame::MassEffect3 { ref file_path, package: Some(_), .. } => {
+                (file_path.clone(), vec![("XBOX 360 package (unsigned)", vec!["*"])])
             }
+            SaveGame::MassEffect1 { ref file_path, .. } => (
+                file_path.clone(),
+                vec![
+                    ("Mass Effect 1 save", vec!["MassEffectSave"]),
+                    ("Mass Effect 1 XBOX 360 save", vec!["xbsav"]),
+                ],
+            ),
             SaveGame::MassEffect1Le { ref file_path, .. } => {
                 (file_path.clone(), vec![("Mass Effect 1 Legendary PC save", vec!["pcsav"])])
             }
@@ -258,6 +442,9 @@ impl SaveHandlerProvider {
             SaveGame::MassEffect2Le { ref file_path, .. } => {
                 (file_path.clone(), vec![("Mass Effect 2 Legendary save", vec!["pcsav"])])
             }
+            SaveGame::MassEffect2LePs4 { ref file_path, .. } => {
+                (file_path.clone(), vec![("Mass Effect 2 Legendary PS4 save", vec!["ps4sav"])])
+            }
             SaveGame::MassEffect3 { ref file_path, .. } => (
                 file_path.clone(),
                 vec![
@@ -265,6 +452,19 @@ impl SaveHandlerProvider {
                     ("Mass Effect 3 XBOX 360 save", vec!["xbsav"]),
                 ],
             ),
+            SaveGame::MassEffect3LePs4 { ref file_path, .. } => {
+                (file_path.clone(), vec![("Mass Effect 3 Legendary PS4 save", vec!["ps4sav"])])
+            }
+        };
+
+        // La signature du package n'est pas refaite, il doit être resigné avant d'être utilisé
+        let notification = match save_game.as_ref() {
+            SaveGame::MassEffect1 { package: Some(_), .. }
+            | SaveGame::MassEffect2 { package: Some(_), .. }
+            | SaveGame::MassEffect3 { package: Some(_), .. } => {
+                "Saved, re-sign the package before use"
+            }
+            _ => "Saved",
         };
 
         let save_game = Rc::clone(save_game);
@@ -283,29 +483,31 @@ impl SaveHandlerProvider {
             };
 
             match handle_save.await.context("Failed to save the save") {
-                Ok(false) => Msg::SaveSaved,
+                Ok(false) => Msg::SaveSaved(notification),
                 Ok(true) => Msg::Noop,
                 Err(err) => Msg::Error(err),
             }
         });
     }
 
-    fn reload_save(ctx: &Context<Self>, path: PathBuf) {
+    fn reload_save(ctx: &Context<Self>, path: PathBuf, me1_schema: Option<Rc<Me1Schema>>) {
         ctx.link().send_future(async move {
             let handle_save = async move {
                 let rpc_file = rpc::reload_save(path).await?;
                 let RpcFile { path, file } = rpc_file;
-                Self::deserialize(path, file.decode()?)
+                Self::deserialize(path, file.decode()?, me1_schema.as_ref())
             };
 
             match handle_save.await.context("Failed to reload the save") {
-                Ok(save_game) => Msg::SaveOpened(save_game),
+                Ok((save_game, notification)) => Msg::SaveOpened(save_game, notification),
                 Err(err) => Msg::Error(err),
             }
         });
     }
 
-    fn deserialize(file_path: PathBuf, input: Vec<u8>) -> Result<SaveGame> {
+    fn deserialize(
+        file_path: PathBuf, input: Vec<u8>, me1_schema: Option<&Rc<Me1Schema>>,
+    ) -> Result<(SaveGame, &'static str)> {
         fn header<'de, T>(header: &'de [u8]) -> Result<T, unreal::Error>
         where
             T: Deserialize<'de>,
@@ -313,12 +515,36 @@ impl SaveHandlerProvider {
             unreal::Deserializer::from_bytes::<T>(header)
         }
 
-        let save_game = if header::<Me1MagicNumber>(&input).is_ok() {
-            // ME1
-            SaveGame::MassEffect1 {
-                file_path,
-                save_game: unreal::Deserializer::from_bytes(&input)?,
+        let mut notification = "Opened";
+        let save_game = if StfsPackage::is_stfs(&input) {
+            // Xbox 360 package
+            let mut package = StfsPackage::from_bytes(input)?;
+            let (save_game, package_notification) = package
+                .find_save_file(|file| Self::deserialize(file_path.clone(), file, me1_schema))?;
+            notification = package_notification;
+            let package = Some(RcRef::new(package));
+            match save_game {
+                SaveGame::MassEffect1 { file_path, save_game, .. } => {
+                    SaveGame::MassEffect1 { file_path, save_game, package }
+                }
+                SaveGame::MassEffect2 { file_path, save_game, .. } => {
+                    SaveGame::MassEffect2 { file_path, save_game, package }
+                }
+                SaveGame::MassEffect3 { file_path, save_game, .. } => {
+                    SaveGame::MassEffect3 { file_path, save_game, package }
+                }
+                _ => bail!("Unsupported file"),
             }
+        } else if let Ok(magic_number) = header::<Me1MagicNumber>(&input) {
+            // ME1
+            let schema = me1_schema.context("The Mass Effect 1 database is not loaded yet")?;
+            let seed = Me1SaveGameSeed(schema);
+            let save_game = if magic_number.is_xbox360 {
+                unreal::Deserializer::from_be_bytes_seed(seed, &input)?
+            } else {
+                unreal::Deserializer::from_bytes_seed(seed, &input)?
+            };
+            SaveGame::MassEffect1 { file_path, save_game: RcRef::new(save_game), package: None }
         } else if header::<Me1LeMagicNumber>(&input).is_ok() {
             // ME1 Legendary
             SaveGame::MassEffect1Le {
@@ -333,35 +559,86 @@ impl SaveHandlerProvider {
             }
         } else if let Ok(save) = header::<Me2Version>(&input) {
             // ME2
+            notification = Self::checksum_notification(&input, save.is_xbox360);
             let save_game = if save.is_xbox360 {
                 unreal::Deserializer::from_be_bytes(&input)?
             } else {
                 unreal::Deserializer::from_bytes(&input)?
             };
-            SaveGame::MassEffect2 { file_path, save_game }
+            SaveGame::MassEffect2 { file_path, save_game, package: None }
         } else if header::<Me2LeVersion>(&input).is_ok() {
-            // ME2 Legendary
-            SaveGame::MassEffect2Le {
-                file_path,
-                save_game: unreal::Deserializer::from_bytes(&input)?,
+            let (save_game, remaining) = unreal::Deserializer::from_bytes_partial(&input)?;
+            if remaining == 0 {
+                // ME2LE PS4, pas de checksum
+                SaveGame::MassEffect2LePs4 { file_path, save_game }
+            } else {
+                // ME2 Legendary
+                notification = Self::checksum_notification(&input, false);
+                SaveGame::MassEffect2Le { file_path, save_game }
             }
         } else if let Ok(save) = header::<Me3Version>(&input) {
-            // ME3
-            let save_game = if save.is_xbox360 {
-                unreal::Deserializer::from_be_bytes(&input)?
+            if save.is_xbox360 {
+                // ME3 Xbox 360
+                notification = Self::checksum_notification(&input, true);
+                let save_game = unreal::Deserializer::from_be_bytes(&input)?;
+                SaveGame::MassEffect3 { file_path, save_game, package: None }
             } else {
-                unreal::Deserializer::from_bytes(&input)?
-            };
-            SaveGame::MassEffect3 { file_path, save_game }
+                let (save_game, remaining) = unreal::Deserializer::from_bytes_partial(&input)?;
+                if remaining == 0 {
+                    // ME3LE PS4, pas de checksum
+                    SaveGame::MassEffect3LePs4 { file_path, save_game }
+                } else {
+                    // ME3
+                    notification = Self::checksum_notification(&input, false);
+                    SaveGame::MassEffect3 { file_path, save_game, package: None }
+                }
+            }
         } else {
             bail!("Unsupported file");
         };
-        Ok(save_game)
+        Ok((save_game, notification))
+    }
+
+    // Les 4 derniers octets sont le CRC du reste de la save
+    // Un mauvais CRC n'empêche pas d'ouvrir la save, il est recalculé à l'enregistrement
+    fn checksum_notification(input: &[u8], is_xbox360: bool) -> &'static str {
+        let is_valid = input.len() >= 4 && {
+            let (data, checksum) = input.split_at(input.len() - 4);
+            let checksum = [checksum[0], checksum[1], checksum[2], checksum[3]];
+            let checksum = if is_xbox360 {
+                u32::from_be_bytes(checksum)
+            } else {
+                u32::from_le_bytes(checksum)
+            };
+
+            let crc = Crc::<u32>::new(&CRC_32_BZIP2);
+            crc.checksum(data) == checksum
+        };
+
+        if is_valid {
+            "Opened"
+        } else {
+            "Opened, wrong checksum fixed on save"
+        }
     }
 
     fn serialize(path: PathBuf, save_game: Rc<SaveGame>) -> Result<RpcFile> {
         let output = match save_game.as_ref() {
-            SaveGame::MassEffect1 { save_game, .. } => unreal::Serializer::to_vec(&save_game)?,
+            SaveGame::MassEffect1 { save_game, package, .. } => {
+                let is_xbox360 = package.is_some()
+                    || path
+                        .extension()
+                        .map(|ext| ext.eq_ignore_ascii_case("xbsav"))
+                        .unwrap_or_default();
+                save_game.borrow_mut().set_xbox360(is_xbox360);
+
+                let output = if is_xbox360 {
+                    unreal::Serializer::to_be_vec(&save_game)?
+                } else {
+                    unreal::Serializer::to_vec(&save_game)?
+                };
+                Self::write_package(package, output)?
+            }
             SaveGame::MassEffect1Le { save_game, .. } => {
                 let mut output = unreal::Serializer::to_vec(&save_game)?;
 
@@ -376,11 +653,12 @@ impl SaveHandlerProvider {
                 output
             }
             SaveGame::MassEffect1LePs4 { save_game, .. } => unreal::Serializer::to_vec(&save_game)?,
-            SaveGame::MassEffect2 { save_game, .. } => {
-                let is_xbox360 = path
-                    .extension()
-                    .map(|ext| ext.eq_ignore_ascii_case("xbsav"))
-                    .unwrap_or_default();
+            SaveGame::MassEffect2 { save_game, package, .. } => {
+                let is_xbox360 = package.is_some()
+                    || path
+                        .extension()
+                        .map(|ext| ext.eq_ignore_ascii_case("xbsav"))
+                        .unwrap_or_default();
 
                 let mut output = if is_xbox360 {
                     unreal::Serializer::to_be_vec(&save_game)?
@@ -397,8 +675,9 @@ impl SaveHandlerProvider {
                     u32::to_le_bytes(checksum)
                 };
                 output.extend(extend);
-                output
+                Self::write_package(package, output)?
             }
+            SaveGame::MassEffect2LePs4 { save_game, .. } => unreal::Serializer::to_vec(&save_game)?,
             SaveGame::MassEffect2Le { save_game, .. } => {
                 let mut output = unreal::Serializer::to_vec(&save_game)?;
 
@@ -407,11 +686,12 @@ impl SaveHandlerProvider {
                 output.extend(&u32::to_le_bytes(checksum));
                 output
             }
-            SaveGame::MassEffect3 { save_game, .. } => {
-                let is_xbox360 = path
-                    .extension()
-                    .map(|ext| ext.eq_ignore_ascii_case("xbsav"))
-                    .unwrap_or_default();
+            SaveGame::MassEffect3 { save_game, package, .. } => {
+                let is_xbox360 = package.is_some()
+                    || path
+                        .extension()
+                        .map(|ext| ext.eq_ignore_ascii_case("xbsav"))
+                        .unwrap_or_default();
 
                 let mut output = if is_xbox360 {
                     unreal::Serializer::to_be_vec(&save_game)?
@@ -428,8 +708,9 @@ impl SaveHandlerProvider {
                     u32::to_le_bytes(checksum)
                 };
                 output.extend(extend);
-                output
+                Self::write_package(package, output)?
             }
+            SaveGame::MassEffect3LePs4 { save_game, .. } => unreal::Serializer::to_vec(&save_game)?,
         };
 
         let rpc_file = RpcFile {
@@ -440,23 +721,88 @@ impl SaveHandlerProvider {
         Ok(rpc_file)
     }
 
+    // La save est réinjectée dans son package Xbox 360
+    fn write_package(package: &Option<RcRef<StfsPackage>>, output: Vec<u8>) -> Result<Vec<u8>> {
+        match package {
+            Some(package) => {
+                package.borrow_mut().write_save_file(&output)?;
+                Ok(package.borrow().to_vec())
+            }
+            None => Ok(output),
+        }
+    }
+
+    fn convert_to_legendary(ctx: &Context<Self>, save_game: &Rc<SaveGame>) {
+        if let SaveGame::MassEffect1 { ref file_path, ref save_game, .. } = **save_game {
+            let result =
+                Me1LeSaveGame::from_me1(&save_game.borrow()).context("Failed to convert the save");
+            match result {
+                Ok(me1_le_save_game) => {
+                    ctx.link().send_message(Msg::SaveConverted(SaveGame::MassEffect1Le {
+                        file_path: file_path.with_extension("pcsav"),
+                        save_game: me1_le_save_game.into(),
+                    }))
+                }
+                Err(err) => ctx.link().send_message(Msg::Error(err)),
+            }
+        }
+    }
+
     fn import_head_morph(ctx: &Context<Self>, callback: Callback<HeadMorph>) {
         ctx.link().send_future(async move {
             let handle_save = async {
                 let has_rpc_file = rpc::import_head_morph().await?;
+                let result = match has_rpc_file {
+                    Some(rpc_file) => Self::parse_head_morph(rpc_file.file.decode()?).map(Some)?,
+                    None => None,
+                };
+                Ok::<_, Error>(result)
+            };
+
+            match handle_save.await.context("Failed to import the head morph") {
+                Ok(Some(head_morph)) => Msg::HeadMorphImported(head_morph, callback),
+                Ok(None) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn parse_head_morph(file: Vec<u8>) -> Result<HeadMorph> {
+        if HeadMorph::is_gibbed(&file) {
+            // Gibbed's head morph
+            HeadMorph::from_gibbed(&file)
+        } else {
+            // TSE head morph
+            let ron = String::from_utf8(file)?;
+            Ok(ron::from_str(&ron)?)
+        }
+    }
+
+    fn head_morph_to_ron(head_morph: &RcRef<HeadMorph>) -> Result<Vec<u8>> {
+        let pretty_config = PrettyConfig::new().enumerate_arrays(true).new_line(String::from('\n'));
+        Ok(ron::ser::to_string_pretty(head_morph, pretty_config)?.into_bytes())
+    }
+
+    fn import_head_morph_from_save(
+        ctx: &Context<Self>, conversion_db: Rc<HeadMorphConversionDb>, to: HeadMorphGame,
+        reference: Option<RcRef<HeadMorph>>, callback: Callback<HeadMorph>,
+        me1_schema: Option<Rc<Me1Schema>>,
+    ) {
+        ctx.link().send_future(async move {
+            let handle_save = async {
+                let has_rpc_file = rpc::open_save(true).await?;
                 let result = match has_rpc_file {
                     Some(rpc_file) => {
-                        let file = rpc_file.file.decode()?;
-                        if file.starts_with(b"GIBBEDMASSEFFECT2HEADMORPH")
-                            || file.starts_with(b"GIBBEDMASSEFFECT3HEADMORPH")
-                        {
-                            // Gibbed's head morph
-                            unreal::Deserializer::from_bytes(&file[31..]).map(Some)?
-                        } else {
-                            // TSE head morph
-                            let ron = String::from_utf8(file)?;
-                            ron::from_str(&ron).map(Some)?
-                        }
+                        let RpcFile { path, file } = rpc_file;
+                        let (save_game, _) =
+                            Self::deserialize(path, file.decode()?, me1_schema.as_ref())?;
+                        let (from, _, head_morph) = Self::head_morph(&save_game)
+                            .context("This save does not have a head morph")?;
+
+                        let head_morph = head_morph.borrow().clone();
+                        let reference = reference.as_ref().map(|reference| reference.borrow());
+                        conversion_db.convert(&head_morph, from, to, reference.as_deref())?;
+                        Some(head_morph)
                     }
                     None => None,
                 };
@@ -471,16 +817,99 @@ impl SaveHandlerProvider {
         });
     }
 
+    fn head_morph(save_game: &SaveGame) -> Option<(HeadMorphGame, bool, RcRef<HeadMorph>)> {
+        let (game, is_female, head_morph) = match save_game {
+            SaveGame::MassEffect1 { .. } => return None,
+            SaveGame::MassEffect1Le { save_game, .. } => {
+                let save_data = RcRef::clone(&save_game.borrow().save_data);
+                let player = RcRef::clone(&save_data.borrow().player);
+                let player = player.borrow();
+                let head_morph = player.head_morph.borrow().clone();
+                (HeadMorphGame::Me1, player.is_female(), head_morph)
+            }
+            SaveGame::MassEffect1LePs4 { save_game, .. } => {
+                let player = RcRef::clone(&save_game.borrow().player);
+                let player = player.borrow();
+                let head_morph = player.head_morph.borrow().clone();
+                (HeadMorphGame::Me1, player.is_female(), head_morph)
+            }
+            SaveGame::MassEffect2 { save_game, .. } => {
+                let player = RcRef::clone(&save_game.borrow().player);
+                let player = player.borrow();
+                let head_morph = player.appearance.borrow().head_morph.borrow().clone();
+                (HeadMorphGame::Me2, player.is_female(), head_morph)
+            }
+            SaveGame::MassEffect2Le { save_game, .. }
+            | SaveGame::MassEffect2LePs4 { save_game, .. } => {
+                let player = RcRef::clone(&save_game.borrow().player);
+                let player = player.borrow();
+                let head_morph = player.appearance.borrow().head_morph.borrow().clone();
+                (HeadMorphGame::Me2, player.is_female(), head_morph)
+            }
+            SaveGame::MassEffect3 { save_game, .. }
+            | SaveGame::MassEffect3LePs4 { save_game, .. } => {
+                let player = RcRef::clone(&save_game.borrow().player);
+                let player = player.borrow();
+                let head_morph = player.appearance.borrow().head_morph.borrow().clone();
+                (HeadMorphGame::Me3, player.is_female(), head_morph)
+            }
+        };
+        head_morph.map(|head_morph| (game, is_female, head_morph))
+    }
+
+    // Le jeu plante au chargement si le head morph ne correspond pas à son modèle,
+    // la base de données peut se tromper, l'utilisateur a le dernier mot
+    fn validate_head_morph(&self, save_game: &SaveGame) -> Option<(HeadMorphGame, Vec<String>)> {
+        let validation_db = self.validation_db.as_ref()?;
+        let (game, is_female, head_morph) = Self::head_morph(save_game)?;
+
+        let report = validation_db.validate(&head_morph.borrow(), game, is_female);
+        (!report.errors.is_empty()).then(|| (game, report.errors))
+    }
+
+    fn save_warning(ctx: &Context<Self>, (game, errors): &(HeadMorphGame, Vec<String>)) -> Html {
+        let errors = errors.iter().map(|error| html! { <p>{ format_code(error) }</p> });
+        html! {
+            <div class="absolute w-screen h-[calc(100vh-28px)] grid place-content-center bg-white/30 z-50">
+                <div class="border border-default-border bg-default-bg max-w-xl">
+                    <div class="px-1 bg-theme-tab select-none">{"Warning"}</div>
+                    <div class="p-1 pt-0.5">
+                        { format!("The head morph may not be compatible with {}, the game may crash on load.", game.name()) }
+                        <hr class="my-0.5 border-t border-default-border" />
+                        { for errors }
+                        <hr class="my-0.5 border-t border-default-border" />
+                        <div class="flex gap-1">
+                            <button class="button" onclick={ctx.link().callback(|_| Msg::SaveAnyway)}>
+                                {"Save anyway"}
+                            </button>
+                            <button class="button" onclick={ctx.link().callback(|_| Msg::CancelSave)}>
+                                {"Cancel"}
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            </div>
+        }
+    }
+
     fn export_head_morph(ctx: &Context<Self>, head_morph: RcRef<HeadMorph>) {
         ctx.link().send_future(async move {
             let handle_save = async {
                 let has_path = rpc::export_head_morph_dialog().await?;
                 let cancelled = match has_path {
                     Some(path) => {
-                        let pretty_config =
-                            PrettyConfig::new().enumerate_arrays(true).new_line(String::from('\n'));
-
-                        let output = ron::ser::to_string_pretty(&head_morph, pretty_config)?;
+                        let extension = path.extension().and_then(|ext| ext.to_str());
+                        let output = match extension {
+                            // Gibbed's head morph
+                            Some("me2headmorph") => {
+                                head_morph.borrow().to_gibbed(HeadMorphGame::Me2)?
+                            }
+                            Some("me3headmorph") => {
+                                head_morph.borrow().to_gibbed(HeadMorphGame::Me3)?
+                            }
+                            // TSE head morph
+                            _ => Self::head_morph_to_ron(&head_morph)?,
+                        };
                         let rpc_file = RpcFile {
                             path,
                             file: Base64File {
@@ -504,14 +933,155 @@ impl SaveHandlerProvider {
         });
     }
 
+    fn import_head_morph_obj(
+        ctx: &Context<Self>, head_morph: RcRef<HeadMorph>, callback: Callback<()>,
+    ) {
+        ctx.link().send_future(async move {
+            let handle_save = async {
+                let has_rpc_file = rpc::import_head_morph_obj().await?;
+                let imported = match has_rpc_file {
+                    Some(rpc_file) => {
+                        let obj = String::from_utf8(rpc_file.file.decode()?)?;
+                        head_morph_obj::import_obj(&head_morph.borrow(), &obj)?;
+                        true
+                    }
+                    None => false,
+                };
+                Ok::<_, Error>(imported)
+            };
+
+            match handle_save.await.context("Failed to import the OBJ") {
+                Ok(true) => Msg::HeadMorphObjImported(callback),
+                Ok(false) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn export_head_morph_obj(
+        ctx: &Context<Self>, head_morph: RcRef<HeadMorph>, topology_db: Rc<HeadMorphTopologyDb>,
+    ) {
+        ctx.link().send_future(async move {
+            let handle_save = async {
+                let has_path = rpc::export_head_morph_obj_dialog().await?;
+                let cancelled = match has_path {
+                    Some(path) => {
+                        let output = head_morph_obj::export_obj(&head_morph.borrow(), &topology_db);
+                        let rpc_file = RpcFile {
+                            path,
+                            file: Base64File {
+                                unencoded_size: output.len(),
+                                base64: base64::encode(output),
+                            },
+                        };
+                        rpc::save_file(rpc_file).await?;
+                        false
+                    }
+                    None => true,
+                };
+                Ok::<_, Error>(cancelled)
+            };
+
+            match handle_save.await.context("Failed to export the OBJ") {
+                Ok(false) => Msg::HeadMorphExported,
+                Ok(true) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn load_head_morph_library(
+        ctx: &Context<Self>, callback: Callback<Vec<(PathBuf, HeadMorphMeta)>>,
+    ) {
+        ctx.link().send_future(async move {
+            let handle_library = async {
+                let mut library = Vec::new();
+                for RpcFile { path, file } in rpc::head_morph_library().await? {
+                    let ron = String::from_utf8(file.decode()?)?;
+                    let meta: HeadMorphMeta = ron::from_str(&ron)
+                        .with_context(|| format!("Invalid file: {}", path.display()))?;
+                    library.push((path, meta));
+                }
+                library.sort_by_key(|(_, meta)| meta.name.to_lowercase());
+                Ok::<_, Error>(library)
+            };
+
+            match handle_library.await.context("Failed to load the head morph library") {
+                Ok(library) => Msg::HeadMorphLibraryLoaded(library, callback),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn add_head_morph_to_library(
+        ctx: &Context<Self>, meta: HeadMorphMeta, head_morph: RcRef<HeadMorph>,
+        callback: Callback<()>,
+    ) {
+        ctx.link().send_future(async move {
+            let handle_library = async {
+                let meta_ron = ron::to_string(&meta)?.into_bytes();
+                let head_morph = Self::head_morph_to_ron(&head_morph)?;
+                let entry = HeadMorphLibraryEntry {
+                    file_stem: meta.file_stem(),
+                    meta: Base64File {
+                        unencoded_size: meta_ron.len(),
+                        base64: base64::encode(meta_ron),
+                    },
+                    head_morph: Base64File {
+                        unencoded_size: head_morph.len(),
+                        base64: base64::encode(head_morph),
+                    },
+                };
+                rpc::add_to_head_morph_library(entry).await
+            };
+
+            match handle_library.await.context("Failed to add the head morph to the library") {
+                Ok(()) => Msg::HeadMorphLibraryChanged("Added", callback),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn remove_head_morph_from_library(
+        ctx: &Context<Self>, meta_path: PathBuf, callback: Callback<()>,
+    ) {
+        ctx.link().send_future(async move {
+            let result = rpc::remove_from_head_morph_library(meta_path).await;
+            match result.context("Failed to remove the head morph from the library") {
+                Ok(()) => Msg::HeadMorphLibraryChanged("Removed", callback),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn import_head_morph_from_library(
+        ctx: &Context<Self>, meta_path: PathBuf, callback: Callback<HeadMorph>,
+    ) {
+        ctx.link().send_future(async move {
+            let handle_library = async {
+                let rpc_file = rpc::open_head_morph_from_library(meta_path).await?;
+                Self::parse_head_morph(rpc_file.file.decode()?)
+            };
+
+            match handle_library.await.context("Failed to import the head morph") {
+                Ok(head_morph) => Msg::HeadMorphImported(head_morph, callback),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
     fn change_theme(&self) {
         if let Some(ref save_game) = self.save_handler.save_game {
             let theme = match save_game.as_ref() {
                 SaveGame::MassEffect1 { .. }
                 | SaveGame::MassEffect1Le { .. }
                 | SaveGame::MassEffect1LePs4 { .. } => Theme::MassEffect1,
-                SaveGame::MassEffect2 { .. } | SaveGame::MassEffect2Le { .. } => Theme::MassEffect2,
-                SaveGame::MassEffect3 { .. } => Theme::MassEffect3,
+                SaveGame::MassEffect2 { .. }
+                | SaveGame::MassEffect2Le { .. }
+                | SaveGame::MassEffect2LePs4 { .. } => Theme::MassEffect2,
+                SaveGame::MassEffect3 { .. } | SaveGame::MassEffect3LePs4 { .. } => {
+                    Theme::MassEffect3
+                }
             };
 
             let body = utils::document().body().unwrap();
@@ -522,3 +1092,64 @@ impl SaveHandlerProvider {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn open_ps4_and_wrong_checksum() -> Result<()> {
+        let files = [
+            ("test/ME2Save.pcsav", false),
+            ("test/ME2Save360.xbsav", true),
+            ("test/ME2LeSave.pcsav", false),
+            ("test/ME3Save.pcsav", false),
+            ("test/ME3Save360.xbsav", true),
+        ];
+
+        for (file, is_xbox360) in files {
+            let mut input = fs::read(file)?;
+            let (_, notification) =
+                SaveHandlerProvider::deserialize(file.into(), input.clone(), None)?;
+            assert_eq!(notification, "Opened");
+
+            // A wrong checksum does not prevent the save from being opened
+            let last = input.len() - 1;
+            input[last] ^= 0xFF;
+            let (save_game, notification) =
+                SaveHandlerProvider::deserialize(file.into(), input.clone(), None)?;
+            assert_eq!(notification, "Opened, wrong checksum fixed on save");
+
+            // The checksum is fixed on save
+            let path = PathBuf::from(file);
+            let output = SaveHandlerProvider::serialize(path, Rc::new(save_game))?;
+            let output = base64::decode(output.file.base64)?;
+            input[last] ^= 0xFF;
+            assert!(output == input, "{}", file);
+            assert_eq!(SaveHandlerProvider::checksum_notification(&output, is_xbox360), "Opened");
+        }
+
+        // PS4 saves have no checksum
+        let (save_game, notification) = SaveHandlerProvider::deserialize(
+            "ME2LeSave.ps4sav".into(),
+            fs::read("test/ME2LeSave.ps4sav")?,
+            None,
+        )?;
+        assert!(matches!(save_game, SaveGame::MassEffect2LePs4 { .. }));
+        assert_eq!(notification, "Opened");
+
+        let (save_game, notification) = SaveHandlerProvider::deserialize(
+            "ME3LeSave.ps4sav".into(),
+            fs::read("test/ME3LeSave.ps4sav")?,
+            None,
+        )?;
+        assert!(matches!(save_game, SaveGame::MassEffect3LePs4 { .. }));
+        assert_eq!(notification, "Opened");
+
+        Ok(())
+    }
+}
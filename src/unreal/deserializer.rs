@@ -20,6 +20,38 @@ impl<'de> Deserializer<'de> {
         T::deserialize(&mut deserializer)
     }
 
+    // Renvoie aussi le nombre d'octets non lus à la fin du tampon
+    pub fn from_bytes_partial<T: Deserialize<'de>>(input: &'de [u8]) -> Result<(T, usize)> {
+        let mut deserializer = Deserializer { input, is_le: true };
+        let value = T::deserialize(&mut deserializer)?;
+        Ok((value, deserializer.input.len()))
+    }
+
+    // Le tampon doit être lu en entier
+    pub fn from_bytes_seed<T: DeserializeSeed<'de>>(seed: T, input: &'de [u8]) -> Result<T::Value> {
+        Self::from_seed(seed, input, true)
+    }
+
+    pub fn from_be_bytes_seed<T: DeserializeSeed<'de>>(
+        seed: T, input: &'de [u8],
+    ) -> Result<T::Value> {
+        Self::from_seed(seed, input, false)
+    }
+
+    fn from_seed<T: DeserializeSeed<'de>>(
+        seed: T, input: &'de [u8], is_le: bool,
+    ) -> Result<T::Value> {
+        let mut deserializer = Deserializer { input, is_le };
+        let value = seed.deserialize(&mut deserializer)?;
+        if !deserializer.input.is_empty() {
+            return Err(super::Error::Message(format!(
+                "{} bytes left after deserialization",
+                deserializer.input.len()
+            )));
+        }
+        Ok(value)
+    }
+
     pub fn from_be_bytes<T: Deserialize<'de>>(input: &'de [u8]) -> Result<T> {
         let mut deserializer = Deserializer { input, is_le: false };
         T::deserialize(&mut deserializer)
@@ -139,7 +171,8 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
 
         let mut string = if len < 0 {
             // Unicode
-            let string_len = (len.abs() * 2) as usize;
+            let string_len =
+                (len.unsigned_abs() as usize).checked_mul(2).ok_or(super::Error::Eof)?;
             let bytes = self.read(string_len)?.to_owned();
 
             let (decoded, _, had_errors) =